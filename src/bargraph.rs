@@ -0,0 +1,317 @@
+//! Horizontal and vertical bar graphs, with the resolution of one pixel.
+//!
+//! [BarGraph] uploads a set of partial-fill glyphs (4 for horizontal bars, filling 1 to 4 columns
+//! of the character; 7 for vertical bars, filling 1 to 7 rows) to CGRAM and uses them to draw the
+//! bar in the given rectangle of the display. Fully lit cells use the full block of the character
+//! ROM (`0xff`). Only the CGRAM locations starting at
+//! [BarGraph::set_glyph_location] are used, so the rest of CGRAM stays available for other glyphs.
+//! Glyphs are uploaded on the first draw only (again after [BarGraph::invalidate]), so several bar
+//! graphs of the same orientation could share the same locations.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::bargraph::BarGraph;
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! // 16 characters (80 pixels) wide bar in the second row
+//! let mut bar = BarGraph::horizontal(Geometry::new(16, 2), 0, 1, 16, 1);
+//! bar.set_range(0, 1000);
+//! bar.set_peak_hold(10);
+//!
+//! bar.set_value(500);
+//! bar.draw(&mut lcd);
+//! ```
+use crate::shadow::{CHARACTER_HEIGHT, CHARACTER_WIDTH};
+use crate::{Delay, Display, Geometry, Hardware, Instruction};
+
+/// Mask of the pixels in the glyph row.
+const ROW_MASK: u8 = 0x1f;
+/// ROM character of the full block, used for fully lit cells.
+const FULL_BLOCK: u8 = 0xff;
+
+/// Direction in which the bar grows.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Orientation {
+    /// Bar grows to the right (to the left for negative values of bipolar bars).
+    Horizontal,
+    /// Bar grows up (down for negative values of bipolar bars).
+    Vertical,
+}
+
+/// Bar graph, see the [module](self) documentation.
+pub struct BarGraph {
+    geometry: Geometry,
+    orientation: Orientation,
+    col: u8,
+    row: u8,
+    width: u8,
+    height: u8,
+    location: u8,
+    min: i32,
+    max: i32,
+    bipolar: bool,
+    /// Length of the bar in pixels, negative for negative values of bipolar bars.
+    pixels: i32,
+    hold: u16,
+    peak: i32,
+    /// Number of updates the peak is held for.
+    peak_left: u16,
+    /// Uploaded glyph set (`true` if mirrored for negative values), `None` if unknown.
+    uploaded: Option<bool>,
+    /// Bar and peak length as of the last draw, `None` if unknown.
+    shown: Option<(i32, i32)>,
+}
+
+impl BarGraph {
+    /// Create a new bar growing to the right, in the rectangle starting at the given column
+    /// (`col`) and row (`row`), `width` characters wide and `height` characters high, on the
+    /// display of the given size (`geometry`).
+    pub fn horizontal(geometry: Geometry, col: u8, row: u8, width: u8, height: u8) -> Self {
+        BarGraph::new(
+            Orientation::Horizontal,
+            geometry,
+            (col, row),
+            (width, height),
+        )
+    }
+
+    /// Create a new bar growing up, in the rectangle starting at the given column (`col`) and row
+    /// (`row`, top one), `width` characters wide and `height` characters high, on the display of
+    /// the given size (`geometry`).
+    pub fn vertical(geometry: Geometry, col: u8, row: u8, width: u8, height: u8) -> Self {
+        BarGraph::new(Orientation::Vertical, geometry, (col, row), (width, height))
+    }
+
+    fn new(
+        orientation: Orientation,
+        geometry: Geometry,
+        (col, row): (u8, u8),
+        (width, height): (u8, u8),
+    ) -> Self {
+        assert!(width > 0 && height > 0);
+        assert!(
+            u16::from(col) + u16::from(width) <= u16::from(geometry.columns())
+                && u16::from(row) + u16::from(height) <= u16::from(geometry.rows())
+        );
+        BarGraph {
+            geometry,
+            orientation,
+            col,
+            row,
+            width,
+            height,
+            location: 0,
+            min: 0,
+            max: 100,
+            bipolar: false,
+            pixels: 0,
+            hold: 0,
+            peak: 0,
+            peak_left: 0,
+            uploaded: None,
+            shown: None,
+        }
+    }
+
+    /// Use CGRAM locations starting at the given one (`location`) for the glyphs: `location` to
+    /// `location + 3` for horizontal bars, `location` to `location + 6` for vertical bars (so
+    /// `location` must be 0 or 1).
+    pub fn set_glyph_location(&mut self, location: u8) {
+        assert!(u16::from(location) + u16::from(self.unit() - 1) <= 8);
+        self.location = location;
+        self.invalidate();
+    }
+
+    /// Range of the CGRAM locations used for the glyphs.
+    pub fn glyph_locations(&self) -> core::ops::Range<u8> {
+        self.location..self.location + self.unit() - 1
+    }
+
+    /// Set the range of values (`min` to `max`), values outside the range are clamped. Empty bar
+    /// is shown for `min` and full bar for `max`. For bipolar bars, the range must include zero.
+    pub fn set_range(&mut self, min: i32, max: i32) {
+        assert!(min < max && (!self.bipolar || (min < 0 && max > 0)));
+        self.min = min;
+        self.max = max;
+    }
+
+    /// Draw the bar from the middle of the rectangle (`bipolar`), to the right or up for positive
+    /// values and to the left or down for negative values. The range set by [BarGraph::set_range]
+    /// must include zero, `min` and `max` map to the ends of the rectangle. Length of the rectangle
+    /// must be even.
+    pub fn set_bipolar(&mut self, bipolar: bool) {
+        assert!(!bipolar || (self.min < 0 && self.max > 0 && self.cells().is_multiple_of(2)));
+        self.bipolar = bipolar;
+    }
+
+    /// Show the highest value (the farthest from zero for bipolar bars) for the given number of
+    /// updates (`updates`, [BarGraph::set_value] calls) after it was reached. Peak is shown as the
+    /// part of the bar in its character, 0 disables the peak marker.
+    pub fn set_peak_hold(&mut self, updates: u16) {
+        self.hold = updates;
+        self.peak = self.pixels;
+        self.peak_left = 0;
+    }
+
+    /// Set the value (`value`) to show.
+    pub fn set_value(&mut self, value: i32) {
+        let value = value.clamp(self.min, self.max);
+        let length = i64::from(self.cells()) * i64::from(self.unit());
+        let pixels = if !self.bipolar {
+            (i64::from(value) - i64::from(self.min)) * length
+                / (i64::from(self.max) - i64::from(self.min))
+        } else if value >= 0 {
+            i64::from(value) * (length / 2) / i64::from(self.max)
+        } else {
+            -(i64::from(value) * (length / 2) / i64::from(self.min))
+        };
+        self.pixels = pixels as i32;
+
+        if self.peak_left > 0
+            && (self.peak < 0) == (self.pixels < 0)
+            && self.peak.abs() > self.pixels.abs()
+        {
+            self.peak_left -= 1;
+        } else {
+            self.peak = self.pixels;
+            self.peak_left = self.hold;
+        }
+    }
+
+    /// Length of the bar in pixels, negative for negative values of bipolar bars.
+    pub fn pixels(&self) -> i32 {
+        self.pixels
+    }
+
+    /// Upload the bar glyphs again and repaint the whole bar on the next draw.
+    pub fn invalidate(&mut self) {
+        self.uploaded = None;
+        self.shown = None;
+    }
+
+    /// Update the display (`lcd`) if the bar has changed since the last draw.
+    pub fn draw<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        let peak = if self.hold > 0 { self.peak } else { 0 };
+        if self.shown == Some((self.pixels, peak)) {
+            return;
+        }
+        let mirrored = self.pixels < 0 || peak < 0;
+        if self.uploaded != Some(mirrored) {
+            self.upload(lcd, mirrored);
+        }
+
+        match self.orientation {
+            Orientation::Horizontal => {
+                for row in self.row..self.row + self.height {
+                    let address = self.geometry.address(self.col, row);
+                    lcd.instruction(Instruction::SetDDRamAddr(address));
+                    for cell in 0..self.width {
+                        lcd.write_raw(self.character(cell, peak));
+                    }
+                }
+            }
+            Orientation::Vertical => {
+                for cell in 0..self.height {
+                    let data = self.character(cell, peak);
+                    let address = self
+                        .geometry
+                        .address(self.col, self.row + self.height - 1 - cell);
+                    lcd.instruction(Instruction::SetDDRamAddr(address));
+                    for _ in 0..self.width {
+                        lcd.write_raw(data);
+                    }
+                }
+            }
+        }
+        self.shown = Some((self.pixels, peak));
+    }
+
+    /// Character of the given cell (`cell`, counting from the left or from the bottom).
+    fn character(&self, cell: u8, peak: i32) -> u8 {
+        let unit = i32::from(self.unit());
+        let cells = i32::from(self.cells());
+        // Cell index counting from the zero position, in the direction of the bar
+        let (index, pixels, peak) = if !self.bipolar {
+            (i32::from(cell), self.pixels, peak)
+        } else if self.pixels < 0 || peak < 0 {
+            (cells / 2 - 1 - i32::from(cell), -self.pixels, -peak)
+        } else {
+            (i32::from(cell) - cells / 2, self.pixels, peak)
+        };
+        if index < 0 {
+            return b' ';
+        }
+        let start = index * unit;
+        let fill = if pixels > start {
+            (pixels - start).min(unit)
+        } else if peak > start && peak <= start + unit {
+            peak - start
+        } else {
+            0
+        };
+        if fill == 0 {
+            b' '
+        } else if fill == unit {
+            FULL_BLOCK
+        } else {
+            self.location + fill as u8 - 1
+        }
+    }
+
+    fn upload<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>, mirrored: bool) {
+        for fill in 1..self.unit() {
+            let mut glyph = [0; 8];
+            match self.orientation {
+                Orientation::Horizontal => {
+                    let line = if mirrored {
+                        ROW_MASK >> (CHARACTER_WIDTH - fill)
+                    } else {
+                        (ROW_MASK << (CHARACTER_WIDTH - fill)) & ROW_MASK
+                    };
+                    glyph = [line; 8];
+                }
+                Orientation::Vertical => {
+                    for (index, line) in glyph.iter_mut().enumerate() {
+                        let filled = if mirrored {
+                            (index as u8) < fill
+                        } else {
+                            index as u8 >= CHARACTER_HEIGHT - fill
+                        };
+                        if filled {
+                            *line = ROW_MASK;
+                        }
+                    }
+                }
+            }
+            lcd.upload_character(self.location + fill - 1, glyph);
+        }
+        self.uploaded = Some(mirrored);
+    }
+
+    /// Number of characters along the bar.
+    fn cells(&self) -> u8 {
+        match self.orientation {
+            Orientation::Horizontal => self.width,
+            Orientation::Vertical => self.height,
+        }
+    }
+
+    /// Number of pixels in one character along the bar.
+    fn unit(&self) -> u8 {
+        match self.orientation {
+            Orientation::Horizontal => CHARACTER_WIDTH,
+            Orientation::Vertical => CHARACTER_HEIGHT,
+        }
+    }
+}
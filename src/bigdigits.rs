@@ -0,0 +1,293 @@
+//! Big digits, spanning several rows and built from the custom glyphs.
+//!
+//! [BigDigits] uploads the segment glyphs of the [Font] to all 8 CGRAM locations and prints
+//! digits, colon, minus and decimal point (see [BigDigits::print]) at any position. Fonts only use
+//! the custom glyphs and ASCII characters, so they look the same with any character ROM.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::bigdigits::{BigDigits, Font};
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! let mut digits = BigDigits::new(Geometry::new(20, 4), &Font::FOUR_ROWS);
+//! // Centered clock
+//! let col = (20 - digits.width("12:34")) / 2;
+//! digits.print(&mut lcd, col, 0, "12:34");
+//! ```
+use crate::{Delay, Display, Geometry, Hardware, Instruction};
+
+/// Blank cell.
+const SP: u8 = b' ';
+
+/// Big digit font: 8 segment glyphs and the characters (glyph codes 0 to 7 or ASCII characters)
+/// of each symbol, row by row.
+pub struct Font {
+    rows: u8,
+    glyphs: [[u8; 8]; 8],
+    digits: [&'static [u8]; 10],
+    colon: &'static [u8],
+    minus: &'static [u8],
+    point: &'static [u8],
+}
+
+impl Font {
+    /// 3x2 characters digits, with rounded corners.
+    pub const TWO_ROWS: Font = {
+        const LT: u8 = 0;
+        const UB: u8 = 1;
+        const RT: u8 = 2;
+        const LL: u8 = 3;
+        const LB: u8 = 4;
+        const LR: u8 = 5;
+        const UM: u8 = 6;
+        const FB: u8 = 7;
+        Font {
+            rows: 2,
+            glyphs: [
+                [0x07, 0x0f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00],
+                [0x1c, 0x1e, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x0f, 0x07],
+                [0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1e, 0x1c],
+                [0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+            ],
+            digits: [
+                &[LT, UB, RT, LL, LB, LR],
+                &[UB, RT, SP, LB, FB, LB],
+                &[UM, UM, RT, LL, LB, LB],
+                &[UM, UM, RT, LB, LB, LR],
+                &[LL, LB, FB, SP, SP, FB],
+                &[FB, UM, UM, LB, LB, LR],
+                &[LT, UM, UM, LL, LB, LR],
+                &[UB, UB, RT, SP, SP, FB],
+                &[LT, UM, RT, LL, LB, LR],
+                &[LT, UM, RT, SP, SP, FB],
+            ],
+            colon: b"..",
+            minus: &[LB, LB, SP, SP],
+            point: &[SP, LB],
+        }
+    };
+
+    /// 3x3 characters digits, with rounded corners.
+    pub const THREE_ROWS: Font = {
+        const TH: u8 = 0;
+        const BH: u8 = 1;
+        const FB: u8 = 2;
+        const TL: u8 = 3;
+        const TR: u8 = 4;
+        const BL: u8 = 5;
+        const BR: u8 = 6;
+        const DT: u8 = 7;
+        Font {
+            rows: 3,
+            glyphs: [
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00],
+                [0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x07, 0x0f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1c, 0x1e, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x0f, 0x07],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1e, 0x1c],
+                [0x00, 0x00, 0x0e, 0x0e, 0x0e, 0x00, 0x00, 0x00],
+            ],
+            digits: [
+                &[TL, TH, TR, FB, SP, FB, BL, BH, BR],
+                &[TH, FB, SP, SP, FB, SP, BH, FB, BH],
+                &[TH, TH, TR, TL, TH, BR, FB, BH, BH],
+                &[TH, TH, TR, SP, TH, FB, BH, BH, BR],
+                &[FB, SP, FB, BL, TH, FB, SP, SP, FB],
+                &[FB, TH, TH, BL, TH, TR, BH, BH, BR],
+                &[TL, TH, TH, FB, TH, TR, BL, BH, BR],
+                &[TH, TH, FB, SP, SP, FB, SP, SP, FB],
+                &[TL, TH, TR, FB, TH, FB, BL, BH, BR],
+                &[TL, TH, TR, BL, TH, FB, BH, BH, BR],
+            ],
+            colon: &[DT, SP, DT],
+            minus: &[SP, SP, TH, TH, SP, SP],
+            point: &[SP, SP, BH],
+        }
+    };
+
+    /// 3x4 characters digits, with rounded corners.
+    pub const FOUR_ROWS: Font = {
+        const TH: u8 = 0;
+        const BH: u8 = 1;
+        const FB: u8 = 2;
+        const TL: u8 = 3;
+        const TR: u8 = 4;
+        const BL: u8 = 5;
+        const BR: u8 = 6;
+        const DT: u8 = 7;
+        Font {
+            rows: 4,
+            glyphs: [
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x00, 0x00, 0x00, 0x00],
+                [0x00, 0x00, 0x00, 0x00, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x07, 0x0f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1c, 0x1e, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x0f, 0x07],
+                [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1e, 0x1c],
+                [0x00, 0x00, 0x0e, 0x0e, 0x0e, 0x00, 0x00, 0x00],
+            ],
+            digits: [
+                &[TL, TH, TR, FB, SP, FB, FB, SP, FB, BL, BH, BR],
+                &[TH, FB, SP, SP, FB, SP, SP, FB, SP, BH, FB, BH],
+                &[TL, TH, TR, SP, BH, BR, TL, TH, SP, BL, BH, BH],
+                &[TH, TH, TR, SP, BH, BR, SP, TH, TR, BH, BH, BR],
+                &[FB, SP, FB, FB, SP, FB, TH, TH, FB, SP, SP, FB],
+                &[FB, TH, TH, BL, BH, TR, SP, SP, FB, BH, BH, BR],
+                &[TL, TH, TH, FB, BH, TR, FB, SP, FB, BL, BH, BR],
+                &[TH, TH, FB, SP, SP, FB, SP, SP, FB, SP, SP, FB],
+                &[TL, TH, TR, BL, BH, BR, TL, TH, TR, BL, BH, BR],
+                &[TL, TH, TR, BL, BH, FB, SP, SP, FB, BH, BH, BR],
+            ],
+            colon: &[SP, DT, DT, SP],
+            minus: &[SP, SP, BH, BH, SP, SP, SP, SP],
+            point: &[SP, SP, SP, BH],
+        }
+    };
+
+    /// Number of rows of each symbol.
+    pub fn rows(&self) -> u8 {
+        self.rows
+    }
+
+    /// Characters of the given symbol (`symbol`), `None` if it is not supported.
+    fn cells(&self, symbol: char) -> Option<&'static [u8]> {
+        match symbol {
+            '0'..='9' => Some(self.digits[symbol as usize - '0' as usize]),
+            ':' => Some(self.colon),
+            '-' => Some(self.minus),
+            '.' => Some(self.point),
+            _ => None,
+        }
+    }
+}
+
+/// Big digit printer, see the [module](self) documentation.
+pub struct BigDigits {
+    geometry: Geometry,
+    font: &'static Font,
+    spacing: u8,
+    /// If font glyphs are in CGRAM.
+    uploaded: bool,
+}
+
+impl BigDigits {
+    /// Create a new big digit printer for the display of the given size (`geometry`), using the
+    /// given font (`font`). Panics if the font does not fit the display.
+    pub fn new(geometry: Geometry, font: &'static Font) -> Self {
+        assert!(font.rows <= geometry.rows());
+        BigDigits {
+            geometry,
+            font,
+            spacing: 1,
+            uploaded: false,
+        }
+    }
+
+    /// Set the number of blank columns between symbols (`columns`, 1 by default).
+    pub fn set_spacing(&mut self, columns: u8) {
+        self.spacing = columns;
+    }
+
+    /// Number of columns [BigDigits::print] would use for the given text (`text`).
+    pub fn width(&self, text: &str) -> u8 {
+        let mut width = 0u8;
+        for (index, symbol) in text.chars().enumerate() {
+            if index > 0 {
+                width = width.saturating_add(self.spacing);
+            }
+            width = width.saturating_add(self.symbol_width(symbol));
+        }
+        width
+    }
+
+    /// Upload font glyphs to CGRAM. Done by the first [BigDigits::print], so only needed if CGRAM
+    /// was used for something else in the meantime.
+    pub fn upload<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        for (location, glyph) in self.font.glyphs.iter().enumerate() {
+            lcd.upload_character(location as u8, *glyph);
+        }
+        self.uploaded = true;
+    }
+
+    /// Print the text (`text`) with the top left corner at the given column (`col`) and row
+    /// (`row`). Digits, colon, minus and decimal point are supported, other characters (like space)
+    /// are left blank, as wide as a digit. Columns past the display width are dropped. Returns the
+    /// column after the text.
+    pub fn print<HW: Hardware + Delay>(
+        &mut self,
+        lcd: &mut Display<HW>,
+        col: u8,
+        row: u8,
+        text: &str,
+    ) -> u8 {
+        assert!(row + self.font.rows <= self.geometry.rows());
+        if !self.uploaded {
+            self.upload(lcd);
+        }
+        let mut col = col;
+        for (index, symbol) in text.chars().enumerate() {
+            if index > 0 {
+                self.fill(lcd, col, row, self.spacing, None);
+                col = col.saturating_add(self.spacing);
+            }
+            let width = self.symbol_width(symbol);
+            self.fill(lcd, col, row, width, self.font.cells(symbol));
+            col = col.saturating_add(width);
+        }
+        col
+    }
+
+    /// Forget that font glyphs are in CGRAM, so next print uploads them again.
+    pub fn invalidate(&mut self) {
+        self.uploaded = false;
+    }
+
+    /// Write the given characters (`cells`, blanks if `None`) to the block `width` columns wide.
+    fn fill<HW: Hardware + Delay>(
+        &self,
+        lcd: &mut Display<HW>,
+        col: u8,
+        row: u8,
+        width: u8,
+        cells: Option<&[u8]>,
+    ) {
+        let columns = self.geometry.columns();
+        if col >= columns || width == 0 {
+            return;
+        }
+        let visible = width.min(columns - col);
+        for line in 0..self.font.rows {
+            let address = self.geometry.address(col, row + line);
+            lcd.instruction(Instruction::SetDDRamAddr(address));
+            for index in 0..visible {
+                let data = cells.map_or(SP, |cells| {
+                    cells[usize::from(line) * usize::from(width) + usize::from(index)]
+                });
+                lcd.write_raw(data);
+            }
+        }
+    }
+
+    fn symbol_width(&self, symbol: char) -> u8 {
+        let cells = self.font.cells(symbol).unwrap_or(self.font.digits[0]);
+        (cells.len() / usize::from(self.font.rows)) as u8
+    }
+}
@@ -0,0 +1,132 @@
+//! Decode sigrok CSV export of the HD44780 bus capture into instructions and text.
+//!
+//! ```text
+//! lcd-decode <capture.csv> --rs <channel> --e <channel> --data <channels> [--rw <channel>]
+//!     [--oscillator <kHz>]
+//! ```
+//!
+//! Data channels are given as comma separated list, D0-D7 (8-bit bus) or D4-D7 (4-bit bus).
+//! Exits with non-zero status if any protocol errors are found.
+use lcd::checker::Constraints;
+use lcd::sigrok::{decode_csv, Event, EventKind, Pinout};
+use lcd::Instruction;
+use std::fmt::Write as _;
+use std::process::exit;
+use std::{env, fs};
+
+const USAGE: &str = "usage: lcd-decode <capture.csv> --rs <channel> --e <channel> \
+                     --data <channels> [--rw <channel>] [--oscillator <kHz>]";
+
+struct Args {
+    path: String,
+    rs: String,
+    enable: String,
+    data: Vec<String>,
+    rw: Option<String>,
+    oscillator: Option<u32>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut path = None;
+    let (mut rs, mut enable, mut data, mut rw, mut oscillator) = (None, None, None, None, None);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--rs" => rs = Some(value()?),
+            "--e" => enable = Some(value()?),
+            "--rw" => rw = Some(value()?),
+            "--data" => data = Some(value()?.split(',').map(String::from).collect()),
+            "--oscillator" => {
+                let khz = value()?;
+                oscillator = Some(
+                    khz.parse()
+                        .ok()
+                        .filter(|&khz| khz > 0)
+                        .ok_or(format!("invalid frequency {}", khz))?,
+                )
+            }
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return Err(format!("unexpected argument {}", arg)),
+        }
+    }
+    let data: Vec<String> = data.ok_or("missing --data")?;
+    if data.len() != 4 && data.len() != 8 {
+        return Err("--data should list either 4 or 8 channels".into());
+    }
+    Ok(Args {
+        path: path.ok_or("missing capture file")?,
+        rs: rs.ok_or("missing --rs")?,
+        enable: enable.ok_or("missing --e")?,
+        data,
+        rw,
+        oscillator,
+    })
+}
+
+/// Print the given event (`event`), consecutive data writes are collected into `text`.
+fn print_event(event: &Event, text: &mut Option<(u64, String)>) {
+    if let EventKind::Instruction(Instruction::Write(byte)) = event.kind {
+        let (_, text) = text.get_or_insert_with(|| (event.time, String::new()));
+        match byte {
+            0x20..=0x7e if byte != b'"' && byte != b'\\' => text.push(byte as char),
+            _ => write!(text, "\\x{:02x}", byte).unwrap(),
+        }
+        return;
+    }
+    flush_text(text);
+    match event.kind {
+        EventKind::Instruction(instruction) => println!("{}  {}", time(event.time), instruction),
+        EventKind::Error(error) => println!("{}  error: {}", time(event.time), error),
+    }
+}
+
+fn flush_text(text: &mut Option<(u64, String)>) {
+    if let Some((start, text)) = text.take() {
+        println!("{}  write \"{}\"", time(start), text);
+    }
+}
+
+fn time(ns: u64) -> String {
+    format!("{:>10}.{:03} us", ns / 1000, ns % 1000)
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|error| {
+        eprintln!("{}\n{}", error, USAGE);
+        exit(2);
+    });
+    let input = fs::read_to_string(&args.path).unwrap_or_else(|error| {
+        eprintln!("cannot read {}: {}", args.path, error);
+        exit(2);
+    });
+
+    let data: Vec<&str> = args.data.iter().map(String::as_str).collect();
+    let mut pinout = Pinout::new(&args.rs, &args.enable, &data);
+    if let Some(rw) = &args.rw {
+        pinout = pinout.rw(rw);
+    }
+    let constraints = match args.oscillator {
+        Some(khz) => Constraints::for_oscillator(khz),
+        None => Constraints::datasheet(),
+    };
+    let events = decode_csv(&input, &pinout, constraints).unwrap_or_else(|error| {
+        eprintln!("cannot decode {}: {}", args.path, error);
+        exit(2);
+    });
+
+    let mut text = None;
+    for event in &events {
+        print_event(event, &mut text);
+    }
+    flush_text(&mut text);
+
+    let errors = events
+        .iter()
+        .filter(|event| matches!(event.kind, EventKind::Error(_)))
+        .count();
+    if errors > 0 {
+        eprintln!("{} protocol error(s) found", errors);
+        exit(1);
+    }
+}
@@ -0,0 +1,307 @@
+//! Pseudo-graphics canvas, backed by the custom glyphs.
+//!
+//! [Canvas] is a small bitmap of up to 8 characters (for example, 4x2 characters, 20x16 pixels),
+//! each character being one CGRAM glyph. Drawing ([Canvas::set_pixel], [Canvas::line],
+//! [Canvas::rect], [Canvas::blit]) only changes the bitmap; [Canvas::flush] uploads the changed
+//! glyphs and [Canvas::place] puts the glyph codes on the display. Since the display shows CGRAM
+//! glyphs wherever their codes are, placed canvas is updated by [Canvas::flush] alone.
+//!
+//! There are gaps between characters on the display, so pixels of the neighbouring characters are
+//! not adjacent.
+//!
+//! With the `graphics` feature, canvas implements `DrawTarget` of the
+//! [embedded-graphics](https://crates.io/crates/embedded-graphics) crate.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::canvas::Canvas;
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! let mut canvas = Canvas::new(4, 2);
+//! canvas.place(&mut lcd, 16, 0);
+//!
+//! canvas.rect(0, 0, 20, 16, true);
+//! canvas.line(0, 0, 19, 15, true);
+//! canvas.flush(&mut lcd);
+//! ```
+use crate::shadow::{CHARACTER_HEIGHT, CHARACTER_WIDTH};
+use crate::{Delay, Display, Hardware};
+
+/// Number of CGRAM glyphs.
+const GLYPHS: u8 = 8;
+
+/// Bitmap of the custom glyphs, see the [module](self) documentation.
+pub struct Canvas {
+    columns: u8,
+    rows: u8,
+    location: u8,
+    /// Glyph of each character, row by row.
+    glyphs: [[u8; 8]; GLYPHS as usize],
+    /// Bit mask of the characters changed since the last flush.
+    dirty: u8,
+}
+
+impl Canvas {
+    /// Create a new blank canvas, `columns` characters wide and `rows` characters high (up to 8
+    /// characters in total).
+    pub fn new(columns: u8, rows: u8) -> Self {
+        assert!(
+            columns > 0 && rows > 0 && u16::from(columns) * u16::from(rows) <= u16::from(GLYPHS)
+        );
+        Canvas {
+            columns,
+            rows,
+            location: 0,
+            glyphs: [[0; 8]; GLYPHS as usize],
+            dirty: 0xff,
+        }
+    }
+
+    /// Use CGRAM locations starting at the given one (`location`) for the glyphs, so canvas smaller
+    /// than 8 characters could share CGRAM with other glyphs.
+    pub fn set_glyph_location(&mut self, location: u8) {
+        assert!(u16::from(location) + u16::from(self.characters()) <= u16::from(GLYPHS));
+        self.location = location;
+        self.invalidate();
+    }
+
+    /// Range of the CGRAM locations used for the glyphs.
+    pub fn glyph_locations(&self) -> core::ops::Range<u8> {
+        self.location..self.location + self.characters()
+    }
+
+    /// Width in pixels.
+    pub fn width(&self) -> u8 {
+        self.columns * CHARACTER_WIDTH
+    }
+
+    /// Height in pixels.
+    pub fn height(&self) -> u8 {
+        self.rows * CHARACTER_HEIGHT
+    }
+
+    /// Turn all pixels off.
+    pub fn clear(&mut self) {
+        for (index, glyph) in self.glyphs.iter_mut().enumerate() {
+            if *glyph != [0; 8] {
+                *glyph = [0; 8];
+                self.dirty |= 1 << index;
+            }
+        }
+    }
+
+    /// If the pixel at the given coordinates (`x`, `y`) is on, `false` outside of the canvas.
+    pub fn pixel(&self, x: i32, y: i32) -> bool {
+        self.locate(x, y)
+            .is_some_and(|(index, line, bit)| self.glyphs[index][line] & bit != 0)
+    }
+
+    /// Turn the pixel at the given coordinates (`x`, `y`) on or off (`on`). Pixels outside of the
+    /// canvas are ignored.
+    pub fn set_pixel(&mut self, x: i32, y: i32, on: bool) {
+        if let Some((index, line, bit)) = self.locate(x, y) {
+            let data = &mut self.glyphs[index][line];
+            let updated = if on { *data | bit } else { *data & !bit };
+            if updated != *data {
+                *data = updated;
+                self.dirty |= 1 << index;
+            }
+        }
+    }
+
+    /// Draw a line between the given points (`x0`, `y0` and `x1`, `y1`, both included). The part
+    /// outside of the canvas is ignored.
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, on: bool) {
+        let (x0, y0, x1, y1) = match self.clip(x0, y0, x1, y1) {
+            Some(line) => line,
+            None => return,
+        };
+        // Bresenham's algorithm
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+        loop {
+            self.set_pixel(x, y, on);
+            if x == x1 && y == y1 {
+                break;
+            }
+            if 2 * error >= dy {
+                error += dy;
+                x += sx;
+            }
+            if 2 * error <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draw an outline of the rectangle with the top left corner at the given point (`x`, `y`),
+    /// `width` pixels wide and `height` pixels high.
+    pub fn rect(&mut self, x: i32, y: i32, width: i32, height: i32, on: bool) {
+        if width <= 0 || height <= 0 {
+            return;
+        }
+        let (right, bottom) = (x.saturating_add(width - 1), y.saturating_add(height - 1));
+        self.line(x, y, right, y, on);
+        self.line(x, bottom, right, bottom, on);
+        self.line(x, y, x, bottom, on);
+        self.line(right, y, right, bottom, on);
+    }
+
+    /// Fill the rectangle with the top left corner at the given point (`x`, `y`), `width` pixels
+    /// wide and `height` pixels high.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, on: bool) {
+        let right = x.saturating_add(width).min(i32::from(self.width()));
+        let bottom = y.saturating_add(height).min(i32::from(self.height()));
+        for py in y.max(0)..bottom {
+            for px in x.max(0)..right {
+                self.set_pixel(px, py, on);
+            }
+        }
+    }
+
+    /// Copy the bitmap (`bitmap`), `width` pixels wide, with the top left corner at the given
+    /// point (`x`, `y`). Each row of the bitmap takes `(width + 7) / 8` bytes, most significant
+    /// bit first; set bits turn pixels on, clear bits turn them off.
+    pub fn blit(&mut self, x: i32, y: i32, width: u8, bitmap: &[u8]) {
+        let stride = usize::from(width).div_ceil(8);
+        if stride == 0 {
+            return;
+        }
+        for (row, line) in bitmap.chunks(stride).enumerate() {
+            for col in 0..usize::from(width) {
+                let on = line
+                    .get(col / 8)
+                    .is_some_and(|data| data & (0x80 >> (col % 8)) != 0);
+                self.set_pixel(
+                    x.saturating_add(col as i32),
+                    y.saturating_add(row as i32),
+                    on,
+                );
+            }
+        }
+    }
+
+    /// Upload glyphs changed since the last flush to CGRAM.
+    pub fn flush<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        for index in 0..self.characters() {
+            if self.dirty & (1 << index) != 0 {
+                lcd.upload_character(self.location + index, self.glyphs[usize::from(index)]);
+            }
+        }
+        self.dirty = 0;
+    }
+
+    /// Put glyph codes on the display (`lcd`), with the top left corner at the given column
+    /// (`col`) and row (`row`), see [Display::position].
+    pub fn place<HW: Hardware + Delay>(&self, lcd: &mut Display<HW>, col: u8, row: u8) {
+        for line in 0..self.rows {
+            lcd.position(col, row + line);
+            for index in 0..self.columns {
+                lcd.write(self.location + line * self.columns + index);
+            }
+        }
+    }
+
+    /// Forget what is in CGRAM, so next flush uploads all glyphs.
+    pub fn invalidate(&mut self) {
+        self.dirty = 0xff;
+    }
+
+    fn characters(&self) -> u8 {
+        self.columns * self.rows
+    }
+
+    /// Clip the line between the given points (`x0`, `y0` and `x1`, `y1`) to the canvas
+    /// (Cohen-Sutherland algorithm), `None` if the line is outside of the canvas.
+    fn clip(&self, x0: i32, y0: i32, x1: i32, y1: i32) -> Option<(i32, i32, i32, i32)> {
+        let (right, bottom) = (i128::from(self.width()) - 1, i128::from(self.height()) - 1);
+        let outcode = |x: i128, y: i128| {
+            u8::from(x < 0)
+                | u8::from(x > right) << 1
+                | u8::from(y < 0) << 2
+                | u8::from(y > bottom) << 3
+        };
+        let (mut x0, mut y0, mut x1, mut y1) = (
+            i128::from(x0),
+            i128::from(y0),
+            i128::from(x1),
+            i128::from(y1),
+        );
+        loop {
+            let (code0, code1) = (outcode(x0, y0), outcode(x1, y1));
+            if code0 | code1 == 0 {
+                return Some((x0 as i32, y0 as i32, x1 as i32, y1 as i32));
+            }
+            if code0 & code1 != 0 {
+                return None;
+            }
+            // Move the outside point to the edge it is beyond
+            let code = if code0 != 0 { code0 } else { code1 };
+            let (x, y) = if code & 0b0011 != 0 {
+                let x = if code & 0b0001 != 0 { 0 } else { right };
+                (x, y0 + (y1 - y0) * (x - x0) / (x1 - x0))
+            } else {
+                let y = if code & 0b0100 != 0 { 0 } else { bottom };
+                (x0 + (x1 - x0) * (y - y0) / (y1 - y0), y)
+            };
+            if code == code0 {
+                x0 = x;
+                y0 = y;
+            } else {
+                x1 = x;
+                y1 = y;
+            }
+        }
+    }
+
+    /// Glyph index, glyph row and bit mask of the given pixel (`x`, `y`).
+    fn locate(&self, x: i32, y: i32) -> Option<(usize, usize, u8)> {
+        if x < 0 || y < 0 || x >= i32::from(self.width()) || y >= i32::from(self.height()) {
+            return None;
+        }
+        let (width, height) = (i32::from(CHARACTER_WIDTH), i32::from(CHARACTER_HEIGHT));
+        let index = (y / height) * i32::from(self.columns) + x / width;
+        let line = y % height;
+        let bit = 0x10 >> (x % width);
+        Some((index as usize, line as usize, bit))
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl embedded_graphics_core::geometry::OriginDimensions for Canvas {
+    fn size(&self) -> embedded_graphics_core::geometry::Size {
+        embedded_graphics_core::geometry::Size::new(
+            u32::from(self.width()),
+            u32::from(self.height()),
+        )
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl embedded_graphics_core::draw_target::DrawTarget for Canvas {
+    type Color = embedded_graphics_core::pixelcolor::BinaryColor;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = embedded_graphics_core::Pixel<Self::Color>>,
+    {
+        for embedded_graphics_core::Pixel(point, color) in pixels {
+            self.set_pixel(point.x, point.y, color.is_on());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,445 @@
+//! Protocol timing checker.
+//!
+//! [TimingChecker] wraps any `Hardware + Delay` implementation and keeps a virtual clock, which
+//! is advanced by [Delay::delay_us] and [Hardware::wait_address] calls (the latter is assumed to
+//! provide the address set-up time). Every pin transition is checked against the datasheet
+//! constraints and violations are collected as a list of [Diagnostic]s.
+//!
+//! Time of the checker creation is considered to be the moment of the power-on.
+//!
+//! ```rust
+//! use lcd::*;
+//! use lcd::checker::{Constraints, TimingChecker};
+//! use lcd::emulator::Hd44780Emulator;
+//!
+//! let hw = TimingChecker::new(Hd44780Emulator::new(FunctionMode::Bit4), Constraints::datasheet());
+//! let mut lcd = Display::with_timing(hw, Timing::worst_case());
+//! lcd.init_with(InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8));
+//! lcd.print("Hello");
+//!
+//! let checker = lcd.unwrap();
+//! assert!(checker.diagnostics().is_empty(), "{:?}", checker.diagnostics());
+//! ```
+use crate::{Delay, FunctionMode, Hardware};
+use core::fmt;
+use std::vec::Vec;
+
+/// Nanoseconds in one microsecond.
+const NS_PER_US: u64 = 1000;
+/// Number of function set instructions in the reset-by-instruction sequence.
+const RESET_STEPS: u8 = 3;
+
+/// Timing constraints to check against. All values are in nanoseconds.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Constraints {
+    /// Time from the power-on to the first instruction (40ms after Vcc rises to 2.7V).
+    pub power_on: u64,
+    /// Wait after the first function set of the reset-by-instruction sequence (4.1ms).
+    pub reset_first: u64,
+    /// Wait after the second function set of the reset-by-instruction sequence (100us).
+    pub reset_second: u64,
+    /// Execution time of the clear display and return home instructions (1.52ms).
+    pub clear_home: u64,
+    /// Execution time of all other instructions (37us).
+    pub command: u64,
+    /// Time to update the address counter after data write or read (tADD, 4us).
+    pub address_update: u64,
+    /// Enable pulse width (PW_EH, 450ns).
+    pub enable_pulse: u64,
+    /// Enable cycle time (tcycE, 1000ns).
+    pub enable_cycle: u64,
+    /// Address set-up time, from R/S and R/W to the enable rising edge (tAS, 40ns).
+    pub address_setup: u64,
+}
+
+impl Constraints {
+    /// Constraints from the HD44780 datasheet, for the typical 270 kHz oscillator.
+    pub const fn datasheet() -> Self {
+        Constraints {
+            power_on: 40_000_000,
+            reset_first: 4_100_000,
+            reset_second: 100_000,
+            clear_home: 1_520_000,
+            command: 37_000,
+            address_update: 4000,
+            enable_pulse: 450,
+            enable_cycle: 1000,
+            address_setup: 40,
+        }
+    }
+
+    /// Constraints for the given oscillator frequency (`khz`, in kHz). Execution times of the
+    /// [Constraints::datasheet] are scaled proportionally.
+    pub fn for_oscillator(khz: u32) -> Self {
+        assert!(khz > 0);
+        let scale = |ns: u64| (ns * 270).div_ceil(u64::from(khz));
+        let datasheet = Constraints::datasheet();
+        Constraints {
+            clear_home: scale(datasheet.clear_home),
+            command: scale(datasheet.command),
+            address_update: scale(datasheet.address_update),
+            ..datasheet
+        }
+    }
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Constraints::datasheet()
+    }
+}
+
+/// Timing constraint violation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Violation {
+    /// First instruction was sent too early after the power-on.
+    PowerOnWait,
+    /// Enable pulse was too short (PW_EH).
+    EnablePulseWidth,
+    /// Enable cycle was too short (tcycE).
+    EnableCycle,
+    /// Enable went high too early after R/S or R/W change (tAS).
+    AddressSetup,
+    /// Instruction or data was sent before the previous one finished executing.
+    ExecutionTime {
+        /// Previous instruction (or data, if `data` is `true`).
+        previous: u8,
+        /// If previous transfer was a data write.
+        data: bool,
+    },
+    /// Instruction or data was sent after busy flag was read as set, without waiting for it to
+    /// clear.
+    Busy,
+}
+
+/// Single timing constraint violation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// Time of the violation since the power-on, in nanoseconds.
+    pub time: u64,
+    /// Violated constraint.
+    pub violation: Violation,
+    /// Actual time between the events, in nanoseconds.
+    pub actual: u64,
+    /// Required time between the events, in nanoseconds.
+    pub required: u64,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "at {}ns: ", self.time)?;
+        match self.violation {
+            Violation::PowerOnWait => write!(f, "first instruction sent too early after power-on")?,
+            Violation::EnablePulseWidth => write!(f, "enable pulse is too short (PW_EH)")?,
+            Violation::EnableCycle => write!(f, "enable cycle is too short (tcycE)")?,
+            Violation::AddressSetup => write!(f, "address set-up time is too short (tAS)")?,
+            Violation::ExecutionTime { previous, data } => write!(
+                f,
+                "previous {} 0x{:02x} did not finish executing",
+                if data { "data write" } else { "instruction" },
+                previous
+            )?,
+            Violation::Busy => write!(f, "sent while busy flag is set")?,
+        }
+        write!(f, " ({}ns, required {}ns)", self.actual, self.required)
+    }
+}
+
+/// `Hardware + Delay` wrapper checking the protocol timing, see the [module](self) documentation.
+pub struct TimingChecker<HW> {
+    hw: HW,
+    constraints: Constraints,
+    diagnostics: Vec<Diagnostic>,
+    now: u64,
+
+    rs: bool,
+    rw: bool,
+    enable: bool,
+    data: u8,
+    control_changed: Option<u64>,
+    enable_rise: Option<u64>,
+    powered: bool,
+
+    eight_bit: bool,
+    second_nibble: bool,
+    pending: u8,
+    reset_step: u8,
+    last: Option<(u8, bool)>,
+    ready_at: u64,
+    busy: bool,
+}
+
+impl<HW: Hardware + Delay> TimingChecker<HW> {
+    /// Create a new checker wrapping the given `Hardware + Delay` implementation (`hw`), checking
+    /// against the given constraints (`constraints`).
+    pub fn new(hw: HW, constraints: Constraints) -> Self {
+        TimingChecker {
+            hw,
+            constraints,
+            diagnostics: Vec::new(),
+            now: 0,
+            rs: false,
+            rw: false,
+            enable: false,
+            data: 0,
+            control_changed: None,
+            enable_rise: None,
+            powered: false,
+            eight_bit: true,
+            second_nibble: false,
+            pending: 0,
+            reset_step: 0,
+            last: None,
+            ready_at: 0,
+            busy: false,
+        }
+    }
+
+    /// Violations found so far.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Take violations found so far, leaving the list empty.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        core::mem::take(&mut self.diagnostics)
+    }
+
+    /// Virtual time since the power-on, in nanoseconds.
+    pub fn now_ns(&self) -> u64 {
+        self.now
+    }
+
+    /// Assume display is already powered on and initialized, so no power-on wait and no
+    /// reset-by-instruction sequence is expected. Should be used when checking warm
+    /// initialization.
+    pub fn assume_initialized(&mut self) {
+        self.powered = true;
+        self.reset_step = RESET_STEPS;
+    }
+
+    /// Unwrap back to the wrapped `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
+
+    /// Wrapped `Hardware + Delay` implementation.
+    pub(crate) fn hw_mut(&mut self) -> &mut HW {
+        &mut self.hw
+    }
+
+    /// Move virtual clock forward to the given time (`time`, in nanoseconds since the power-on).
+    pub(crate) fn advance_to(&mut self, time: u64) {
+        self.now = self.now.max(time);
+    }
+
+    fn report(&mut self, violation: Violation, actual: u64, required: u64) {
+        self.diagnostics.push(Diagnostic {
+            time: self.now,
+            violation,
+            actual,
+            required,
+        });
+    }
+
+    fn check(&mut self, violation: Violation, actual: u64, required: u64) {
+        if actual < required {
+            self.report(violation, actual, required);
+        }
+    }
+
+    fn four_bit_transfer(&self) -> bool {
+        !self.eight_bit
+    }
+
+    fn enable_rising(&mut self) {
+        if !self.powered {
+            self.powered = true;
+            self.check(Violation::PowerOnWait, self.now, self.constraints.power_on);
+        }
+        if let Some(changed) = self.control_changed {
+            self.check(
+                Violation::AddressSetup,
+                self.now - changed,
+                self.constraints.address_setup,
+            );
+        }
+        if let Some(rise) = self.enable_rise {
+            self.check(
+                Violation::EnableCycle,
+                self.now - rise,
+                self.constraints.enable_cycle,
+            );
+        }
+        self.enable_rise = Some(self.now);
+
+        // Only busy flag could be read while controller is busy
+        let first = !self.four_bit_transfer() || !self.second_nibble;
+        let busy_read = self.rw && !self.rs;
+        if first && !busy_read {
+            if self.busy {
+                self.report(Violation::Busy, 0, 0);
+                self.busy = false;
+            } else if self.now < self.ready_at {
+                let (previous, data) = self.last.unwrap_or((0, false));
+                let required = self.execution_time(previous, data);
+                let actual = required.saturating_sub(self.ready_at - self.now);
+                self.report(
+                    Violation::ExecutionTime { previous, data },
+                    actual,
+                    required,
+                );
+            }
+        }
+    }
+
+    fn enable_falling(&mut self) {
+        if let Some(rise) = self.enable_rise {
+            self.check(
+                Violation::EnablePulseWidth,
+                self.now - rise,
+                self.constraints.enable_pulse,
+            );
+        }
+
+        let complete = if self.four_bit_transfer() {
+            self.second_nibble = !self.second_nibble;
+            !self.second_nibble
+        } else {
+            true
+        };
+        if self.rw {
+            if complete && self.rs {
+                self.ready_at = self.now + self.constraints.address_update;
+            }
+            return;
+        }
+
+        let value = match self.hw.mode() {
+            FunctionMode::Bit8 => self.data,
+            FunctionMode::Bit4 => (self.data & 0xf) << 4,
+        };
+        let byte = if self.four_bit_transfer() {
+            if !complete {
+                self.pending = value >> 4;
+                return;
+            }
+            (self.pending << 4) | (value >> 4)
+        } else {
+            value
+        };
+
+        if !self.rs && byte & 0xe0 == 0x20 {
+            // Function set
+            let eight_bit = byte & 0x10 != 0;
+            if eight_bit != self.eight_bit {
+                self.second_nibble = false;
+            }
+            self.eight_bit = eight_bit;
+        }
+        // Reset-by-instruction sequence is the first three function sets selecting 8-bit mode
+        if self.reset_step < RESET_STEPS && !self.rs && byte & 0xf0 == 0x30 {
+            self.reset_step += 1;
+        } else {
+            self.reset_step = RESET_STEPS;
+        }
+
+        self.last = Some((byte, self.rs));
+        self.ready_at = self.now + self.execution_time(byte, self.rs);
+    }
+
+    fn execution_time(&self, byte: u8, data: bool) -> u64 {
+        if data {
+            self.constraints.command + self.constraints.address_update
+        } else if byte & 0xe0 == 0x20 && self.reset_step == 1 {
+            self.constraints.reset_first
+        } else if byte & 0xe0 == 0x20 && self.reset_step == 2 {
+            self.constraints.reset_second
+        } else if byte == 0x01 || byte & 0xfe == 0x02 {
+            self.constraints.clear_home
+        } else {
+            self.constraints.command
+        }
+    }
+
+    fn control_changed(&mut self) {
+        self.control_changed = Some(self.now);
+    }
+}
+
+impl<HW: Hardware + Delay> Hardware for TimingChecker<HW> {
+    fn rs(&mut self, bit: bool) {
+        if bit != self.rs {
+            self.rs = bit;
+            self.control_changed();
+        }
+        self.hw.rs(bit)
+    }
+
+    fn enable(&mut self, bit: bool) {
+        if bit && !self.enable {
+            self.enable = true;
+            self.enable_rising();
+        } else if !bit && self.enable {
+            self.enable = false;
+            self.enable_falling();
+        }
+        self.hw.enable(bit)
+    }
+
+    fn data(&mut self, data: u8) {
+        self.data = data;
+        self.hw.data(data)
+    }
+
+    fn wait_address(&mut self) {
+        self.now += self.constraints.address_setup;
+        self.hw.wait_address()
+    }
+
+    fn mode(&self) -> FunctionMode {
+        self.hw.mode()
+    }
+
+    fn can_read(&self) -> bool {
+        self.hw.can_read()
+    }
+
+    fn rw(&mut self, bit: bool) {
+        if bit != self.rw {
+            self.rw = bit;
+            self.control_changed();
+        }
+        self.hw.rw(bit)
+    }
+
+    fn read_data(&mut self) -> u8 {
+        let data = self.hw.read_data();
+        let first = !self.four_bit_transfer() || !self.second_nibble;
+        if self.rw && !self.rs && first {
+            let flag = match self.hw.mode() {
+                FunctionMode::Bit8 => 0x80,
+                FunctionMode::Bit4 => 0x08,
+            };
+            if data & flag != 0 {
+                self.busy = true;
+            } else {
+                // Controller reports it is ready, trust it
+                self.busy = false;
+                self.ready_at = self.now;
+            }
+        }
+        data
+    }
+
+    fn apply(&mut self) {
+        self.hw.apply()
+    }
+}
+
+impl<HW: Hardware + Delay> Delay for TimingChecker<HW> {
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.now += u64::from(delay_usec) * NS_PER_US;
+        self.hw.delay_us(delay_usec)
+    }
+}
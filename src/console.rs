@@ -0,0 +1,229 @@
+//! Scrolling log console, like `tail -f` on the display.
+//!
+//! [Console] keeps the last lines written with `write!` / `writeln!` (as many as there are rows),
+//! older lines scroll up. Lines longer than the display width either wrap to the next row or get
+//! truncated. Console only changes its own buffer, [Console::draw] updates the display, only
+//! rewriting characters which have changed since the last draw.
+//!
+//! ```rust,no_run
+//! use core::fmt::Write;
+//! use lcd::*;
+//! use lcd::console::Console;
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! let mut console = Console::new(Geometry::new(20, 4));
+//! console.set_timestamp(true);
+//!
+//! console.set_time(65);
+//! writeln!(console, "booted").unwrap();
+//! console.draw(&mut lcd); // "01:05 booted" on the bottom row
+//! ```
+use crate::shadow::{Shadow, MAX_CHARACTERS};
+use crate::{Delay, Display, Geometry, Hardware};
+use core::fmt;
+
+/// Length of the timestamp prefix (`mm:ss `).
+const TIMESTAMP_LENGTH: usize = 6;
+
+/// What to do with the lines longer than the display width.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Overflow {
+    /// Continue on the next row.
+    #[default]
+    Wrap,
+    /// Drop characters past the end of the row.
+    Truncate,
+}
+
+/// Scrolling log console, see the [module](self) documentation.
+pub struct Console {
+    geometry: Geometry,
+    overflow: Overflow,
+    /// Ring buffer of rows, `head` is the top one.
+    lines: [u8; MAX_CHARACTERS],
+    head: u8,
+    /// Column in the bottom row where the next character goes.
+    col: u8,
+    /// New line was requested, but not started yet (so the last line stays at the bottom).
+    pending_newline: bool,
+    /// No characters were written to the current line yet.
+    line_start: bool,
+    shown: Shadow,
+    paused: bool,
+    timestamp: bool,
+    time: u32,
+}
+
+impl Console {
+    /// Create a new empty console for the display of the given size (`geometry`).
+    pub fn new(geometry: Geometry) -> Self {
+        Console {
+            geometry,
+            overflow: Overflow::Wrap,
+            lines: [b' '; MAX_CHARACTERS],
+            head: 0,
+            col: 0,
+            pending_newline: false,
+            line_start: true,
+            shown: Shadow::new(geometry),
+            paused: false,
+            timestamp: false,
+            time: 0,
+        }
+    }
+
+    /// Set what to do with the lines longer than the display width (`overflow`). Only affects
+    /// lines written after the change.
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /// Prefix each line with the time (`mm:ss`) given by [Console::set_time].
+    pub fn set_timestamp(&mut self, enabled: bool) {
+        self.timestamp = enabled;
+    }
+
+    /// Set current time (`seconds`) for the timestamp prefix.
+    pub fn set_time(&mut self, seconds: u32) {
+        self.time = seconds;
+    }
+
+    /// Stop updating the display. Lines written while paused are still kept (as many as there
+    /// are rows) and shown after [Console::resume].
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume updating the display.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// If display updates are paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Remove all lines.
+    pub fn clear(&mut self) {
+        self.lines = [b' '; MAX_CHARACTERS];
+        self.col = 0;
+        self.pending_newline = false;
+        self.line_start = true;
+    }
+
+    /// Repaint all rows on the next draw (for example, after display was used for something
+    /// else).
+    pub fn invalidate(&mut self) {
+        self.shown.invalidate();
+    }
+
+    /// Content of the given row (`row`, 0 is the top one).
+    pub fn row(&self, row: u8) -> &[u8] {
+        assert!(row < self.geometry.rows());
+        let index = (self.head + row) % self.geometry.rows();
+        self.row_slice(index)
+    }
+
+    /// Update the display (`lcd`), rewriting only characters which have changed since the last
+    /// draw. Does nothing while paused.
+    pub fn draw<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        if self.paused {
+            return;
+        }
+        let columns = usize::from(self.geometry.columns());
+        for row in 0..self.geometry.rows() {
+            let start = usize::from((self.head + row) % self.geometry.rows()) * columns;
+            self.shown
+                .update(lcd, 0, row, &self.lines[start..start + columns]);
+        }
+    }
+
+    fn row_slice(&self, index: u8) -> &[u8] {
+        let columns = usize::from(self.geometry.columns());
+        let start = usize::from(index) * columns;
+        &self.lines[start..start + columns]
+    }
+
+    fn put(&mut self, byte: u8) {
+        if byte == b'\n' {
+            if self.pending_newline {
+                // Previous line was empty
+                self.new_row();
+            }
+            self.pending_newline = true;
+            return;
+        }
+        if self.pending_newline {
+            self.pending_newline = false;
+            self.new_row();
+        }
+        if self.line_start {
+            self.line_start = false;
+            if self.timestamp {
+                let mut prefix = [b' '; TIMESTAMP_LENGTH];
+                let minutes = (self.time / 60) % 100;
+                let seconds = self.time % 60;
+                prefix[0] = b'0' + (minutes / 10) as u8;
+                prefix[1] = b'0' + (minutes % 10) as u8;
+                prefix[2] = b':';
+                prefix[3] = b'0' + (seconds / 10) as u8;
+                prefix[4] = b'0' + (seconds % 10) as u8;
+                for &data in prefix.iter() {
+                    self.put_char(data);
+                }
+            }
+        }
+        self.put_char(byte);
+    }
+
+    fn put_char(&mut self, byte: u8) {
+        if self.col >= self.geometry.columns() {
+            match self.overflow {
+                Overflow::Truncate => return,
+                Overflow::Wrap => self.scroll(),
+            }
+        }
+        let columns = usize::from(self.geometry.columns());
+        let bottom = (self.head + self.geometry.rows() - 1) % self.geometry.rows();
+        self.lines[usize::from(bottom) * columns + usize::from(self.col)] = byte;
+        self.col += 1;
+    }
+
+    /// Start a new line.
+    fn new_row(&mut self) {
+        self.scroll();
+        self.line_start = true;
+    }
+
+    /// Scroll rows up by one, leaving the bottom row empty.
+    fn scroll(&mut self) {
+        let columns = usize::from(self.geometry.columns());
+        let top = usize::from(self.head) * columns;
+        for item in &mut self.lines[top..top + columns] {
+            *item = b' ';
+        }
+        self.head = (self.head + 1) % self.geometry.rows();
+        self.col = 0;
+    }
+}
+
+impl fmt::Write for Console {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &byte in s.as_bytes() {
+            self.put(byte);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,431 @@
+//! Character generator ROM (CGROM) of the controller.
+//!
+//! Glyphs are 5 dots wide, one byte per row (bit 4 is the leftmost dot). Characters use the top
+//! 7 rows, the 8th row is where the cursor is displayed (only a few characters with descenders
+//! use it). Codes `0x00` - `0x0f` are CGRAM characters and have no ROM glyph.
+//!
+//! Tables are drawn after the character font tables of the HD44780U datasheet. Characters with
+//! descenders are given in their 5x8 form only, so they are the same in 5x10 font mode.
+
+/// Character generator ROM, selected by the controller ROM code.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Rom {
+    /// Japanese standard font: ASCII (with `¥`, `→` and `←` instead of `\`, `~` and DEL),
+    /// half-width katakana and a few Greek and mathematical symbols.
+    #[default]
+    A00,
+    /// European standard font: ASCII, arrows and symbols, Cyrillic, Greek and Western European
+    /// characters.
+    A02,
+}
+
+/// Characters used for the CGRAM codes in the text snapshots.
+const CUSTOM_CHARS: &str = "⓪①②③④⑤⑥⑦";
+/// Closest Unicode characters of the A00 codes `0xe0` - `0xff`.
+const A00_HIGH_CHARS: &str = "αäβεμσρg√⁻jˣ¢£ñöpqθ∞ΩüΣπxy千万円÷ █";
+/// Closest Unicode characters of the A02 codes `0x10` - `0x1f`.
+const A02_LOW_CHARS: &str = "▶◀“”⏫⏬•↵↑↓→←≤≥▲▼";
+/// Closest Unicode characters of the A02 codes `0x80` - `0xbf`.
+const A02_HIGH_CHARS: &str = "БДЖЗИЙЛПУЦЧШЩЪЫЭα♪ΓπΣσ♬τ🔔ΘΩδ∞♥ε∩ ¡¢£¤¥¦§ƒ©ª«ЮЯ®‘°±²³₧µ¶·ω¹º»¼½¾¿";
+
+impl Rom {
+    /// Glyph of the given character code (`code`), one byte per row. All dots are off for the
+    /// CGRAM codes (`0x00` - `0x0f`) and the codes which are not defined in this ROM.
+    pub fn glyph(self, code: u8) -> [u8; 8] {
+        match (self, code) {
+            (Rom::A00, 0x5c) => A00_SYMBOLS[0],
+            (Rom::A00, 0x7e) => A00_SYMBOLS[1],
+            (Rom::A00, 0x7f) => A00_SYMBOLS[2],
+            (_, 0x20..=0x7f) => ASCII[(code - 0x20) as usize],
+            (Rom::A00, 0xa0..=0xff) => A00_HIGH[(code - 0xa0) as usize],
+            (Rom::A02, 0x10..=0x1f) => A02_LOW[(code - 0x10) as usize],
+            (Rom::A02, 0x80..=0xff) => A02_HIGH[(code - 0x80) as usize],
+            _ => [0; 8],
+        }
+    }
+
+    /// Closest Unicode character for the given character code (`code`). CGRAM codes are mapped to
+    /// the circled digits (`⓪` - `⑦`), codes which are not defined in this ROM are mapped to the
+    /// space.
+    pub fn to_char(self, code: u8) -> char {
+        let nth = |chars: &str, index: u8| chars.chars().nth(index as usize).unwrap_or(' ');
+        match (self, code) {
+            (_, 0x00..=0x0f) => nth(CUSTOM_CHARS, code & 0x7),
+            (Rom::A00, 0x5c) => '¥',
+            (Rom::A00, 0x7e) => '→',
+            (Rom::A00, 0x7f) => '←',
+            (Rom::A00, 0x20..=0x7d) => code as char,
+            (Rom::A00, 0xa1..=0xdf) => {
+                // Half-width katakana are in the same order as in JIS X 0201
+                core::char::from_u32(0xff61 + u32::from(code - 0xa1)).unwrap_or(' ')
+            }
+            (Rom::A00, 0xe0..=0xff) => nth(A00_HIGH_CHARS, code - 0xe0),
+            (Rom::A02, 0x10..=0x1f) => nth(A02_LOW_CHARS, code - 0x10),
+            (Rom::A02, 0x7f) => '⌂',
+            (Rom::A02, 0x20..=0x7e) => code as char,
+            (Rom::A02, 0x80..=0xbf) => nth(A02_HIGH_CHARS, code - 0x80),
+            // Same as ISO 8859-1
+            (Rom::A02, 0xc0..=0xff) => code as char,
+            _ => ' ',
+        }
+    }
+}
+
+/// Glyphs of the codes `0x20` - `0x7f` (A02 variant, A00 differs in `0x5c`, `0x7e` and `0x7f`).
+const ASCII: [[u8; 8]; 96] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x20 (space)
+    [0x04, 0x04, 0x04, 0x04, 0x00, 0x00, 0x04, 0x00], // 0x21 !
+    [0x0a, 0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x22 "
+    [0x0a, 0x0a, 0x1f, 0x0a, 0x1f, 0x0a, 0x0a, 0x00], // 0x23 #
+    [0x04, 0x0f, 0x14, 0x0e, 0x05, 0x1e, 0x04, 0x00], // 0x24 $
+    [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00], // 0x25 %
+    [0x0c, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0d, 0x00], // 0x26 &
+    [0x0c, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x27 '
+    [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00], // 0x28 (
+    [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00], // 0x29 )
+    [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00, 0x00], // 0x2a *
+    [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00, 0x00], // 0x2b +
+    [0x00, 0x00, 0x00, 0x00, 0x0c, 0x04, 0x08, 0x00], // 0x2c ,
+    [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00], // 0x2d -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00], // 0x2e .
+    [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00], // 0x2f /
+    [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e, 0x00], // 0x30 0
+    [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0x31 1
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f, 0x00], // 0x32 2
+    [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e, 0x00], // 0x33 3
+    [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02, 0x00], // 0x34 4
+    [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e, 0x00], // 0x35 5
+    [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e, 0x00], // 0x36 6
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00], // 0x37 7
+    [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e, 0x00], // 0x38 8
+    [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c, 0x00], // 0x39 9
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x0c, 0x00, 0x00], // 0x3a :
+    [0x00, 0x0c, 0x0c, 0x00, 0x0c, 0x04, 0x08, 0x00], // 0x3b ;
+    [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00], // 0x3c <
+    [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00, 0x00], // 0x3d =
+    [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00], // 0x3e >
+    [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00], // 0x3f ?
+    [0x0e, 0x11, 0x01, 0x0d, 0x15, 0x15, 0x0e, 0x00], // 0x40 @
+    [0x0e, 0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0x41 A
+    [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e, 0x00], // 0x42 B
+    [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e, 0x00], // 0x43 C
+    [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c, 0x00], // 0x44 D
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f, 0x00], // 0x45 E
+    [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10, 0x00], // 0x46 F
+    [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f, 0x00], // 0x47 G
+    [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11, 0x00], // 0x48 H
+    [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0x49 I
+    [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c, 0x00], // 0x4a J
+    [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00], // 0x4b K
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f, 0x00], // 0x4c L
+    [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00], // 0x4d M
+    [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00], // 0x4e N
+    [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0x4f O
+    [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10, 0x00], // 0x50 P
+    [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d, 0x00], // 0x51 Q
+    [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11, 0x00], // 0x52 R
+    [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e, 0x00], // 0x53 S
+    [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x54 T
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0x55 U
+    [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04, 0x00], // 0x56 V
+    [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a, 0x00], // 0x57 W
+    [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11, 0x00], // 0x58 X
+    [0x11, 0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x00], // 0x59 Y
+    [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f, 0x00], // 0x5a Z
+    [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e, 0x00], // 0x5b [
+    [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00], // 0x5c \
+    [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e, 0x00], // 0x5d ]
+    [0x04, 0x0a, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x5e ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x00], // 0x5f _
+    [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x60 `
+    [0x00, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0x61 a
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1e, 0x00], // 0x62 b
+    [0x00, 0x00, 0x0e, 0x10, 0x10, 0x11, 0x0e, 0x00], // 0x63 c
+    [0x01, 0x01, 0x0d, 0x13, 0x11, 0x11, 0x0f, 0x00], // 0x64 d
+    [0x00, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00], // 0x65 e
+    [0x06, 0x09, 0x08, 0x1c, 0x08, 0x08, 0x08, 0x00], // 0x66 f
+    [0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e, 0x00], // 0x67 g
+    [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0x68 h
+    [0x04, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0x69 i
+    [0x02, 0x00, 0x06, 0x02, 0x02, 0x12, 0x0c, 0x00], // 0x6a j
+    [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00], // 0x6b k
+    [0x0c, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0x6c l
+    [0x00, 0x00, 0x1a, 0x15, 0x15, 0x11, 0x11, 0x00], // 0x6d m
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0x6e n
+    [0x00, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0x6f o
+    [0x00, 0x00, 0x1e, 0x11, 0x1e, 0x10, 0x10, 0x00], // 0x70 p
+    [0x00, 0x00, 0x0d, 0x13, 0x0f, 0x01, 0x01, 0x00], // 0x71 q
+    [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00], // 0x72 r
+    [0x00, 0x00, 0x0e, 0x10, 0x0e, 0x01, 0x1e, 0x00], // 0x73 s
+    [0x08, 0x08, 0x1c, 0x08, 0x08, 0x09, 0x06, 0x00], // 0x74 t
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00], // 0x75 u
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0a, 0x04, 0x00], // 0x76 v
+    [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0a, 0x00], // 0x77 w
+    [0x00, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00], // 0x78 x
+    [0x00, 0x00, 0x11, 0x11, 0x0f, 0x01, 0x0e, 0x00], // 0x79 y
+    [0x00, 0x00, 0x1f, 0x02, 0x04, 0x08, 0x1f, 0x00], // 0x7a z
+    [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00], // 0x7b {
+    [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x7c |
+    [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00], // 0x7d }
+    [0x00, 0x00, 0x00, 0x0d, 0x12, 0x00, 0x00, 0x00], // 0x7e ~
+    [0x00, 0x04, 0x0a, 0x11, 0x11, 0x11, 0x1f, 0x00], // 0x7f ⌂
+];
+
+/// A00 glyphs of the codes `0x5c`, `0x7e` and `0x7f`.
+const A00_SYMBOLS: [[u8; 8]; 3] = [
+    [0x11, 0x0a, 0x1f, 0x04, 0x1f, 0x04, 0x04, 0x00], // 0x5c ¥
+    [0x00, 0x04, 0x02, 0x1f, 0x02, 0x04, 0x00, 0x00], // 0x7e →
+    [0x00, 0x04, 0x08, 0x1f, 0x08, 0x04, 0x00, 0x00], // 0x7f ←
+];
+
+/// A00 glyphs of the codes `0xa0` - `0xff`.
+const A00_HIGH: [[u8; 8]; 96] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xa0 (blank)
+    [0x00, 0x00, 0x00, 0x00, 0x1c, 0x14, 0x1c, 0x00], // 0xa1 ｡
+    [0x07, 0x04, 0x04, 0x04, 0x00, 0x00, 0x00, 0x00], // 0xa2 ｢
+    [0x00, 0x00, 0x00, 0x04, 0x04, 0x04, 0x1c, 0x00], // 0xa3 ｣
+    [0x00, 0x00, 0x00, 0x00, 0x10, 0x08, 0x04, 0x00], // 0xa4 ､
+    [0x00, 0x00, 0x00, 0x0c, 0x0c, 0x00, 0x00, 0x00], // 0xa5 ･
+    [0x00, 0x1f, 0x01, 0x1f, 0x01, 0x02, 0x04, 0x00], // 0xa6 ｦ
+    [0x00, 0x00, 0x1f, 0x01, 0x06, 0x04, 0x08, 0x00], // 0xa7 ｧ
+    [0x00, 0x00, 0x02, 0x04, 0x0c, 0x14, 0x04, 0x00], // 0xa8 ｨ
+    [0x00, 0x00, 0x04, 0x1f, 0x11, 0x01, 0x06, 0x00], // 0xa9 ｩ
+    [0x00, 0x00, 0x00, 0x1f, 0x04, 0x04, 0x1f, 0x00], // 0xaa ｪ
+    [0x00, 0x00, 0x02, 0x1f, 0x06, 0x0a, 0x12, 0x00], // 0xab ｫ
+    [0x00, 0x00, 0x08, 0x1f, 0x09, 0x0a, 0x08, 0x00], // 0xac ｬ
+    [0x00, 0x00, 0x00, 0x0e, 0x02, 0x02, 0x1f, 0x00], // 0xad ｭ
+    [0x00, 0x00, 0x1e, 0x02, 0x1e, 0x02, 0x1e, 0x00], // 0xae ｮ
+    [0x00, 0x00, 0x00, 0x15, 0x15, 0x01, 0x06, 0x00], // 0xaf ｯ
+    [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00, 0x00], // 0xb0 ｰ
+    [0x1f, 0x01, 0x05, 0x06, 0x04, 0x04, 0x08, 0x00], // 0xb1 ｱ
+    [0x01, 0x02, 0x04, 0x0c, 0x14, 0x04, 0x04, 0x00], // 0xb2 ｲ
+    [0x04, 0x1f, 0x11, 0x11, 0x01, 0x02, 0x04, 0x00], // 0xb3 ｳ
+    [0x00, 0x1f, 0x04, 0x04, 0x04, 0x04, 0x1f, 0x00], // 0xb4 ｴ
+    [0x02, 0x1f, 0x02, 0x06, 0x0a, 0x12, 0x02, 0x00], // 0xb5 ｵ
+    [0x08, 0x1f, 0x09, 0x09, 0x09, 0x09, 0x12, 0x00], // 0xb6 ｶ
+    [0x04, 0x1f, 0x04, 0x1f, 0x04, 0x04, 0x04, 0x00], // 0xb7 ｷ
+    [0x00, 0x0f, 0x09, 0x11, 0x01, 0x02, 0x0c, 0x00], // 0xb8 ｸ
+    [0x08, 0x0f, 0x12, 0x02, 0x02, 0x02, 0x04, 0x00], // 0xb9 ｹ
+    [0x00, 0x1f, 0x01, 0x01, 0x01, 0x01, 0x1f, 0x00], // 0xba ｺ
+    [0x0a, 0x1f, 0x0a, 0x0a, 0x02, 0x04, 0x08, 0x00], // 0xbb ｻ
+    [0x00, 0x18, 0x01, 0x19, 0x01, 0x02, 0x1c, 0x00], // 0xbc ｼ
+    [0x00, 0x1f, 0x01, 0x02, 0x04, 0x0a, 0x11, 0x00], // 0xbd ｽ
+    [0x08, 0x1f, 0x09, 0x0a, 0x08, 0x08, 0x07, 0x00], // 0xbe ｾ
+    [0x00, 0x11, 0x11, 0x09, 0x01, 0x02, 0x0c, 0x00], // 0xbf ｿ
+    [0x00, 0x0f, 0x09, 0x17, 0x01, 0x02, 0x0c, 0x00], // 0xc0 ﾀ
+    [0x02, 0x1c, 0x04, 0x1f, 0x04, 0x04, 0x08, 0x00], // 0xc1 ﾁ
+    [0x00, 0x15, 0x15, 0x15, 0x01, 0x02, 0x04, 0x00], // 0xc2 ﾂ
+    [0x0e, 0x00, 0x1f, 0x04, 0x04, 0x04, 0x08, 0x00], // 0xc3 ﾃ
+    [0x08, 0x08, 0x08, 0x0c, 0x0a, 0x08, 0x08, 0x00], // 0xc4 ﾄ
+    [0x04, 0x04, 0x1f, 0x04, 0x04, 0x08, 0x10, 0x00], // 0xc5 ﾅ
+    [0x00, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x1f, 0x00], // 0xc6 ﾆ
+    [0x00, 0x1f, 0x01, 0x0a, 0x04, 0x0a, 0x10, 0x00], // 0xc7 ﾇ
+    [0x04, 0x1f, 0x02, 0x04, 0x0e, 0x15, 0x04, 0x00], // 0xc8 ﾈ
+    [0x02, 0x02, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00], // 0xc9 ﾉ
+    [0x00, 0x04, 0x02, 0x11, 0x11, 0x11, 0x11, 0x00], // 0xca ﾊ
+    [0x10, 0x10, 0x1f, 0x10, 0x10, 0x10, 0x0f, 0x00], // 0xcb ﾋ
+    [0x00, 0x1f, 0x01, 0x01, 0x01, 0x02, 0x0c, 0x00], // 0xcc ﾌ
+    [0x00, 0x08, 0x14, 0x02, 0x01, 0x00, 0x00, 0x00], // 0xcd ﾍ
+    [0x04, 0x1f, 0x04, 0x04, 0x15, 0x15, 0x04, 0x00], // 0xce ﾎ
+    [0x00, 0x1f, 0x01, 0x01, 0x0a, 0x04, 0x02, 0x00], // 0xcf ﾏ
+    [0x00, 0x0e, 0x00, 0x0e, 0x00, 0x0e, 0x01, 0x00], // 0xd0 ﾐ
+    [0x00, 0x04, 0x08, 0x10, 0x11, 0x1f, 0x01, 0x00], // 0xd1 ﾑ
+    [0x00, 0x01, 0x01, 0x0a, 0x04, 0x0a, 0x10, 0x00], // 0xd2 ﾒ
+    [0x00, 0x1f, 0x08, 0x1f, 0x08, 0x08, 0x07, 0x00], // 0xd3 ﾓ
+    [0x08, 0x08, 0x1f, 0x09, 0x0a, 0x08, 0x08, 0x00], // 0xd4 ﾔ
+    [0x00, 0x0e, 0x02, 0x02, 0x02, 0x02, 0x1f, 0x00], // 0xd5 ﾕ
+    [0x00, 0x1f, 0x01, 0x1f, 0x01, 0x01, 0x1f, 0x00], // 0xd6 ﾖ
+    [0x0e, 0x00, 0x1f, 0x01, 0x01, 0x02, 0x04, 0x00], // 0xd7 ﾗ
+    [0x12, 0x12, 0x12, 0x12, 0x02, 0x04, 0x08, 0x00], // 0xd8 ﾘ
+    [0x00, 0x04, 0x14, 0x14, 0x15, 0x15, 0x16, 0x00], // 0xd9 ﾙ
+    [0x00, 0x10, 0x10, 0x11, 0x12, 0x14, 0x18, 0x00], // 0xda ﾚ
+    [0x00, 0x1f, 0x11, 0x11, 0x11, 0x11, 0x1f, 0x00], // 0xdb ﾛ
+    [0x00, 0x1f, 0x11, 0x11, 0x01, 0x02, 0x04, 0x00], // 0xdc ﾜ
+    [0x00, 0x18, 0x01, 0x01, 0x02, 0x04, 0x18, 0x00], // 0xdd ﾝ
+    [0x14, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xde ﾞ
+    [0x1c, 0x14, 0x1c, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xdf ﾟ
+    [0x00, 0x00, 0x09, 0x15, 0x12, 0x12, 0x0d, 0x00], // 0xe0 α
+    [0x0a, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe1 ä
+    [0x00, 0x00, 0x0e, 0x11, 0x1e, 0x11, 0x1e, 0x10], // 0xe2 β
+    [0x00, 0x00, 0x0e, 0x10, 0x0c, 0x10, 0x0e, 0x00], // 0xe3 ε
+    [0x00, 0x00, 0x11, 0x11, 0x13, 0x15, 0x10, 0x00], // 0xe4 μ
+    [0x00, 0x00, 0x0f, 0x14, 0x11, 0x11, 0x0e, 0x00], // 0xe5 σ
+    [0x00, 0x00, 0x0c, 0x12, 0x11, 0x1e, 0x10, 0x00], // 0xe6 ρ
+    [0x00, 0x00, 0x0f, 0x11, 0x11, 0x0f, 0x01, 0x0e], // 0xe7 g
+    [0x00, 0x00, 0x07, 0x04, 0x04, 0x14, 0x08, 0x00], // 0xe8 √
+    [0x00, 0x00, 0x02, 0x1d, 0x02, 0x00, 0x00, 0x00], // 0xe9 ⁻
+    [0x00, 0x01, 0x00, 0x03, 0x01, 0x01, 0x01, 0x0e], // 0xea j
+    [0x00, 0x14, 0x08, 0x14, 0x00, 0x00, 0x00, 0x00], // 0xeb ˣ
+    [0x00, 0x04, 0x0e, 0x14, 0x15, 0x0e, 0x04, 0x00], // 0xec ¢
+    [0x08, 0x08, 0x1c, 0x08, 0x1c, 0x08, 0x0f, 0x00], // 0xed £
+    [0x0e, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0xee ñ
+    [0x0a, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xef ö
+    [0x00, 0x00, 0x16, 0x19, 0x11, 0x1e, 0x10, 0x10], // 0xf0 p
+    [0x00, 0x00, 0x0d, 0x13, 0x11, 0x0f, 0x01, 0x01], // 0xf1 q
+    [0x00, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x0e, 0x00], // 0xf2 θ
+    [0x00, 0x00, 0x00, 0x0a, 0x15, 0x0a, 0x00, 0x00], // 0xf3 ∞
+    [0x00, 0x0e, 0x11, 0x11, 0x11, 0x0a, 0x1b, 0x00], // 0xf4 Ω
+    [0x0a, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00], // 0xf5 ü
+    [0x1f, 0x10, 0x08, 0x04, 0x08, 0x10, 0x1f, 0x00], // 0xf6 Σ
+    [0x00, 0x00, 0x1f, 0x0a, 0x0a, 0x0a, 0x13, 0x00], // 0xf7 π
+    [0x1f, 0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00], // 0xf8 x
+    [0x00, 0x00, 0x11, 0x11, 0x11, 0x0f, 0x01, 0x0e], // 0xf9 y
+    [0x01, 0x1e, 0x08, 0x1f, 0x04, 0x04, 0x04, 0x00], // 0xfa 千
+    [0x00, 0x1f, 0x08, 0x0f, 0x09, 0x11, 0x03, 0x00], // 0xfb 万
+    [0x00, 0x1f, 0x15, 0x1f, 0x11, 0x11, 0x13, 0x00], // 0xfc 円
+    [0x00, 0x04, 0x00, 0x1f, 0x00, 0x04, 0x00, 0x00], // 0xfd ÷
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xfe (blank)
+    [0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f, 0x1f], // 0xff █
+];
+
+/// A02 glyphs of the codes `0x10` - `0x1f`.
+const A02_LOW: [[u8; 8]; 16] = [
+    [0x10, 0x18, 0x1c, 0x1e, 0x1c, 0x18, 0x10, 0x00], // 0x10 ▶
+    [0x01, 0x03, 0x07, 0x0f, 0x07, 0x03, 0x01, 0x00], // 0x11 ◀
+    [0x09, 0x12, 0x1b, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x12 “
+    [0x1b, 0x09, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00], // 0x13 ”
+    [0x04, 0x0e, 0x1f, 0x00, 0x04, 0x0e, 0x1f, 0x00], // 0x14 ⏫
+    [0x1f, 0x0e, 0x04, 0x00, 0x1f, 0x0e, 0x04, 0x00], // 0x15 ⏬
+    [0x00, 0x00, 0x0e, 0x0e, 0x0e, 0x00, 0x00, 0x00], // 0x16 •
+    [0x01, 0x01, 0x05, 0x09, 0x1f, 0x08, 0x04, 0x00], // 0x17 ↵
+    [0x04, 0x0e, 0x15, 0x04, 0x04, 0x04, 0x04, 0x00], // 0x18 ↑
+    [0x04, 0x04, 0x04, 0x04, 0x15, 0x0e, 0x04, 0x00], // 0x19 ↓
+    [0x00, 0x04, 0x02, 0x1f, 0x02, 0x04, 0x00, 0x00], // 0x1a →
+    [0x00, 0x04, 0x08, 0x1f, 0x08, 0x04, 0x00, 0x00], // 0x1b ←
+    [0x02, 0x04, 0x08, 0x04, 0x02, 0x00, 0x1f, 0x00], // 0x1c ≤
+    [0x08, 0x04, 0x02, 0x04, 0x08, 0x00, 0x1f, 0x00], // 0x1d ≥
+    [0x00, 0x04, 0x04, 0x0e, 0x0e, 0x1f, 0x00, 0x00], // 0x1e ▲
+    [0x00, 0x1f, 0x0e, 0x0e, 0x04, 0x04, 0x00, 0x00], // 0x1f ▼
+];
+
+/// A02 glyphs of the codes `0x80` - `0xff`.
+const A02_HIGH: [[u8; 8]; 128] = [
+    [0x1f, 0x10, 0x10, 0x1e, 0x11, 0x11, 0x1e, 0x00], // 0x80 Б
+    [0x06, 0x0a, 0x0a, 0x0a, 0x0a, 0x1f, 0x11, 0x00], // 0x81 Д
+    [0x15, 0x15, 0x15, 0x0e, 0x15, 0x15, 0x15, 0x00], // 0x82 Ж
+    [0x1e, 0x01, 0x01, 0x0e, 0x01, 0x01, 0x1e, 0x00], // 0x83 З
+    [0x11, 0x11, 0x13, 0x15, 0x19, 0x11, 0x11, 0x00], // 0x84 И
+    [0x0a, 0x04, 0x11, 0x13, 0x15, 0x19, 0x11, 0x00], // 0x85 Й
+    [0x07, 0x09, 0x09, 0x09, 0x09, 0x09, 0x11, 0x00], // 0x86 Л
+    [0x1f, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x00], // 0x87 П
+    [0x11, 0x11, 0x11, 0x0f, 0x01, 0x01, 0x0e, 0x00], // 0x88 У
+    [0x12, 0x12, 0x12, 0x12, 0x12, 0x1f, 0x01, 0x00], // 0x89 Ц
+    [0x11, 0x11, 0x11, 0x0f, 0x01, 0x01, 0x01, 0x00], // 0x8a Ч
+    [0x15, 0x15, 0x15, 0x15, 0x15, 0x15, 0x1f, 0x00], // 0x8b Ш
+    [0x15, 0x15, 0x15, 0x15, 0x15, 0x1f, 0x01, 0x00], // 0x8c Щ
+    [0x18, 0x08, 0x08, 0x0e, 0x09, 0x09, 0x0e, 0x00], // 0x8d Ъ
+    [0x11, 0x11, 0x11, 0x19, 0x15, 0x15, 0x19, 0x00], // 0x8e Ы
+    [0x0e, 0x11, 0x01, 0x07, 0x01, 0x11, 0x0e, 0x00], // 0x8f Э
+    [0x00, 0x00, 0x09, 0x15, 0x12, 0x12, 0x0d, 0x00], // 0x90 α
+    [0x04, 0x06, 0x05, 0x04, 0x04, 0x1c, 0x1c, 0x00], // 0x91 ♪
+    [0x1f, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00], // 0x92 Γ
+    [0x00, 0x00, 0x1f, 0x0a, 0x0a, 0x0a, 0x0a, 0x00], // 0x93 π
+    [0x1f, 0x10, 0x08, 0x04, 0x08, 0x10, 0x1f, 0x00], // 0x94 Σ
+    [0x00, 0x00, 0x0f, 0x14, 0x11, 0x11, 0x0e, 0x00], // 0x95 σ
+    [0x03, 0x0d, 0x09, 0x09, 0x0b, 0x1b, 0x18, 0x00], // 0x96 ♬
+    [0x00, 0x00, 0x1f, 0x04, 0x04, 0x04, 0x06, 0x00], // 0x97 τ
+    [0x04, 0x0e, 0x0e, 0x0e, 0x1f, 0x00, 0x04, 0x00], // 0x98 🔔
+    [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x0e, 0x00], // 0x99 Θ
+    [0x0e, 0x11, 0x11, 0x11, 0x0a, 0x0a, 0x1b, 0x00], // 0x9a Ω
+    [0x0c, 0x10, 0x08, 0x0e, 0x11, 0x11, 0x0e, 0x00], // 0x9b δ
+    [0x00, 0x00, 0x0a, 0x15, 0x15, 0x0a, 0x00, 0x00], // 0x9c ∞
+    [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x00], // 0x9d ♥
+    [0x00, 0x00, 0x0e, 0x10, 0x0c, 0x10, 0x0e, 0x00], // 0x9e ε
+    [0x00, 0x0e, 0x11, 0x11, 0x11, 0x11, 0x00, 0x00], // 0x9f ∩
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xa0 (blank)
+    [0x04, 0x00, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00], // 0xa1 ¡
+    [0x04, 0x0e, 0x15, 0x14, 0x15, 0x0e, 0x04, 0x00], // 0xa2 ¢
+    [0x06, 0x09, 0x08, 0x1c, 0x08, 0x09, 0x16, 0x00], // 0xa3 £
+    [0x00, 0x11, 0x0e, 0x0a, 0x0e, 0x11, 0x00, 0x00], // 0xa4 ¤
+    [0x11, 0x0a, 0x1f, 0x04, 0x1f, 0x04, 0x04, 0x00], // 0xa5 ¥
+    [0x04, 0x04, 0x04, 0x00, 0x04, 0x04, 0x04, 0x00], // 0xa6 ¦
+    [0x0e, 0x10, 0x0e, 0x11, 0x0e, 0x01, 0x0e, 0x00], // 0xa7 §
+    [0x02, 0x05, 0x04, 0x0e, 0x04, 0x04, 0x14, 0x00], // 0xa8 ƒ
+    [0x0e, 0x11, 0x17, 0x14, 0x17, 0x11, 0x0e, 0x00], // 0xa9 ©
+    [0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00, 0x1f, 0x00], // 0xaa ª
+    [0x00, 0x05, 0x0a, 0x14, 0x0a, 0x05, 0x00, 0x00], // 0xab «
+    [0x12, 0x15, 0x15, 0x1d, 0x15, 0x15, 0x12, 0x00], // 0xac Ю
+    [0x0f, 0x11, 0x11, 0x0f, 0x05, 0x09, 0x11, 0x00], // 0xad Я
+    [0x0e, 0x11, 0x1d, 0x19, 0x15, 0x11, 0x0e, 0x00], // 0xae ®
+    [0x04, 0x08, 0x0c, 0x00, 0x00, 0x00, 0x00, 0x00], // 0xaf ‘
+    [0x0c, 0x12, 0x12, 0x0c, 0x00, 0x00, 0x00, 0x00], // 0xb0 °
+    [0x04, 0x04, 0x1f, 0x04, 0x04, 0x00, 0x1f, 0x00], // 0xb1 ±
+    [0x0c, 0x12, 0x04, 0x08, 0x1e, 0x00, 0x00, 0x00], // 0xb2 ²
+    [0x1c, 0x02, 0x0c, 0x02, 0x1c, 0x00, 0x00, 0x00], // 0xb3 ³
+    [0x1c, 0x14, 0x1c, 0x17, 0x12, 0x12, 0x11, 0x00], // 0xb4 ₧
+    [0x00, 0x00, 0x11, 0x11, 0x13, 0x15, 0x10, 0x00], // 0xb5 µ
+    [0x0f, 0x15, 0x15, 0x0d, 0x05, 0x05, 0x05, 0x00], // 0xb6 ¶
+    [0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00], // 0xb7 ·
+    [0x00, 0x00, 0x00, 0x0a, 0x11, 0x15, 0x0a, 0x00], // 0xb8 ω
+    [0x08, 0x18, 0x08, 0x08, 0x1c, 0x00, 0x00, 0x00], // 0xb9 ¹
+    [0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00, 0x1f, 0x00], // 0xba º
+    [0x00, 0x14, 0x0a, 0x05, 0x0a, 0x14, 0x00, 0x00], // 0xbb »
+    [0x11, 0x12, 0x14, 0x0b, 0x15, 0x07, 0x01, 0x00], // 0xbc ¼
+    [0x11, 0x12, 0x14, 0x0b, 0x11, 0x04, 0x07, 0x00], // 0xbd ½
+    [0x19, 0x0a, 0x18, 0x0b, 0x15, 0x07, 0x01, 0x00], // 0xbe ¾
+    [0x04, 0x00, 0x04, 0x08, 0x10, 0x11, 0x0e, 0x00], // 0xbf ¿
+    [0x08, 0x04, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0xc0 À
+    [0x02, 0x04, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0xc1 Á
+    [0x04, 0x0a, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0xc2 Â
+    [0x0d, 0x12, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0xc3 Ã
+    [0x0a, 0x00, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0xc4 Ä
+    [0x0e, 0x0a, 0x0e, 0x11, 0x1f, 0x11, 0x11, 0x00], // 0xc5 Å
+    [0x0f, 0x14, 0x14, 0x1e, 0x14, 0x14, 0x17, 0x00], // 0xc6 Æ
+    [0x0e, 0x11, 0x10, 0x11, 0x0e, 0x04, 0x0c, 0x00], // 0xc7 Ç
+    [0x08, 0x04, 0x1f, 0x10, 0x1e, 0x10, 0x1f, 0x00], // 0xc8 È
+    [0x02, 0x04, 0x1f, 0x10, 0x1e, 0x10, 0x1f, 0x00], // 0xc9 É
+    [0x04, 0x0a, 0x1f, 0x10, 0x1e, 0x10, 0x1f, 0x00], // 0xca Ê
+    [0x0a, 0x00, 0x1f, 0x10, 0x1e, 0x10, 0x1f, 0x00], // 0xcb Ë
+    [0x08, 0x04, 0x0e, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xcc Ì
+    [0x02, 0x04, 0x0e, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xcd Í
+    [0x04, 0x0a, 0x0e, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xce Î
+    [0x0a, 0x00, 0x0e, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xcf Ï
+    [0x1c, 0x12, 0x11, 0x1d, 0x11, 0x12, 0x1c, 0x00], // 0xd0 Ð
+    [0x0d, 0x12, 0x11, 0x19, 0x15, 0x13, 0x11, 0x00], // 0xd1 Ñ
+    [0x08, 0x04, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xd2 Ò
+    [0x02, 0x04, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xd3 Ó
+    [0x04, 0x0a, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xd4 Ô
+    [0x0d, 0x12, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xd5 Õ
+    [0x0a, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xd6 Ö
+    [0x00, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x00, 0x00], // 0xd7 ×
+    [0x0e, 0x13, 0x15, 0x15, 0x15, 0x19, 0x0e, 0x00], // 0xd8 Ø
+    [0x08, 0x04, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xd9 Ù
+    [0x02, 0x04, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xda Ú
+    [0x04, 0x0a, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xdb Û
+    [0x0a, 0x00, 0x11, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xdc Ü
+    [0x02, 0x04, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x00], // 0xdd Ý
+    [0x10, 0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x00], // 0xde Þ
+    [0x0c, 0x12, 0x12, 0x16, 0x11, 0x11, 0x16, 0x00], // 0xdf ß
+    [0x08, 0x04, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe0 à
+    [0x02, 0x04, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe1 á
+    [0x04, 0x0a, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe2 â
+    [0x0d, 0x12, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe3 ã
+    [0x0a, 0x00, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe4 ä
+    [0x0e, 0x0a, 0x0e, 0x01, 0x0f, 0x11, 0x0f, 0x00], // 0xe5 å
+    [0x00, 0x00, 0x1a, 0x05, 0x0f, 0x14, 0x0f, 0x00], // 0xe6 æ
+    [0x00, 0x0e, 0x10, 0x10, 0x0e, 0x04, 0x0c, 0x00], // 0xe7 ç
+    [0x08, 0x04, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00], // 0xe8 è
+    [0x02, 0x04, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00], // 0xe9 é
+    [0x04, 0x0a, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00], // 0xea ê
+    [0x0a, 0x00, 0x0e, 0x11, 0x1f, 0x10, 0x0e, 0x00], // 0xeb ë
+    [0x08, 0x04, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xec ì
+    [0x02, 0x04, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xed í
+    [0x04, 0x0a, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xee î
+    [0x0a, 0x00, 0x0c, 0x04, 0x04, 0x04, 0x0e, 0x00], // 0xef ï
+    [0x0a, 0x04, 0x0a, 0x0e, 0x11, 0x11, 0x0e, 0x00], // 0xf0 ð
+    [0x0d, 0x12, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00], // 0xf1 ñ
+    [0x08, 0x04, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xf2 ò
+    [0x02, 0x04, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xf3 ó
+    [0x04, 0x0a, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xf4 ô
+    [0x0d, 0x12, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xf5 õ
+    [0x0a, 0x00, 0x0e, 0x11, 0x11, 0x11, 0x0e, 0x00], // 0xf6 ö
+    [0x00, 0x04, 0x00, 0x1f, 0x00, 0x04, 0x00, 0x00], // 0xf7 ÷
+    [0x00, 0x00, 0x0e, 0x13, 0x15, 0x19, 0x0e, 0x00], // 0xf8 ø
+    [0x08, 0x04, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00], // 0xf9 ù
+    [0x02, 0x04, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00], // 0xfa ú
+    [0x04, 0x0a, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00], // 0xfb û
+    [0x0a, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0d, 0x00], // 0xfc ü
+    [0x02, 0x04, 0x11, 0x11, 0x0f, 0x01, 0x0e, 0x00], // 0xfd ý
+    [0x00, 0x10, 0x1e, 0x11, 0x1e, 0x10, 0x10, 0x00], // 0xfe þ
+    [0x0a, 0x00, 0x11, 0x11, 0x0f, 0x01, 0x0e, 0x00], // 0xff ÿ
+];
@@ -0,0 +1,542 @@
+//! Software emulator of the HD44780 controller, for host-side tests.
+//!
+//! [Hd44780Emulator] implements both [Hardware] and [Delay], so it could be given directly to
+//! [Display::new](crate::Display::new). It decodes pin transitions the same way the controller
+//! does (including 4-bit transfers and the reset-by-instruction sequence) and maintains DDRAM,
+//! CGRAM, address counter, display shift, entry mode and display control state, which could be
+//! inspected afterwards.
+//!
+//! Emulator keeps a virtual clock, advanced by [Delay::delay_us] calls, which is used to answer
+//! busy flag reads and to select the blink phase.
+//!
+//! With the `std` feature, display content could be rendered into a text snapshot
+//! (`snapshot`, for golden tests) or an image (`render`, which could be saved as SVG or PBM),
+//! using glyphs of the selected character generator [Rom] and the CGRAM.
+//!
+//! ```rust
+//! use lcd::*;
+//! use lcd::emulator::Hd44780Emulator;
+//!
+//! let mut lcd = Display::new(Hd44780Emulator::new(FunctionMode::Bit4));
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! lcd.position(3, 1);
+//! lcd.print("Hello");
+//!
+//! let emulator = lcd.unwrap();
+//! assert_eq!(emulator.character_at(3, 1, 16), b'H');
+//! assert_eq!(emulator.address(), 0x48);
+//! ```
+use crate::{Delay, FunctionMode, Hardware};
+
+mod font;
+#[cfg(feature = "std")]
+mod render;
+
+pub use self::font::Rom;
+#[cfg(feature = "std")]
+pub use self::render::Frame;
+
+/// Size of the DDRAM, in characters.
+pub const DDRAM_SIZE: usize = 80;
+/// Size of the CGRAM, in bytes.
+pub const CGRAM_SIZE: usize = 64;
+/// Length of the line in 2-line mode.
+const LINE_LENGTH: u8 = 40;
+/// Address of the second line in 2-line mode.
+const SECOND_LINE: u8 = 0x40;
+
+/// Execution time of the most instructions, in microseconds.
+const EXECUTION_US: u64 = 37;
+/// Execution time of the clear display and return home instructions, in microseconds.
+const EXECUTION_LONG_US: u64 = 1520;
+/// Time to update address counter after data read or write (tADD), in microseconds.
+const ADDRESS_UPDATE_US: u64 = 4;
+/// Duration of each blink phase, in microseconds.
+const BLINK_US: u64 = 409_600;
+
+/// Busy flag in the result of busy flag / address read.
+const BUSY_FLAG: u8 = 0x80;
+
+/// Software emulator of the HD44780 controller, see the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct Hd44780Emulator {
+    bus: FunctionMode,
+    readable: bool,
+    rom: Rom,
+
+    // Pins
+    rs: bool,
+    rw: bool,
+    enable: bool,
+    data: u8,
+
+    // Interface state
+    second_nibble: bool,
+    pending: u8,
+    output: u8,
+
+    // Function set
+    eight_bit: bool,
+    two_lines: bool,
+    large_font: bool,
+
+    // Display control
+    display_on: bool,
+    cursor_on: bool,
+    blink_on: bool,
+
+    // Entry mode
+    increment: bool,
+    shift: bool,
+
+    address: u8,
+    cgram_selected: bool,
+    display_shift: u8,
+    ddram: [u8; DDRAM_SIZE],
+    cgram: [u8; CGRAM_SIZE],
+
+    now: u64,
+    busy_until: u64,
+}
+
+impl Hd44780Emulator {
+    /// Create a new emulator of the display attached with the data bus of the given width
+    /// (`bus`). Controller is in the state it gets after the internal reset at power-on.
+    pub fn new(bus: FunctionMode) -> Self {
+        Hd44780Emulator {
+            bus,
+            readable: false,
+            rom: Rom::default(),
+            rs: false,
+            rw: false,
+            enable: false,
+            data: 0,
+            second_nibble: false,
+            pending: 0,
+            output: 0,
+            eight_bit: true,
+            two_lines: false,
+            large_font: false,
+            display_on: false,
+            cursor_on: false,
+            blink_on: false,
+            increment: true,
+            shift: false,
+            address: 0,
+            cgram_selected: false,
+            display_shift: 0,
+            ddram: [b' '; DDRAM_SIZE],
+            cgram: [0; CGRAM_SIZE],
+            now: 0,
+            busy_until: 0,
+        }
+    }
+
+    /// Enable or disable reading from the display (R/W pin being connected). When enabled,
+    /// [Display](crate::Display) polls busy flag instead of waiting.
+    pub fn set_readable(&mut self, readable: bool) {
+        self.readable = readable;
+    }
+
+    /// Select character generator ROM (`rom`) used to render the display content. Default is
+    /// [Rom::A00].
+    pub fn set_rom(&mut self, rom: Rom) {
+        self.rom = rom;
+    }
+
+    /// Character generator ROM used to render the display content.
+    pub fn rom(&self) -> Rom {
+        self.rom
+    }
+
+    /// Virtual time since the power-on, in microseconds.
+    pub fn now_us(&self) -> u64 {
+        self.now
+    }
+
+    /// If controller is busy executing the last instruction.
+    pub fn is_busy(&self) -> bool {
+        self.now < self.busy_until
+    }
+
+    /// Current value of the address counter.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// If address counter points to the CGRAM (`true`) or DDRAM (`false`).
+    pub fn is_cgram_address(&self) -> bool {
+        self.cgram_selected
+    }
+
+    /// If controller is in 8-bit mode (DL).
+    pub fn is_eight_bit(&self) -> bool {
+        self.eight_bit
+    }
+
+    /// If controller is in 2-line mode (N).
+    pub fn is_two_lines(&self) -> bool {
+        self.two_lines
+    }
+
+    /// If controller uses 5x10 font (F).
+    pub fn is_large_font(&self) -> bool {
+        self.large_font
+    }
+
+    /// If display is on (D).
+    pub fn is_display_on(&self) -> bool {
+        self.display_on
+    }
+
+    /// If cursor is on (C).
+    pub fn is_cursor_on(&self) -> bool {
+        self.cursor_on
+    }
+
+    /// If blinking of the cursor position character is on (B).
+    pub fn is_blink_on(&self) -> bool {
+        self.blink_on
+    }
+
+    /// Phase of the blinking at the current virtual time: `true` when character at the cursor
+    /// position is replaced with all dots on (if blinking is on).
+    pub fn blink_phase(&self) -> bool {
+        (self.now / BLINK_US) % 2 == 1
+    }
+
+    /// If address counter is incremented after data access (I/D).
+    pub fn is_increment(&self) -> bool {
+        self.increment
+    }
+
+    /// If display is shifted after data write (S).
+    pub fn is_shift(&self) -> bool {
+        self.shift
+    }
+
+    /// Current display shift, in characters (0 to the line length minus one), positive values
+    /// meaning display content moved to the left.
+    pub fn display_shift(&self) -> u8 {
+        self.display_shift
+    }
+
+    /// Content of the DDRAM. In 2-line mode, first 40 characters are the first line (addresses
+    /// `0x00` - `0x27`), next 40 characters are the second line (addresses `0x40` - `0x67`).
+    pub fn ddram(&self) -> &[u8; DDRAM_SIZE] {
+        &self.ddram
+    }
+
+    /// Content of the CGRAM.
+    pub fn cgram(&self) -> &[u8; CGRAM_SIZE] {
+        &self.cgram
+    }
+
+    /// Character at the given DDRAM address (`address`).
+    pub fn ddram_at(&self, address: u8) -> u8 {
+        self.ddram[self.ddram_index(address)]
+    }
+
+    /// Character visible at the given column (`col`) and row (`row`) of the display with the given
+    /// number of columns (`columns`), taking display shift into account. Rows 2 and 3 are the
+    /// continuation of the rows 0 and 1, as on the typical 4-row displays.
+    pub fn character_at(&self, col: u8, row: u8, columns: u8) -> u8 {
+        let (line, col) = if row >= 2 {
+            (row - 2, col + columns)
+        } else {
+            (row, col)
+        };
+        self.ddram_at(self.line_address(line, col))
+    }
+
+    /// Column (`col`) and row (`row`) of the cursor on the display with the given number of
+    /// columns (`columns`), taking display shift into account. Returns `None` if address counter
+    /// points to the CGRAM or cursor is not within the visible area.
+    pub fn cursor_position(&self, columns: u8, rows: u8) -> Option<(u8, u8)> {
+        if self.cgram_selected {
+            return None;
+        }
+        let (line, offset) = if self.two_lines {
+            if self.address >= SECOND_LINE {
+                (1, self.address - SECOND_LINE)
+            } else {
+                (0, self.address)
+            }
+        } else {
+            (0, self.address)
+        };
+        let length = self.line_length();
+        let col = (offset + length - self.display_shift % length) % length;
+        let (row, col) = if col >= columns {
+            (line + 2, col - columns)
+        } else {
+            (line, col)
+        };
+        if row < rows && col < columns {
+            Some((col, row))
+        } else {
+            None
+        }
+    }
+
+    /// Image of the custom character (`code`, 0-7 in 5x8 font, 0-3 in 5x10 font), one byte per
+    /// row.
+    pub fn custom_glyph(&self, code: u8) -> [u8; 8] {
+        let mut glyph = [0; 8];
+        let start = ((code & 0x7) as usize) * 8;
+        glyph.copy_from_slice(&self.cgram[start..start + 8]);
+        glyph
+    }
+
+    fn line_length(&self) -> u8 {
+        if self.two_lines {
+            LINE_LENGTH
+        } else {
+            DDRAM_SIZE as u8
+        }
+    }
+
+    /// DDRAM address of the given line (`line`) and visible column (`col`).
+    fn line_address(&self, line: u8, col: u8) -> u8 {
+        let length = self.line_length();
+        let offset = (col + self.display_shift) % length;
+        if self.two_lines && line > 0 {
+            SECOND_LINE + offset
+        } else {
+            offset
+        }
+    }
+
+    fn ddram_index(&self, address: u8) -> usize {
+        if self.two_lines {
+            if address >= SECOND_LINE {
+                (LINE_LENGTH + (address - SECOND_LINE) % LINE_LENGTH) as usize
+            } else {
+                (address % LINE_LENGTH) as usize
+            }
+        } else {
+            (address as usize) % DDRAM_SIZE
+        }
+    }
+
+    /// Move address counter forward (`forward`) or backward.
+    fn move_address(&mut self, forward: bool) {
+        if self.cgram_selected {
+            self.address = if forward {
+                self.address.wrapping_add(1)
+            } else {
+                self.address.wrapping_sub(1)
+            } & 0x3f;
+            return;
+        }
+        self.address = match (self.two_lines, forward, self.address) {
+            (true, true, 0x27) => 0x40,
+            (true, true, 0x67) => 0x00,
+            (true, false, 0x00) => 0x67,
+            (true, false, 0x40) => 0x27,
+            (false, true, 0x4f) => 0x00,
+            (false, false, 0x00) => 0x4f,
+            (_, true, address) => address.wrapping_add(1) & 0x7f,
+            (_, false, address) => address.wrapping_sub(1) & 0x7f,
+        };
+    }
+
+    /// Shift display to the left (`left`) or to the right.
+    fn move_display(&mut self, left: bool) {
+        let length = self.line_length();
+        self.display_shift = if left {
+            (self.display_shift + 1) % length
+        } else {
+            (self.display_shift + length - 1) % length
+        };
+    }
+
+    fn execute(&mut self, cmd: u8) {
+        let mut duration = EXECUTION_US;
+        if cmd & 0x80 != 0 {
+            // Set DDRAM address
+            self.cgram_selected = false;
+            self.address = cmd & 0x7f;
+        } else if cmd & 0x40 != 0 {
+            // Set CGRAM address
+            self.cgram_selected = true;
+            self.address = cmd & 0x3f;
+        } else if cmd & 0x20 != 0 {
+            // Function set
+            let eight_bit = cmd & 0x10 != 0;
+            if eight_bit != self.eight_bit {
+                self.second_nibble = false;
+            }
+            self.eight_bit = eight_bit;
+            self.two_lines = cmd & 0x08 != 0;
+            self.large_font = cmd & 0x04 != 0;
+            self.display_shift %= self.line_length();
+        } else if cmd & 0x10 != 0 {
+            // Cursor or display shift
+            let right = cmd & 0x04 != 0;
+            if cmd & 0x08 != 0 {
+                self.move_display(!right);
+            } else {
+                self.move_address(right);
+            }
+        } else if cmd & 0x08 != 0 {
+            // Display control
+            self.display_on = cmd & 0x04 != 0;
+            self.cursor_on = cmd & 0x02 != 0;
+            self.blink_on = cmd & 0x01 != 0;
+        } else if cmd & 0x04 != 0 {
+            // Entry mode set
+            self.increment = cmd & 0x02 != 0;
+            self.shift = cmd & 0x01 != 0;
+        } else if cmd & 0x02 != 0 {
+            // Return home
+            self.cgram_selected = false;
+            self.address = 0;
+            self.display_shift = 0;
+            duration = EXECUTION_LONG_US;
+        } else if cmd & 0x01 != 0 {
+            // Clear display
+            self.ddram = [b' '; DDRAM_SIZE];
+            self.cgram_selected = false;
+            self.address = 0;
+            self.display_shift = 0;
+            self.increment = true;
+            duration = EXECUTION_LONG_US;
+        }
+        self.busy_until = self.now + duration;
+    }
+
+    fn write_data(&mut self, data: u8) {
+        if self.cgram_selected {
+            self.cgram[(self.address & 0x3f) as usize] = data & 0x1f;
+        } else {
+            let index = self.ddram_index(self.address);
+            self.ddram[index] = data;
+            if self.shift {
+                self.move_display(self.increment);
+            }
+        }
+        self.move_address(self.increment);
+        self.busy_until = self.now + EXECUTION_US + ADDRESS_UPDATE_US;
+    }
+
+    fn read_register(&self) -> u8 {
+        if self.rs {
+            if self.cgram_selected {
+                self.cgram[(self.address & 0x3f) as usize]
+            } else {
+                self.ddram_at(self.address)
+            }
+        } else {
+            let busy = if self.is_busy() { BUSY_FLAG } else { 0 };
+            busy | (self.address & 0x7f)
+        }
+    }
+
+    /// Width of the transfer on the wires: only D7-D4 are used in 4-bit mode.
+    fn four_bit_transfer(&self) -> bool {
+        !self.eight_bit
+    }
+
+    /// Value of D7-D0 as seen by the controller.
+    fn bus_value(&self) -> u8 {
+        match self.bus {
+            FunctionMode::Bit8 => self.data,
+            // Only D7-D4 are connected
+            FunctionMode::Bit4 => (self.data & 0xf) << 4,
+        }
+    }
+
+    fn enable_rising(&mut self) {
+        if !self.rw {
+            return;
+        }
+        let value = if self.four_bit_transfer() && self.second_nibble {
+            self.pending << 4
+        } else {
+            let value = self.read_register();
+            self.pending = value & 0xf;
+            value
+        };
+        self.output = match self.bus {
+            FunctionMode::Bit8 => value,
+            FunctionMode::Bit4 => value >> 4,
+        };
+    }
+
+    fn enable_falling(&mut self) {
+        let complete = if self.four_bit_transfer() {
+            self.second_nibble = !self.second_nibble;
+            !self.second_nibble
+        } else {
+            true
+        };
+
+        if self.rw {
+            if complete && self.rs {
+                self.move_address(self.increment);
+                self.busy_until = self.now + ADDRESS_UPDATE_US;
+            }
+            return;
+        }
+
+        let value = self.bus_value();
+        let byte = if self.four_bit_transfer() {
+            if !complete {
+                self.pending = value >> 4;
+                return;
+            }
+            (self.pending << 4) | (value >> 4)
+        } else {
+            value
+        };
+
+        if self.rs {
+            self.write_data(byte);
+        } else {
+            self.execute(byte);
+        }
+    }
+}
+
+impl Hardware for Hd44780Emulator {
+    fn rs(&mut self, bit: bool) {
+        self.rs = bit;
+    }
+
+    fn enable(&mut self, bit: bool) {
+        if bit && !self.enable {
+            self.enable = true;
+            self.enable_rising();
+        } else if !bit && self.enable {
+            self.enable = false;
+            self.enable_falling();
+        }
+    }
+
+    fn data(&mut self, data: u8) {
+        self.data = data;
+    }
+
+    fn mode(&self) -> FunctionMode {
+        self.bus
+    }
+
+    fn can_read(&self) -> bool {
+        self.readable
+    }
+
+    fn rw(&mut self, bit: bool) {
+        self.rw = bit;
+    }
+
+    fn read_data(&mut self) -> u8 {
+        self.output
+    }
+}
+
+impl Delay for Hd44780Emulator {
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.now += u64::from(delay_usec);
+    }
+}
@@ -0,0 +1,208 @@
+//! Rendering of the emulated display content into text snapshots and images.
+use super::Hd44780Emulator;
+use std::fmt::Write;
+use std::string::String;
+use std::vec;
+use std::vec::Vec;
+
+/// Width of the character cell, in dots.
+const CELL_WIDTH: usize = 5;
+/// Height of the character cell in 5x8 font, in dots (including the cursor row).
+const CELL_HEIGHT: usize = 8;
+/// Height of the character cell in 5x10 font, in dots (including the cursor row).
+const CELL_HEIGHT_LARGE: usize = 11;
+/// Gap between the character cells (both horizontal and vertical), in dots.
+const GAP: usize = 1;
+/// Maximum length of the line in the PBM file.
+const PBM_LINE: usize = 70;
+
+/// Color of the dots which are on, in SVG images.
+const SVG_ON: &str = "#1e2a10";
+/// Color of the dots which are off, in SVG images.
+const SVG_OFF: &str = "#8fb838";
+/// Color of the background (gaps between the dots), in SVG images.
+const SVG_BACKGROUND: &str = "#9bc53d";
+
+/// Monochrome image of the display, one dot per pixel. Character cells are separated by a gap
+/// of one dot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cell_height: usize,
+    dots: Vec<bool>,
+}
+
+impl Frame {
+    fn new(columns: u8, rows: u8, cell_height: usize) -> Self {
+        assert!(columns > 0 && rows > 0);
+        let width = usize::from(columns) * (CELL_WIDTH + GAP) - GAP;
+        let height = usize::from(rows) * (cell_height + GAP) - GAP;
+        Frame {
+            width,
+            height,
+            cell_height,
+            dots: vec![false; width * height],
+        }
+    }
+
+    /// Width of the image, in dots.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the image, in dots.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// If dot at the given coordinates (`x`, `y`) is on. Panics if out of range.
+    pub fn dot(&self, x: usize, y: usize) -> bool {
+        assert!(x < self.width && y < self.height);
+        self.dots[y * self.width + x]
+    }
+
+    fn set_dot(&mut self, x: usize, y: usize, on: bool) {
+        self.dots[y * self.width + x] = on;
+    }
+
+    /// Image in the plain PBM (`P1`) format, dots which are on being black.
+    pub fn to_pbm(&self) -> String {
+        let mut pbm = String::new();
+        writeln!(pbm, "P1").unwrap();
+        writeln!(pbm, "{} {}", self.width, self.height).unwrap();
+        for row in self.dots.chunks(self.width) {
+            for line in row.chunks(PBM_LINE) {
+                pbm.extend(line.iter().map(|&on| if on { '1' } else { '0' }));
+                pbm.push('\n');
+            }
+        }
+        pbm
+    }
+
+    /// Image in the SVG format, with each dot being a square of the given size (`scale`, in
+    /// pixels).
+    pub fn to_svg(&self, scale: usize) -> String {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width, height, SVG_BACKGROUND
+        )
+        .unwrap();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if self.is_gap(x, y) {
+                    continue;
+                }
+                let fill = if self.dot(x, y) { SVG_ON } else { SVG_OFF };
+                writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{s}" height="{s}" fill="{}"/>"#,
+                    x * scale,
+                    y * scale,
+                    fill,
+                    s = scale
+                )
+                .unwrap();
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// If given coordinates (`x`, `y`) are in the gap between the character cells.
+    fn is_gap(&self, x: usize, y: usize) -> bool {
+        x % (CELL_WIDTH + GAP) >= CELL_WIDTH || y % (self.cell_height + GAP) >= self.cell_height
+    }
+}
+
+impl Hd44780Emulator {
+    /// Text snapshot of the display with the given number of columns (`columns`) and rows
+    /// (`rows`), taking display shift into account. Characters are mapped with [Rom::to_char] of
+    /// the selected ROM, each row is enclosed in `|` and followed by a new line. Display control
+    /// (display on / off, cursor and blinking) is not taken into account.
+    ///
+    /// [Rom::to_char]: super::Rom::to_char
+    pub fn snapshot(&self, columns: u8, rows: u8) -> String {
+        let mut snapshot = String::new();
+        for row in 0..rows {
+            snapshot.push('|');
+            for col in 0..columns {
+                snapshot.push(self.rom.to_char(self.character_at(col, row, columns)));
+            }
+            snapshot.push_str("|\n");
+        }
+        snapshot
+    }
+
+    /// Image of the display with the given number of columns (`columns`) and rows (`rows`), as it
+    /// looks at the current virtual time: glyphs come from the selected ROM or CGRAM, cursor
+    /// underline and blinking (see [Hd44780Emulator::blink_phase]) are shown, nothing is shown
+    /// if display is off. Panics if there are no columns or rows.
+    pub fn render(&self, columns: u8, rows: u8) -> Frame {
+        let cell_height = self.cell_height();
+        let mut frame = Frame::new(columns, rows, cell_height);
+        if !self.display_on {
+            return frame;
+        }
+
+        let cursor = self.cursor_position(columns, rows);
+        for row in 0..rows {
+            for col in 0..columns {
+                let mut glyph = self.cell_glyph(self.character_at(col, row, columns));
+                if cursor == Some((col, row)) {
+                    if self.blink_on && self.blink_phase() {
+                        glyph = [0x1f; CELL_HEIGHT_LARGE];
+                    } else if self.cursor_on {
+                        glyph[cell_height - 1] = 0x1f;
+                    }
+                }
+
+                let left = usize::from(col) * (CELL_WIDTH + GAP);
+                let top = usize::from(row) * (cell_height + GAP);
+                for (y, bits) in glyph.iter().take(cell_height).enumerate() {
+                    for x in 0..CELL_WIDTH {
+                        let on = bits & (0x10 >> x) != 0;
+                        frame.set_dot(left + x, top + y, on);
+                    }
+                }
+            }
+        }
+        frame
+    }
+
+    /// Height of the character cell: 5x10 font is only available in 1-line mode.
+    fn cell_height(&self) -> usize {
+        if self.large_font && !self.two_lines {
+            CELL_HEIGHT_LARGE
+        } else {
+            CELL_HEIGHT
+        }
+    }
+
+    /// Glyph of the given character code (`code`), from either CGRAM or ROM.
+    fn cell_glyph(&self, code: u8) -> [u8; CELL_HEIGHT_LARGE] {
+        let mut glyph = [0; CELL_HEIGHT_LARGE];
+        if code < 0x10 {
+            if self.cell_height() == CELL_HEIGHT_LARGE {
+                // 5x10 characters take 16 bytes of CGRAM each, character code bit 0 is ignored
+                let start = usize::from((code >> 1) & 0x3) * 16;
+                glyph.copy_from_slice(&self.cgram[start..start + CELL_HEIGHT_LARGE]);
+            } else {
+                glyph[..CELL_HEIGHT].copy_from_slice(&self.custom_glyph(code));
+            }
+        } else {
+            glyph[..CELL_HEIGHT].copy_from_slice(&self.rom.glyph(code));
+        }
+        glyph
+    }
+}
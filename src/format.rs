@@ -0,0 +1,174 @@
+//! Formatting of numbers and aligned text into byte buffers, without `core::fmt`, which takes
+//! several kilobytes of flash on small microcontrollers. Used by the printing methods of
+//! [Display] below and by the fields of [Screen](crate::screen::Screen).
+use crate::{Delay, Display, Hardware};
+
+/// Length of the longest formatted number (sign, 10 digits and decimal point).
+pub(crate) const NUMBER_LENGTH: usize = 12;
+
+/// Character filling the field of a number that doesn't fit in it.
+const OVERFLOW: u8 = b'#';
+
+/// Alignment of the text in the field.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Align {
+    /// Text starts at the first column of the field.
+    #[default]
+    Left,
+    /// Text ends at the last column of the field.
+    Right,
+    /// Text is in the middle of the field (closer to the left if it can't be exactly in the
+    /// middle).
+    Center,
+}
+
+/// Format the number (`value`) into the buffer (`buffer`) as decimal digits.
+pub(crate) fn format_u32(value: u32, buffer: &mut [u8; NUMBER_LENGTH]) -> &[u8] {
+    let start = digits(value, 0, buffer);
+    &buffer[start..]
+}
+
+/// Format the number (`value`) into the buffer (`buffer`) as decimal digits, with the decimal
+/// point before the last `scale` digits (0 to 9).
+pub(crate) fn format_i32(value: i32, scale: u8, buffer: &mut [u8; NUMBER_LENGTH]) -> &[u8] {
+    let mut start = digits(value.unsigned_abs(), scale, buffer);
+    if value < 0 {
+        start -= 1;
+        buffer[start] = b'-';
+    }
+    &buffer[start..]
+}
+
+/// Format the number (`value`) into the buffer (`buffer`) as upper case hexadecimal digits,
+/// at least `width` (up to 8) of them.
+pub(crate) fn format_hex(value: u32, width: u8, buffer: &mut [u8; NUMBER_LENGTH]) -> &[u8] {
+    assert!(width <= 8);
+    let mut rest = value;
+    let mut start = buffer.len();
+    while rest != 0 || start == buffer.len() || buffer.len() - start < usize::from(width) {
+        start -= 1;
+        buffer[start] = b"0123456789ABCDEF"[(rest & 0xf) as usize];
+        rest >>= 4;
+    }
+    &buffer[start..]
+}
+
+/// Write the text (`text`) into the field (`field`) with the given alignment (`align`), padded
+/// with spaces. Text longer than the field is cut at the end.
+pub(crate) fn align(text: &[u8], align: Align, field: &mut [u8]) {
+    let length = text.len().min(field.len());
+    let start = padding(length, field.len(), align);
+    for item in field.iter_mut() {
+        *item = b' ';
+    }
+    field[start..start + length].copy_from_slice(&text[..length]);
+}
+
+/// Number of spaces before the text of the given length (`length`) in the field `width`
+/// characters wide with the given alignment (`align`).
+fn padding(length: usize, width: usize, align: Align) -> usize {
+    match align {
+        Align::Left => 0,
+        Align::Right => width - length,
+        Align::Center => (width - length) / 2,
+    }
+}
+
+/// Put decimal digits of the number (`value`) at the end of the buffer (`buffer`), with the
+/// decimal point before the last `scale` digits. Returns the index of the first digit.
+fn digits(value: u32, scale: u8, buffer: &mut [u8; NUMBER_LENGTH]) -> usize {
+    assert!(scale <= 9);
+    let mut rest = value;
+    let mut start = buffer.len();
+    let mut count = 0;
+    // At least one digit before the decimal point
+    while rest != 0 || count <= scale {
+        if scale > 0 && count == scale {
+            start -= 1;
+            buffer[start] = b'.';
+        }
+        start -= 1;
+        buffer[start] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        count += 1;
+    }
+    start
+}
+
+/// Printing of numbers and aligned text without `core::fmt`, which takes several kilobytes of
+/// flash on small microcontrollers.
+impl<HW: Hardware + Delay> Display<HW> {
+    /// Print the number (`value`) as decimal digits.
+    pub fn print_u32(&mut self, value: u32) -> &Self {
+        let mut buffer = [0; NUMBER_LENGTH];
+        self.print_bytes(format_u32(value, &mut buffer))
+    }
+
+    /// Print the number (`value`) as decimal digits, with the minus sign if it is negative.
+    pub fn print_i32(&mut self, value: i32) -> &Self {
+        let mut buffer = [0; NUMBER_LENGTH];
+        self.print_bytes(format_i32(value, 0, &mut buffer))
+    }
+
+    /// Print the fixed-point number (`value`) with the decimal point before the last `scale`
+    /// digits (0 to 9), so `-1234` is printed as `-12.34` with `scale` of 2.
+    pub fn print_decimal(&mut self, value: i32, scale: u8) -> &Self {
+        let mut buffer = [0; NUMBER_LENGTH];
+        self.print_bytes(format_i32(value, scale, &mut buffer))
+    }
+
+    /// Print the number (`value`) as upper case hexadecimal digits, padded with zeros to at least
+    /// `width` digits (0 to 8).
+    pub fn print_hex(&mut self, value: u32, width: u8) -> &Self {
+        let mut buffer = [0; NUMBER_LENGTH];
+        self.print_bytes(format_hex(value, width, &mut buffer))
+    }
+
+    /// Print the given string (`str`) right-aligned in the field `width` characters wide, padded
+    /// with spaces. String longer than the field is cut at the end.
+    pub fn print_right(&mut self, str: &str, width: u8) -> &Self {
+        self.print_aligned(str.as_bytes(), width, Align::Right)
+    }
+
+    /// Print the given string (`str`) in the middle of the field `width` characters wide, padded
+    /// with spaces. String longer than the field is cut at the end.
+    pub fn print_centered(&mut self, str: &str, width: u8) -> &Self {
+        self.print_aligned(str.as_bytes(), width, Align::Center)
+    }
+
+    /// Print the fixed-point number (`value`, see [Display::print_decimal]) in the field `width`
+    /// characters wide with the given alignment (`align`), padded with spaces. Number wider than
+    /// the field is not cut (that would show a wrong value), the field is filled with `#` instead.
+    pub fn print_number(&mut self, value: i32, scale: u8, width: u8, align: Align) -> &Self {
+        let mut buffer = [0; NUMBER_LENGTH];
+        let text = format_i32(value, scale, &mut buffer);
+        if text.len() > usize::from(width) {
+            for _ in 0..width {
+                self.write(OVERFLOW);
+            }
+            return self;
+        }
+        self.print_aligned(text, width, align)
+    }
+
+    fn print_aligned(&mut self, text: &[u8], width: u8, align: Align) -> &Self {
+        let width = usize::from(width);
+        let length = text.len().min(width);
+        let before = padding(length, width, align);
+        for _ in 0..before {
+            self.write(b' ');
+        }
+        self.print_bytes(&text[..length]);
+        for _ in before + length..width {
+            self.write(b' ');
+        }
+        self
+    }
+
+    fn print_bytes(&mut self, text: &[u8]) -> &Self {
+        for &data in text {
+            self.write(data);
+        }
+        self
+    }
+}
@@ -0,0 +1,78 @@
+/// DDRAM address of the second row.
+const SECOND_ROW: u8 = 0x40;
+/// Number of characters in DDRAM in 1-line mode.
+const DDRAM_1LINE: u8 = 80;
+/// Number of characters in DDRAM per line in 2-line mode.
+const DDRAM_2LINE: u8 = 40;
+/// Number of characters in DDRAM per line in 4-line mode of KS0073 / SSD1803A.
+const DDRAM_4LINE: u8 = 0x20;
+
+/// Size of the display in characters (`columns` x `rows`), which defines DDRAM address of each
+/// row.
+///
+/// Rows 0 and 1 start at the addresses `0x00` and `0x40`, rows 2 and 3 continue rows 0 and 1, so
+/// they start at `columns` and `0x40 + columns` (`0x14` and `0x54` for 20x4 displays, `0x10` and
+/// `0x50` for 16x4 displays). Controllers with native 4-line mode use a different layout, see
+/// [Geometry::four_line].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Geometry {
+    columns: u8,
+    rows: u8,
+    /// If rows start at `0x00`, `0x20`, `0x40` and `0x60`.
+    four_line: bool,
+}
+
+impl Geometry {
+    /// Create a new geometry with the given number of columns (`columns`) and rows (`rows`, 1 to
+    /// 4). Panics if such display does not fit DDRAM.
+    pub fn new(columns: u8, rows: u8) -> Self {
+        assert!(columns > 0 && rows > 0 && rows <= 4);
+        let limit = match rows {
+            1 => DDRAM_1LINE,
+            2 => DDRAM_2LINE,
+            _ => DDRAM_2LINE / 2,
+        };
+        assert!(columns <= limit);
+        Geometry {
+            columns,
+            rows,
+            four_line: false,
+        }
+    }
+
+    /// Create a new geometry of 4 rows with the given number of columns (`columns`), for the
+    /// native 4-line mode of KS0073 / SSD1803A controllers, where rows start at the addresses
+    /// `0x00`, `0x20`, `0x40` and `0x60`. Panics if such display does not fit DDRAM.
+    pub fn four_line(columns: u8) -> Self {
+        assert!(columns > 0 && columns <= DDRAM_4LINE);
+        Geometry {
+            columns,
+            rows: 4,
+            four_line: true,
+        }
+    }
+
+    /// Number of columns.
+    pub fn columns(self) -> u8 {
+        self.columns
+    }
+
+    /// Number of rows.
+    pub fn rows(self) -> u8 {
+        self.rows
+    }
+
+    /// DDRAM address of the character at the given column (`col`) and row (`row`). Panics if
+    /// column or row is out of range.
+    pub fn address(self, col: u8, row: u8) -> u8 {
+        assert!(col < self.columns && row < self.rows);
+        let offset = match row {
+            _ if self.four_line => row * DDRAM_4LINE,
+            0 => 0,
+            1 => SECOND_ROW,
+            2 => self.columns,
+            _ => SECOND_ROW + self.columns,
+        };
+        offset + col
+    }
+}
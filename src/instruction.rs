@@ -0,0 +1,300 @@
+use crate::{
+    Command, Direction, DisplayBlink, DisplayCursor, DisplayMode, EntryModeDirection,
+    EntryModeShift, FunctionDots, FunctionLine, FunctionMode, Scroll,
+};
+use core::fmt;
+
+/// Busy flag in the result of busy flag / address read.
+const BUSY_FLAG: u8 = 0x80;
+
+/// Single HD44780 bus transfer (instruction, data write or read), with its arguments.
+///
+/// This is the encoding used by [Display](crate::Display), so it could also be used to decode
+/// the traffic captured from the bus.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Instruction {
+    ClearDisplay,
+    ReturnHome,
+    EntryModeSet(EntryModeDirection, EntryModeShift),
+    DisplayControl(DisplayMode, DisplayCursor, DisplayBlink),
+    CursorShift(Scroll, Direction),
+    FunctionSet(FunctionMode, FunctionLine, FunctionDots),
+    SetCGRamAddr(u8),
+    SetDDRamAddr(u8),
+    /// Write data to CGRAM or DDRAM.
+    Write(u8),
+    /// Read busy flag and address counter.
+    ReadBusyAddress {
+        busy: bool,
+        address: u8,
+    },
+    /// Read data from CGRAM or DDRAM.
+    Read(u8),
+}
+
+impl Instruction {
+    /// Decode transfer from the state of R/S (`rs`) and R/W (`rw`) pins and the byte transferred
+    /// (`byte`). Returns `None` for the instruction code `0x00`, which is not defined.
+    pub fn decode(rs: bool, rw: bool, byte: u8) -> Option<Self> {
+        let bit = |mask: u8| byte & mask != 0;
+        let instruction = match (rs, rw) {
+            (true, false) => Instruction::Write(byte),
+            (true, true) => Instruction::Read(byte),
+            (false, true) => Instruction::ReadBusyAddress {
+                busy: bit(BUSY_FLAG),
+                address: byte & !BUSY_FLAG,
+            },
+            _ if bit(0x80) => Instruction::SetDDRamAddr(byte & 0x7f),
+            _ if bit(0x40) => Instruction::SetCGRamAddr(byte & 0x3f),
+            _ if bit(0x20) => Instruction::FunctionSet(
+                if bit(0x10) {
+                    FunctionMode::Bit8
+                } else {
+                    FunctionMode::Bit4
+                },
+                if bit(0x08) {
+                    FunctionLine::Line2
+                } else {
+                    FunctionLine::Line1
+                },
+                if bit(0x04) {
+                    FunctionDots::Dots5x10
+                } else {
+                    FunctionDots::Dots5x8
+                },
+            ),
+            _ if bit(0x10) => Instruction::CursorShift(
+                if bit(0x08) {
+                    Scroll::DisplayMove
+                } else {
+                    Scroll::CursorMove
+                },
+                if bit(0x04) {
+                    Direction::Right
+                } else {
+                    Direction::Left
+                },
+            ),
+            _ if bit(0x08) => Instruction::DisplayControl(
+                if bit(0x04) {
+                    DisplayMode::DisplayOn
+                } else {
+                    DisplayMode::DisplayOff
+                },
+                if bit(0x02) {
+                    DisplayCursor::CursorOn
+                } else {
+                    DisplayCursor::CursorOff
+                },
+                if bit(0x01) {
+                    DisplayBlink::BlinkOn
+                } else {
+                    DisplayBlink::BlinkOff
+                },
+            ),
+            _ if bit(0x04) => Instruction::EntryModeSet(
+                if bit(0x02) {
+                    EntryModeDirection::EntryRight
+                } else {
+                    EntryModeDirection::EntryLeft
+                },
+                if bit(0x01) {
+                    EntryModeShift::Shift
+                } else {
+                    EntryModeShift::NoShift
+                },
+            ),
+            _ if bit(0x02) => Instruction::ReturnHome,
+            _ if bit(0x01) => Instruction::ClearDisplay,
+            _ => return None,
+        };
+        Some(instruction)
+    }
+
+    /// State of the R/S pin for this transfer.
+    pub fn rs(self) -> bool {
+        matches!(self, Instruction::Write(_) | Instruction::Read(_))
+    }
+
+    /// State of the R/W pin for this transfer.
+    pub fn rw(self) -> bool {
+        matches!(
+            self,
+            Instruction::ReadBusyAddress { .. } | Instruction::Read(_)
+        )
+    }
+
+    /// Byte transferred over the data bus.
+    pub fn byte(self) -> u8 {
+        match self {
+            Instruction::ClearDisplay => Command::ClearDisplay as u8,
+            Instruction::ReturnHome => Command::ReturnHome as u8,
+            Instruction::EntryModeSet(dir, shift) => {
+                (Command::EntryModeSet as u8) | (dir as u8) | (shift as u8)
+            }
+            Instruction::DisplayControl(display, cursor, blink) => {
+                (Command::DisplayControl as u8) | (display as u8) | (cursor as u8) | (blink as u8)
+            }
+            Instruction::CursorShift(scroll, dir) => {
+                (Command::CursorShift as u8) | (scroll as u8) | (dir as u8)
+            }
+            Instruction::FunctionSet(mode, line, dots) => {
+                (Command::FunctionSet as u8) | (mode as u8) | (line as u8) | (dots as u8)
+            }
+            Instruction::SetCGRamAddr(address) => (Command::SetCGRamAddr as u8) | (address & 0x3f),
+            Instruction::SetDDRamAddr(address) => (Command::SetDDRamAddr as u8) | (address & 0x7f),
+            Instruction::Write(data) | Instruction::Read(data) => data,
+            Instruction::ReadBusyAddress { busy, address } => {
+                if busy {
+                    BUSY_FLAG | address
+                } else {
+                    address
+                }
+            }
+        }
+    }
+
+    /// If this is clear display or return home instruction, which take longer to execute.
+    pub fn is_long(self) -> bool {
+        matches!(self, Instruction::ClearDisplay | Instruction::ReturnHome)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        match *self {
+            Instruction::ClearDisplay => write!(f, "clear display"),
+            Instruction::ReturnHome => write!(f, "return home"),
+            Instruction::EntryModeSet(dir, shift) => write!(
+                f,
+                "entry mode set: {}, {}",
+                match dir {
+                    EntryModeDirection::EntryLeft => "decrement",
+                    EntryModeDirection::EntryRight => "increment",
+                },
+                match shift {
+                    EntryModeShift::NoShift => "no shift",
+                    EntryModeShift::Shift => "shift",
+                }
+            ),
+            Instruction::DisplayControl(display, cursor, blink) => write!(
+                f,
+                "display control: display {}, cursor {}, blink {}",
+                on_off(display == DisplayMode::DisplayOn),
+                on_off(cursor == DisplayCursor::CursorOn),
+                on_off(blink == DisplayBlink::BlinkOn)
+            ),
+            Instruction::CursorShift(scroll, dir) => write!(
+                f,
+                "{} shift {}",
+                match scroll {
+                    Scroll::CursorMove => "cursor",
+                    Scroll::DisplayMove => "display",
+                },
+                match dir {
+                    Direction::Left => "left",
+                    Direction::Right => "right",
+                }
+            ),
+            Instruction::FunctionSet(mode, line, dots) => write!(
+                f,
+                "function set: {}, {}, {}",
+                match mode {
+                    FunctionMode::Bit4 => "4-bit",
+                    FunctionMode::Bit8 => "8-bit",
+                },
+                match line {
+                    FunctionLine::Line1 => "1 line",
+                    FunctionLine::Line2 => "2 lines",
+                },
+                match dots {
+                    FunctionDots::Dots5x8 => "5x8 dots",
+                    FunctionDots::Dots5x10 => "5x10 dots",
+                }
+            ),
+            Instruction::SetCGRamAddr(address) => write!(f, "set CGRAM address 0x{:02x}", address),
+            Instruction::SetDDRamAddr(address) => write!(f, "set DDRAM address 0x{:02x}", address),
+            Instruction::Write(data) => write!(f, "write 0x{:02x}", data),
+            Instruction::ReadBusyAddress { busy, address } => write!(
+                f,
+                "read busy flag: {}, address 0x{:02x}",
+                if busy { "busy" } else { "ready" },
+                address
+            ),
+            Instruction::Read(data) => write!(f, "read 0x{:02x}", data),
+        }
+    }
+}
+
+/// Result of latching a single transfer into [Assembler].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Transfer {
+    /// First half of the 4-bit transfer, waiting for the second one.
+    Half,
+    /// Complete transfer; `None` if instruction is not defined.
+    Complete(Option<Instruction>),
+    /// R/S or R/W changed between the halves of 4-bit transfer; first half was dropped and the
+    /// new one is waiting for its pair.
+    Incomplete,
+}
+
+/// Assembles bus transfers into instructions. In 4-bit mode halves are paired, following the
+/// interface width set by the function set instructions (display starts in 8-bit mode).
+#[derive(Clone, Debug)]
+pub(crate) struct Assembler {
+    bus: FunctionMode,
+    eight_bit: bool,
+    pending: Option<(bool, bool, u8)>,
+}
+
+impl Assembler {
+    pub fn new(bus: FunctionMode) -> Self {
+        Assembler {
+            bus,
+            eight_bit: true,
+            pending: None,
+        }
+    }
+
+    /// Assume the interface width was already set to the bus width.
+    pub fn assume_initialized(&mut self) {
+        self.eight_bit = self.bus == FunctionMode::Bit8;
+    }
+
+    /// If there is a first half of the 4-bit transfer waiting for its pair.
+    #[cfg(feature = "std")]
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Latch data bus (`data`, D3-D0 in 4-bit mode) with the given state of R/S (`rs`) and R/W
+    /// (`rw`) pins.
+    pub fn latch(&mut self, rs: bool, rw: bool, data: u8) -> Transfer {
+        let byte = match self.bus {
+            FunctionMode::Bit8 => data,
+            // D3-D0 are not connected
+            FunctionMode::Bit4 if self.eight_bit => (data & 0xf) << 4,
+            FunctionMode::Bit4 => match self.pending.take() {
+                Some((first_rs, first_rw, high)) if first_rs == rs && first_rw == rw => {
+                    (high << 4) | (data & 0xf)
+                }
+                pending => {
+                    self.pending = Some((rs, rw, data & 0xf));
+                    return if pending.is_some() {
+                        Transfer::Incomplete
+                    } else {
+                        Transfer::Half
+                    };
+                }
+            },
+        };
+
+        let instruction = Instruction::decode(rs, rw, byte);
+        if let Some(Instruction::FunctionSet(mode, _, _)) = instruction {
+            self.eight_bit = mode == FunctionMode::Bit8;
+            self.pending = None;
+        }
+        Transfer::Complete(instruction)
+    }
+}
@@ -0,0 +1,349 @@
+//! Support for the extended function set of Samsung KS0073 and Solomon SSD1803A controllers.
+//!
+//! These controllers are compatible with HD44780, but have an extended instruction set (selected
+//! by the RE bit of the function set instruction), which enables native 4-line mode, per-line
+//! horizontal scrolling, bidirectional entry (mirrored segments / commons) and, on SSD1803A,
+//! double height lines.
+//!
+//! In 4-line mode, DDRAM rows start at addresses `0x00`, `0x20`, `0x40` and `0x60`, so
+//! [Display::init_extended] sets the display geometry to [Geometry::four_line], which is used by
+//! [Display::position]. Widgets should be given the same geometry.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::ks0073::{Controller, Ks0073, Lines};
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(Ks0073::new(hw, Controller::Ks0073));
+//! lcd.init_extended(Lines::Line4);
+//! lcd.display(DisplayMode::DisplayOn, DisplayCursor::CursorOff, DisplayBlink::BlinkOff);
+//! lcd.position(0, 3);
+//! lcd.print("Bottom line");
+//! ```
+use crate::{
+    Command, Delay, Display, FunctionDots, FunctionLine, FunctionMode, Geometry, Hardware,
+    InitConfig,
+};
+
+/// Number of columns in 4-line mode.
+const COLUMNS_4LINE: u8 = 20;
+
+/// Number of lines bit of the function set instruction.
+const FUNCTION_N: u8 = 0x08;
+/// Double height bit of the function set instruction (SSD1803A, RE = 0).
+const FUNCTION_DH: u8 = 0x04;
+/// Special register bit of the function set instruction (SSD1803A, RE = 0).
+const FUNCTION_IS: u8 = 0x01;
+
+/// Extended instructions (available when RE = 1).
+#[derive(Copy, Clone, Debug)]
+enum ExtendedCommand {
+    /// Bidirectional entry (instead of entry mode set).
+    Bidirectional = 0x04,
+    /// Extended function set (instead of display control).
+    ExtendedFunctionSet = 0x08,
+    /// Scroll enable (KS0073) or double height (SSD1803A, IS = 0) (instead of cursor or display
+    /// shift).
+    ScrollEnableDoubleHeight = 0x10,
+    /// Scroll quantity (instead of set DDRAM address).
+    ScrollQuantity = 0x80,
+}
+
+/// 6-dot font width bit of the extended function set instruction.
+const EXTENDED_FW: u8 = 0x04;
+/// Black / white inverting cursor bit of the extended function set instruction.
+const EXTENDED_BW: u8 = 0x02;
+/// 4-line mode bit of the extended function set instruction.
+const EXTENDED_NW: u8 = 0x01;
+
+/// Maximum scroll quantity, in dots.
+pub const MAX_SCROLL_QUANTITY: u8 = 48;
+
+/// Controller flavor, which defines the layout of the function set instruction.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Controller {
+    Ks0073,
+    Ssd1803a,
+}
+
+impl Controller {
+    /// RE bit of the function set instruction.
+    fn re(self) -> u8 {
+        match self {
+            Controller::Ks0073 => 0x04,
+            Controller::Ssd1803a => 0x02,
+        }
+    }
+}
+
+/// Number of display lines.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Lines {
+    Line1,
+    Line2,
+    Line4,
+}
+
+/// Double height mode (SSD1803A only).
+#[derive(Copy, Clone, Debug)]
+pub enum DoubleHeight {
+    /// All lines are of the normal height.
+    Off,
+    /// Two normal lines on top of one double height line.
+    Bottom,
+    /// Double height line between two normal lines.
+    Middle,
+    /// Two double height lines.
+    Both,
+    /// Double height line on top of two normal lines.
+    Top,
+}
+
+/// `Hardware + Delay` wrapper for displays with KS0073 or SSD1803A controller. Keeps the state of
+/// the function set and extended function set instructions.
+pub struct Ks0073<HW> {
+    hw: HW,
+    controller: Controller,
+    lines: Lines,
+    extended: u8,
+    double_height: bool,
+}
+
+impl<HW: Hardware + Delay> Ks0073<HW> {
+    /// Create a new [Ks0073] from the given `Hardware + Delay` implementation and controller
+    /// flavor (`controller`).
+    pub fn new(hw: HW, controller: Controller) -> Self {
+        Ks0073 {
+            hw,
+            controller,
+            lines: Lines::Line2,
+            extended: 0,
+            double_height: false,
+        }
+    }
+
+    /// Unwrap back to the `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
+
+    fn function_base(&self) -> u8 {
+        let mut cmd = (Command::FunctionSet as u8) | (self.hw.mode() as u8);
+        if self.lines != Lines::Line1 {
+            cmd |= FUNCTION_N;
+        }
+        cmd
+    }
+
+    fn function_set(&self) -> u8 {
+        let mut cmd = self.function_base();
+        if self.double_height {
+            cmd |= FUNCTION_DH;
+        }
+        cmd
+    }
+
+    fn extended_function_set(&self) -> u8 {
+        self.function_base() | self.controller.re()
+    }
+}
+
+impl<HW: Hardware> Hardware for Ks0073<HW> {
+    #[inline(always)]
+    fn rs(&mut self, bit: bool) {
+        self.hw.rs(bit)
+    }
+
+    #[inline(always)]
+    fn enable(&mut self, bit: bool) {
+        self.hw.enable(bit)
+    }
+
+    #[inline(always)]
+    fn data(&mut self, data: u8) {
+        self.hw.data(data)
+    }
+
+    #[inline(always)]
+    fn wait_address(&mut self) {
+        self.hw.wait_address()
+    }
+
+    #[inline(always)]
+    fn mode(&self) -> FunctionMode {
+        self.hw.mode()
+    }
+
+    #[inline(always)]
+    fn can_read(&self) -> bool {
+        self.hw.can_read()
+    }
+
+    #[inline(always)]
+    fn rw(&mut self, bit: bool) {
+        self.hw.rw(bit)
+    }
+
+    #[inline(always)]
+    fn read_data(&mut self) -> u8 {
+        self.hw.read_data()
+    }
+
+    #[inline(always)]
+    fn apply(&mut self) {
+        self.hw.apply()
+    }
+}
+
+impl<HW: Delay> Delay for Ks0073<HW> {
+    #[inline(always)]
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.hw.delay_us(delay_usec)
+    }
+}
+
+impl<HW: Hardware + Delay> Display<Ks0073<HW>> {
+    /// Initialize display for the given number of lines (`lines`). Runs the regular [Display::init]
+    /// sequence (with 5x8 font, as 5x10 font is not supported by these controllers), then sets
+    /// the extended function set. In 4-line mode, display geometry is set to 20x4
+    /// [Geometry::four_line].
+    #[inline(never)]
+    pub fn init_extended(&mut self, lines: Lines) {
+        let line = match lines {
+            Lines::Line1 => FunctionLine::Line1,
+            _ => FunctionLine::Line2,
+        };
+        let config = InitConfig::new(line, FunctionDots::Dots5x8).power_on_wait(false);
+        self.init_with(if lines == Lines::Line4 {
+            config.geometry(Geometry::four_line(COLUMNS_4LINE))
+        } else {
+            config
+        });
+
+        self.hw.lines = lines;
+        self.hw.double_height = false;
+        self.hw.extended = if lines == Lines::Line4 {
+            EXTENDED_NW
+        } else {
+            0
+        };
+        self.send_extended_function();
+    }
+
+    /// Select 6-dot (`true`) or 5-dot (`false`) font width.
+    pub fn set_font_width_6(&mut self, enabled: bool) -> &Self {
+        self.set_extended_flag(EXTENDED_FW, enabled);
+        self
+    }
+
+    /// Enable black / white inverting cursor.
+    pub fn set_inverting_cursor(&mut self, enabled: bool) -> &Self {
+        self.set_extended_flag(EXTENDED_BW, enabled);
+        self
+    }
+
+    /// Enable horizontal scroll for the given lines (`lines`, bit 0 for the first line, bit 1 for
+    /// the second line and so on). Only enabled lines are affected by the `scroll` and
+    /// `set_scroll_quantity`.
+    pub fn set_scroll_enable(&mut self, lines: u8) -> &Self {
+        let cmd = (ExtendedCommand::ScrollEnableDoubleHeight as u8) | (lines & 0xf);
+        if self.hw.controller == Controller::Ssd1803a {
+            // Scroll enable is selected by the IS bit, which is only settable when RE = 0
+            let special = self.hw.function_set() | FUNCTION_IS;
+            self.command(special);
+            self.extended(|lcd| {
+                lcd.command(cmd);
+            });
+        } else {
+            self.extended(|lcd| {
+                lcd.command(cmd);
+            });
+        }
+        self
+    }
+
+    /// Set horizontal dot scroll quantity (`quantity`, 0-48, panics otherwise) for the lines
+    /// enabled via `set_scroll_enable`.
+    pub fn set_scroll_quantity(&mut self, quantity: u8) -> &Self {
+        assert!(quantity <= MAX_SCROLL_QUANTITY);
+        self.extended(|lcd| {
+            lcd.command((ExtendedCommand::ScrollQuantity as u8) | quantity);
+        });
+        self
+    }
+
+    /// Set bidirectional entry: reverse common (`common`, top-bottom mirror) and segment
+    /// (`segment`, left-right mirror) direction. KS0073 only supports segment direction, `common`
+    /// is ignored.
+    pub fn set_bidirectional(&mut self, common: bool, segment: bool) -> &Self {
+        let mut cmd = ExtendedCommand::Bidirectional as u8;
+        if common && self.hw.controller == Controller::Ssd1803a {
+            cmd |= 0x02;
+        }
+        if segment {
+            cmd |= 0x01;
+        }
+        self.extended(|lcd| {
+            lcd.command(cmd);
+        });
+        self
+    }
+
+    /// Set double height mode (`mode`). Only supported by SSD1803A (panics otherwise).
+    pub fn set_double_height(&mut self, mode: DoubleHeight) -> &Self {
+        assert_eq!(self.hw.controller, Controller::Ssd1803a);
+        let ud = match mode {
+            DoubleHeight::Off => None,
+            DoubleHeight::Bottom => Some(0x00),
+            DoubleHeight::Middle => Some(0x04),
+            DoubleHeight::Both => Some(0x08),
+            DoubleHeight::Top => Some(0x0c),
+        };
+        if let Some(ud) = ud {
+            self.hw.double_height = true;
+            self.extended(|lcd| {
+                lcd.command((ExtendedCommand::ScrollEnableDoubleHeight as u8) | ud);
+            });
+        } else {
+            self.hw.double_height = false;
+            let function = self.hw.function_set();
+            self.command(function);
+        }
+        self
+    }
+
+    fn set_extended_flag(&mut self, flag: u8, enabled: bool) {
+        if enabled {
+            self.hw.extended |= flag;
+        } else {
+            self.hw.extended &= !flag;
+        }
+        self.send_extended_function();
+    }
+
+    fn send_extended_function(&mut self) {
+        let cmd = (ExtendedCommand::ExtendedFunctionSet as u8) | self.hw.extended;
+        self.extended(|lcd| {
+            lcd.command(cmd);
+        });
+    }
+
+    /// Run given closure with extended instruction set selected (RE = 1), then switch back to the
+    /// normal instruction set.
+    fn extended(&mut self, f: impl FnOnce(&mut Self)) {
+        let extended = self.hw.extended_function_set();
+        self.command(extended);
+        f(self);
+        let function = self.hw.function_set();
+        self.command(function);
+    }
+}
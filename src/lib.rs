@@ -9,11 +9,16 @@
 //! when `Hardware::can_read()` returns `false` (the default implementation), it is not used and should be
 //! wired for "write" (low-level, 0).
 //!
-//! The implementation is completely stateless. Clients are free to reuse the same `Display` object
-//! or to create one every time access to LCD is required.
+//! `Display` keeps the configuration it was initialized with (like the display geometry) and, with
+//! the `text` feature, the text cursor and the last display control state, so the same `Display`
+//! object should be kept for as long as LCD is used. Creating a new one (with [Display::new]) is
+//! possible, but it knows nothing about the display, so geometry-based positioning and text modes
+//! are not available until it is initialized again.
 //!
 //! `Display` also implements the `core::fmt::Write` trait, so it could be used as a target of `write!`
-//! macro.
+//! macro. To avoid the size of `core::fmt`, numbers could be printed with [Display::print_i32],
+//! [Display::print_decimal], [Display::print_hex] and others, or with the `ufmt` feature, using the
+//! [ufmt](https://crates.io/crates/ufmt) crate.
 //! 
 //! If you have separate [Hardware] and [Delay] implementations, they can be combined using the
 //! [HardwareDelay] struct.
@@ -101,7 +106,53 @@
 //!
 //! [1]: https://en.wikipedia.org/wiki/Hitachi_HD44780_LCD_controller
 
-#[derive(Copy, Clone, Debug)]
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod bargraph;
+pub mod bigdigits;
+pub mod canvas;
+#[cfg(feature = "std")]
+pub mod checker;
+pub mod console;
+#[cfg(feature = "emulator")]
+pub mod emulator;
+mod format;
+mod geometry;
+mod instruction;
+pub mod ks0073;
+pub mod marquee;
+pub mod menu;
+pub mod screen;
+mod shadow;
+#[cfg(feature = "std")]
+pub mod sigrok;
+pub mod sparkline;
+pub mod st7032;
+pub mod stateful;
+#[cfg(feature = "text")]
+mod terminal;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "text")]
+mod text;
+mod timing;
+pub mod transport;
+pub mod us2066;
+#[cfg(feature = "std")]
+pub mod vcd;
+pub mod ws0010;
+
+pub use crate::format::Align;
+pub use crate::geometry::Geometry;
+pub use crate::instruction::Instruction;
+#[cfg(feature = "text")]
+pub use crate::text::TextMode;
+pub use crate::timing::Timing;
+#[cfg(feature = "text")]
+use crate::text::TextCursor;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum FunctionMode {
     /// Send data 4 bits at the time
     Bit4 = 0x00,
@@ -109,55 +160,55 @@ pub enum FunctionMode {
     Bit8 = 0x10,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum FunctionDots {
     Dots5x8 = 0x00,
     Dots5x10 = 0x04,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum FunctionLine {
     Line1 = 0x00,
     Line2 = 0x08,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum DisplayBlink {
     BlinkOff = 0x00,
     BlinkOn = 0x01,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum DisplayCursor {
     CursorOff = 0x00,
     CursorOn = 0x02,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum DisplayMode {
     DisplayOff = 0x00,
     DisplayOn = 0x04,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Direction {
     Left = 0x00,
     Right = 0x04,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Scroll {
     CursorMove = 0x00,
     DisplayMove = 0x08,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum EntryModeDirection {
     EntryLeft = 0x00,
     EntryRight = 0x02,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum EntryModeShift {
     NoShift = 0x00,
     Shift = 0x01,
@@ -175,21 +226,99 @@ pub enum Command {
     SetDDRamAddr = 0x80,
 }
 
+/// Configuration of the display initialization, see [Display::init_with].
+#[derive(Copy, Clone, Debug)]
+pub struct InitConfig {
+    line: FunctionLine,
+    dots: FunctionDots,
+    power_on_wait: bool,
+    warm: bool,
+    clear: bool,
+    display: DisplayMode,
+    cursor: DisplayCursor,
+    blink: DisplayBlink,
+    direction: EntryModeDirection,
+    shift: EntryModeShift,
+    geometry: Option<Geometry>,
+}
+
+impl InitConfig {
+    /// Create a new configuration for the given number of lines (`line`) and font size (`dots`).
+    ///
+    /// By default, initialization waits for the power to stabilize, runs the full
+    /// reset-by-instruction sequence, clears the display and leaves it turned off, with cursor
+    /// moving right and no display shift.
+    pub fn new(line: FunctionLine, dots: FunctionDots) -> Self {
+        InitConfig {
+            line,
+            dots,
+            power_on_wait: true,
+            warm: false,
+            clear: true,
+            display: DisplayMode::DisplayOff,
+            cursor: DisplayCursor::CursorOff,
+            blink: DisplayBlink::BlinkOff,
+            direction: EntryModeDirection::EntryRight,
+            shift: EntryModeShift::NoShift,
+            geometry: None,
+        }
+    }
+
+    /// Wait for more than 40ms before initialization, as required by the datasheet after power-on.
+    /// Could be disabled if initialization is known to run long after the display is powered on.
+    pub fn power_on_wait(mut self, wait: bool) -> Self {
+        self.power_on_wait = wait;
+        self
+    }
+
+    /// Skip the slow reset-by-instruction sequence. Should only be used when display is known to
+    /// be already configured for the current interface mode (for example, after MCU reset).
+    pub fn warm(mut self, warm: bool) -> Self {
+        self.warm = warm;
+        self
+    }
+
+    /// Clear the display during initialization. Could be disabled to preserve the display content
+    /// during warm initialization.
+    pub fn clear(mut self, clear: bool) -> Self {
+        self.clear = clear;
+        self
+    }
+
+    /// Display mode (`display`), cursor (`cursor`) and blink (`blink`) to leave the display in.
+    pub fn display(
+        mut self,
+        display: DisplayMode,
+        cursor: DisplayCursor,
+        blink: DisplayBlink,
+    ) -> Self {
+        self.display = display;
+        self.cursor = cursor;
+        self.blink = blink;
+        self
+    }
+
+    /// Entry mode direction (`dir`) and display shift (`scroll`) to leave the display in.
+    pub fn entry_mode(mut self, dir: EntryModeDirection, scroll: EntryModeShift) -> Self {
+        self.direction = dir;
+        self.shift = scroll;
+        self
+    }
+
+    /// Size of the display (`geometry`), used by [Display::position] to find DDRAM address of
+    /// each row. If not given, rows 2 and 3 are assumed to start at `0x14` and `0x54` (20x4
+    /// display layout).
+    pub fn geometry(mut self, geometry: Geometry) -> Self {
+        self.geometry = Some(geometry);
+        self
+    }
+}
+
 pub trait Delay {
     /// Delay for given amount of time (in microseconds).
     fn delay_us(&mut self, delay_usec: u32);
 }
 
-trait InternalHardware {
-    fn rs(&self, bit: bool);
-    fn enable(&self, bit: bool);
-    fn data(&self, data: u8);
-    fn wait_address(&self) {}
-    fn mode(&self) -> FunctionMode;
-    fn rw(&self, bit: bool);
-    fn read_data(&self) -> u8;
-}
-
 pub trait Hardware {
     fn rs(&mut self, bit: bool);
     fn enable(&mut self, bit: bool);
@@ -245,17 +374,146 @@ pub trait Backlight {
     fn set_backlight(&mut self, enabled: bool);
 }
 
-/// Object implementing HD44780 protocol. This is stateless (could be created as many times as needed).
+/// Trait for the I2C bus used by controllers with native I2C interface (like ST7032 or US2066).
+pub trait I2c {
+    /// Write given bytes (`bytes`) to the device at the given 7-bit address (`address`) in a single
+    /// transaction.
+    fn write(&mut self, address: u8, bytes: &[u8]);
+}
+
+/// Trait for the SPI bus used by controllers with native serial interface (like US2066).
+pub trait Spi {
+    /// Write given bytes (`bytes`) to the device in a single transfer (with chip select asserted).
+    fn write(&mut self, bytes: &[u8]);
+}
+
+/// Object implementing HD44780 protocol. The only state it keeps is the configuration it was
+/// initialized with and, with the `text` feature, the text cursor (see `Display::set_text_mode`)
+/// and the last display control sent by [Display::display].
 pub struct Display<HW: Hardware + Delay> {
     hw: HW,
+    timing: Timing,
+    config: Option<InitConfig>,
+    #[cfg(feature = "text")]
+    text: Option<TextCursor>,
+    /// Display, cursor and blink state, changed one at a time by the escape sequences.
+    #[cfg(feature = "text")]
+    control: (DisplayMode, DisplayCursor, DisplayBlink),
+}
+
+/// Display which is not initialized yet. The only thing it allows is initialization, which
+/// returns [Display], so it cannot be used before being initialized.
+///
+/// ```rust,no_run
+/// # use lcd::*;
+/// # struct HW {}
+/// # impl Hardware for HW {
+/// #   fn rs(&mut self, bit: bool) { }
+/// #   fn enable(&mut self, bit: bool) { }
+/// #   fn data(&mut self, data: u8) { }
+/// # }
+/// # impl Delay for HW {
+/// #   fn delay_us(&mut self, delay_usec: u32) { }
+/// # }
+/// # let hw = HW {};
+/// let mut lcd = UninitializedDisplay::new(hw).init(
+///     InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+///         .geometry(Geometry::new(20, 4))
+///         .display(DisplayMode::DisplayOn, DisplayCursor::CursorOff, DisplayBlink::BlinkOff),
+/// );
+/// lcd.position(0, 3);
+/// lcd.print("Bottom line");
+/// ```
+///
+/// Printing before initialization does not compile:
+///
+/// ```rust,compile_fail
+/// # use lcd::*;
+/// # struct HW {}
+/// # impl Hardware for HW {
+/// #   fn rs(&mut self, bit: bool) { }
+/// #   fn enable(&mut self, bit: bool) { }
+/// #   fn data(&mut self, data: u8) { }
+/// # }
+/// # impl Delay for HW {
+/// #   fn delay_us(&mut self, delay_usec: u32) { }
+/// # }
+/// # let hw = HW {};
+/// let mut lcd = UninitializedDisplay::new(hw);
+/// lcd.print("Hello");
+/// ```
+pub struct UninitializedDisplay<HW: Hardware + Delay> {
+    hw: HW,
+    timing: Timing,
 }
 
-trait WaitReady {
-    fn wait_ready(&self, delay: u32);
+impl<HW: Hardware + Delay> UninitializedDisplay<HW> {
+    /// Create a new uninitialized display from the given `Hardware + Delay` implementation.
+    pub fn new(hw: HW) -> Self {
+        UninitializedDisplay::with_timing(hw, Timing::default())
+    }
+
+    /// Create a new uninitialized display from the given `Hardware + Delay` implementation, using
+    /// given timing profile (`timing`).
+    pub fn with_timing(hw: HW, timing: Timing) -> Self {
+        UninitializedDisplay { hw, timing }
+    }
+
+    /// Initialize the display with the given configuration (`config`), see [Display::init_with].
+    pub fn init(self, config: InitConfig) -> Display<HW> {
+        let mut display = Display::with_timing(self.hw, self.timing);
+        display.init_with(config);
+        display
+    }
+
+    /// Unwrap back to the `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
 }
 
+/// Writes text to the display. With the `text` feature, if text mode is set (see
+/// `Display::set_text_mode`), control characters and escape sequences are interpreted, like a tiny
+/// terminal would do:
+///
+/// * `\n` moves to the beginning of the next row (scrolling up in `TextMode::Scroll` mode, going
+///   to the first row otherwise), `\r` moves to the beginning of the row, `\t` moves to the next
+///   tab stop (every 4 columns), backspace (`\x08`) moves back and erases the character, form feed
+///   (`\x0c`) clears the display.
+/// * ANSI / VT100 sequences: `ESC[<row>;<col>H` (1-based) moves cursor, `ESC[2J` clears the
+///   display, `ESC[K` clears to the end of the row, `ESC[?25h` / `ESC[?25l` shows / hides cursor.
+///   Other sequences are ignored.
+/// * Linux `charlcd` sequences: `ESC[LD` / `ESC[Ld` turns display on / off, `ESC[LC` / `ESC[Lc`
+///   cursor, `ESC[LB` / `ESC[Lb` blink, `ESC[Ll` / `ESC[Lr` shifts cursor, `ESC[LL` / `ESC[LR`
+///   shifts display, `ESC[Lk` clears to the end of the row, `ESC[LI` reinitializes display,
+///   `ESC[Lx<col>y<row>;` moves cursor (0-based), `ESC[LG<n><hex>;` uploads custom character `n`.
+///   Backlight, font and lines sequences are ignored.
+///
+/// In `TextMode::Raw` mode (and without the `text` feature), text is written as is.
 impl<HW: Hardware + Delay> core::fmt::Write for Display<HW> {
     fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        #[cfg(feature = "text")]
+        if self.text.is_some() {
+            self.write_terminal(s);
+            return Ok(());
+        }
+        self.print(s);
+        Ok(())
+    }
+}
+
+/// Same as the `core::fmt::Write` implementation, for the [ufmt](https://crates.io/crates/ufmt)
+/// crate (with the `ufmt` feature).
+#[cfg(feature = "ufmt")]
+impl<HW: Hardware + Delay> ufmt_write::uWrite for Display<HW> {
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        #[cfg(feature = "text")]
+        if self.text.is_some() {
+            self.write_terminal(s);
+            return Ok(());
+        }
         self.print(s);
         Ok(())
     }
@@ -271,7 +529,46 @@ impl<HW: Hardware + Delay + Backlight> Backlight for Display<HW> {
 impl<HW: Hardware + Delay> Display<HW> {
     /// Create a new Display object from the given `Hardware + Delay` implementation.
     pub fn new(hw: HW) -> Display<HW> {
-        Display { hw }
+        Display::with_timing(hw, Timing::default())
+    }
+
+    /// Create a new Display object from the given `Hardware + Delay` implementation, using given
+    /// timing profile (`timing`).
+    pub fn with_timing(hw: HW, timing: Timing) -> Display<HW> {
+        Display {
+            hw,
+            timing,
+            config: None,
+            #[cfg(feature = "text")]
+            text: None,
+            #[cfg(feature = "text")]
+            control: (
+                DisplayMode::DisplayOff,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff,
+            ),
+        }
+    }
+
+    /// Timing profile used by this Display object.
+    pub fn timing(&self) -> &Timing {
+        &self.timing
+    }
+
+    /// Number of lines the display was initialized with, `None` if it was not initialized.
+    pub fn lines(&self) -> Option<FunctionLine> {
+        self.config.map(|config| config.line)
+    }
+
+    /// Font size the display was initialized with, `None` if it was not initialized.
+    pub fn dots(&self) -> Option<FunctionDots> {
+        self.config.map(|config| config.dots)
+    }
+
+    /// Size of the display given in the initialization configuration (see
+    /// [InitConfig::geometry]).
+    pub fn geometry(&self) -> Option<Geometry> {
+        self.config.and_then(|config| config.geometry)
     }
 
     /// Initialize LCD display. Sets an equivalent of the following setup:
@@ -293,9 +590,83 @@ impl<HW: Hardware + Delay> Display<HW> {
     /// lcd.clear();
     /// lcd.entry_mode(EntryModeDirection::EntryRight, EntryModeShift::NoShift);
     /// ```
-    #[inline(never)]
+    ///
+    /// Does not wait for the power to stabilize, use [Display::init_with] if initialization could
+    /// run right after the power-on.
     pub fn init(&mut self, line: FunctionLine, dots: FunctionDots) {
+        self.init_with(InitConfig::new(line, dots).power_on_wait(false));
+    }
+
+    /// Initialize LCD display with the given configuration (`config`).
+    ///
+    /// ```rust,no_run
+    /// # use lcd::*;
+    /// # struct HW {}
+    /// # impl Hardware for HW {
+    /// #   fn rs(&mut self, bit: bool) { }
+    /// #   fn enable(&mut self, bit: bool) { }
+    /// #   fn data(&mut self, data: u8) { }
+    /// # }
+    /// # impl Delay for HW {
+    /// #   fn delay_us(&mut self, delay_usec: u32) { }
+    /// # }
+    /// # let hw = HW {};
+    /// # let mut lcd = Display::new(hw);
+    /// lcd.init_with(
+    ///     InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+    ///         .display(DisplayMode::DisplayOn, DisplayCursor::CursorOff, DisplayBlink::BlinkOff),
+    /// );
+    /// ```
+    #[inline(never)]
+    pub fn init_with(&mut self, config: InitConfig) {
+        if config.power_on_wait {
+            self.hw.delay_us(self.timing.power_on);
+        }
+
         let mode = self.hw.mode();
+        if !config.warm {
+            self.reset(mode);
+        }
+
+        // Finally, set # lines, font size
+        self.instruction(Instruction::FunctionSet(mode, config.line, config.dots));
+
+        // Now display should be properly initialized, we can check BF now
+        // Though if we are not checking BF, waiting time is longer
+        if config.warm {
+            // Display could be on, so clear it before changing the display control, so old
+            // content does not flash
+            if config.clear {
+                self.clear();
+            }
+            self.display(config.display, config.cursor, config.blink);
+            self.entry_mode(config.direction, config.shift);
+        } else {
+            // Datasheet order: display off, clear, entry mode, then the requested display control
+            let off = (
+                DisplayMode::DisplayOff,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff,
+            );
+            self.display(off.0, off.1, off.2);
+            if config.clear {
+                self.clear();
+            }
+            self.entry_mode(config.direction, config.shift);
+            if (config.display, config.cursor, config.blink) != off {
+                self.display(config.display, config.cursor, config.blink);
+            }
+        }
+        self.config = Some(config);
+        #[cfg(feature = "text")]
+        if config.geometry.is_none() {
+            // Text cursor cannot be tracked without geometry
+            self.text = None;
+        }
+    }
+
+    /// Run reset-by-instruction sequence, which sets the interface mode (`mode`).
+    fn reset(&mut self, mode: FunctionMode) {
         self.hw.rs(false);
         self.hw.apply();
         self.hw.wait_address();
@@ -312,10 +683,10 @@ impl<HW: Hardware + Delay> Display<HW> {
                         | (FunctionDots::Dots5x10 as u8),
                 ); // Send command for the first time
 
-                self.hw.delay_us(4500); // Wait for more than 4.1ms
+                self.hw.delay_us(self.timing.reset_first); // Wait for more than 4.1ms
 
                 self.pulse_enable(); // Repeat for the second time
-                self.hw.delay_us(150); // Wait for more than 100us
+                self.hw.delay_us(self.timing.reset_second); // Wait for more than 100us
 
                 self.pulse_enable(); // Repeat for the third time
                 self.wait_ready_default();
@@ -323,10 +694,10 @@ impl<HW: Hardware + Delay> Display<HW> {
             FunctionMode::Bit4 => {
                 // Run initialization procedure for the display (4-bit mode).
                 self.send_data(((Command::FunctionSet as u8) | (FunctionMode::Bit8 as u8)) >> 4);
-                self.hw.delay_us(4500); // Wait for more than 4.1ms
+                self.hw.delay_us(self.timing.reset_first); // Wait for more than 4.1ms
 
                 self.pulse_enable(); // Repeat for the second time
-                self.hw.delay_us(150); // Wait for more than 100us
+                self.hw.delay_us(self.timing.reset_second); // Wait for more than 100us
 
                 self.pulse_enable(); // Repeat for the third time
                 self.wait_ready_default(); // Wait fo FunctionSet to finish
@@ -336,42 +707,33 @@ impl<HW: Hardware + Delay> Display<HW> {
                 self.wait_ready_default(); // Wait for FunctionSet to finish
             }
         }
-
-        // Finally, set # lines, font size
-        self.command((Command::FunctionSet as u8) | (mode as u8) | (line as u8) | (dots as u8));
-
-        // Now display should be properly initialized, we can check BF now
-        // Though if we are not checking BF, waiting time is longer
-        self.display(
-            DisplayMode::DisplayOff,
-            DisplayCursor::CursorOff,
-            DisplayBlink::BlinkOff,
-        );
-        self.clear();
-        self.entry_mode(EntryModeDirection::EntryRight, EntryModeShift::NoShift);
     }
 
     /// Clears display and returns cursor to the home position (address 0).
     pub fn clear(&mut self) -> &Self {
-        self.command(Command::ClearDisplay as u8);
+        self.instruction(Instruction::ClearDisplay);
         // This command could take as long as 1.52ms to execute
-        self.wait_ready(2000);
+        self.wait_ready(self.timing.clear_home);
+        #[cfg(feature = "text")]
+        self.text_clear();
         self
     }
 
     /// Returns cursor to home position. Also returns display being shifted to the original position.
     /// DDRAM content remains unchanged.
     pub fn home(&mut self) -> &Self {
-        self.command(Command::ReturnHome as u8);
+        self.instruction(Instruction::ReturnHome);
         // This command could take as long as 1.52ms to execute
-        self.wait_ready(2000);
+        self.wait_ready(self.timing.clear_home);
+        #[cfg(feature = "text")]
+        self.text_move(0, 0, true);
         self
     }
 
     /// Sets cursor move direction (`entry`); specifies to shift the display (`scroll`).
     /// These operations are performed during data read/write.
     pub fn entry_mode(&mut self, dir: EntryModeDirection, scroll: EntryModeShift) -> &Self {
-        self.command((Command::EntryModeSet as u8) | (dir as u8) | (scroll as u8))
+        self.instruction(Instruction::EntryModeSet(dir, scroll))
     }
 
     /// Sets on/off of all display (`display`), cursor on/off (`cursor`), and blink of cursor
@@ -382,30 +744,45 @@ impl<HW: Hardware + Delay> Display<HW> {
         cursor: DisplayCursor,
         blink: DisplayBlink,
     ) -> &Self {
-        self.command(
-            (Command::DisplayControl as u8) | (display as u8) | (cursor as u8) | (blink as u8),
-        )
+        #[cfg(feature = "text")]
+        {
+            self.control = (display, cursor, blink);
+        }
+        self.instruction(Instruction::DisplayControl(display, cursor, blink))
     }
 
     /// Sets display-shift, direction (`dir`). DDRAM content remains unchanged.
     pub fn scroll(&mut self, dir: Direction) -> &Self {
-        self.command((Command::CursorShift as u8) | (Scroll::DisplayMove as u8) | (dir as u8))
+        self.instruction(Instruction::CursorShift(Scroll::DisplayMove, dir))
     }
 
     /// Sets cursor-shift, direction (`dir`). DDRAM content remains unchanged.
     pub fn cursor(&mut self, dir: Direction) -> &Self {
-        self.command((Command::CursorShift as u8) | (Scroll::CursorMove as u8) | (dir as u8))
+        self.instruction(Instruction::CursorShift(Scroll::CursorMove, dir));
+        #[cfg(feature = "text")]
+        self.text_shift(dir);
+        self
     }
 
-    /// Sets the cursor position to the given row (`row`) and column (`col`).
+    /// Sets the cursor position to the given row (`row`) and column (`col`). DDRAM address of the
+    /// row is defined by the display geometry (see [InitConfig::geometry]); if it is given, panics
+    /// if column or row is out of range.
     pub fn position(&mut self, col: u8, row: u8) {
-        let offset = match row {
-            1 => 0x40,
-            2 => 0x14,
-            3 => 0x54,
-            _ => 0,
+        let address = match self.geometry() {
+            Some(geometry) => geometry.address(col, row),
+            None => {
+                let offset = match row {
+                    1 => 0x40,
+                    2 => 0x14,
+                    3 => 0x54,
+                    _ => 0,
+                };
+                col + offset
+            }
         };
-        self.command((Command::SetDDRamAddr as u8) | (col + offset));
+        self.instruction(Instruction::SetDDRamAddr(address));
+        #[cfg(feature = "text")]
+        self.text_move(col, row, true);
     }
 
     /// Print given string (`str`) on the LCD screen.
@@ -416,16 +793,27 @@ impl<HW: Hardware + Delay> Display<HW> {
         self
     }
 
-    /// Write given character (given as `data` of type `u8`) on the LCD screen.
-    #[inline(never)]
+    /// Write given character (given as `data` of type `u8`) on the LCD screen, at the text cursor
+    /// if text mode is set (see `Display::set_text_mode`, with the `text` feature).
     pub fn write(&mut self, data: u8) -> &Self {
+        #[cfg(feature = "text")]
+        if self.text.is_some() {
+            self.write_text(data);
+            return self;
+        }
+        self.write_raw(data)
+    }
+
+    /// Write given data (`data`) to DDRAM or CGRAM at the current address.
+    #[inline(never)]
+    fn write_raw(&mut self, data: u8) -> &Self {
         self.hw.rs(true);
         self.hw.apply();
         self.hw.wait_address(); // tAS
         self.send(data);
         self.wait_ready_default();
         // It takes 4us more (tADD) to update address counter
-        self.hw.delay_us(5);
+        self.hw.delay_us(self.timing.address_update);
         self
     }
 
@@ -437,15 +825,24 @@ impl<HW: Hardware + Delay> Display<HW> {
         assert!(location <= 7);
 
         // Only 8 locations are available
-        self.command((Command::SetCGRamAddr as u8) | ((location & 0x7) << 3));
+        self.instruction(Instruction::SetCGRamAddr((location & 0x7) << 3));
         for item in map.iter().take(8) {
-            self.write(*item);
+            self.write_raw(*item);
         }
         self
     }
 
+    fn instruction(&mut self, instruction: Instruction) -> &Self {
+        self.command(instruction.byte())
+    }
+
     #[inline(never)]
     fn command(&mut self, cmd: u8) -> &Self {
+        #[cfg(feature = "text")]
+        if let Some(text) = &mut self.text {
+            // Instruction could change the address counter
+            text.addressed = false;
+        }
         self.hw.rs(false);
         self.hw.apply();
         self.hw.wait_address(); // tAS
@@ -456,14 +853,14 @@ impl<HW: Hardware + Delay> Display<HW> {
 
     // Typical command wait time is 37us
     fn wait_ready_default(&mut self) {
-        self.wait_ready(50);
+        self.wait_ready(self.timing.command);
     }
 
     #[inline(never)]
     fn pulse_enable(&mut self) {
         self.hw.enable(true);
         self.hw.apply();
-        self.hw.delay_us(1); // minimum delay is 450 ns
+        self.hw.delay_us(self.timing.enable_pulse); // minimum delay is 450 ns
         self.hw.enable(false);
         self.hw.apply();
     }
@@ -505,6 +902,7 @@ impl<HW: Hardware + Delay> Display<HW> {
             // Back to write mode
             self.hw.rw(false);
             self.hw.apply();
+            self.hw.wait_address(); // tAS
         } else {
             // Cannot read "ready" flag, so do a delay.
             self.hw.delay_us(delay);
@@ -515,9 +913,9 @@ impl<HW: Hardware + Delay> Display<HW> {
     fn receive_data(&mut self) -> u8 {
         self.hw.enable(true);
         self.hw.apply();
-        self.hw.delay_us(1);
+        self.hw.delay_us(self.timing.enable_pulse);
         let data = self.hw.read_data();
-        self.hw.delay_us(1);
+        self.hw.delay_us(self.timing.enable_pulse);
         self.hw.enable(false);
         self.hw.apply();
         data
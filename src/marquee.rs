@@ -0,0 +1,239 @@
+//! Marquee (ticker), scrolling text longer than the display width.
+//!
+//! [Marquee] either scrolls the text across one row by rewriting it (software scrolling, any text
+//! length, other rows are left alone), or writes the text once and then shifts the whole display
+//! with [Display::scroll] (hardware scrolling, text is limited to the 40 characters of the DDRAM
+//! line and all rows move together).
+//!
+//! Marquee is driven by [Marquee::tick], which should be called periodically (for example, from a
+//! timer); text moves by one character every few ticks (see [Marquee::set_speed]). [Marquee::draw]
+//! updates the display.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::marquee::Marquee;
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! let mut marquee = Marquee::row(Geometry::new(16, 2), 1, "Breaking news: it is raining");
+//! marquee.set_speed(5);
+//! marquee.set_bounce(true);
+//! loop {
+//!     // Every 50 ms or so
+//!     marquee.tick();
+//!     marquee.draw(&mut lcd);
+//! #   break;
+//! }
+//! ```
+use crate::{Delay, Direction, Display, Geometry, Hardware, Instruction};
+
+/// Number of characters in DDRAM per line in 2-line mode.
+const DDRAM_LINE: usize = 40;
+
+/// Scrolling text, see the [module](self) documentation.
+pub struct Marquee<'a> {
+    geometry: Geometry,
+    row: u8,
+    text: &'a [u8],
+    /// If the whole display is shifted instead of rewriting the row.
+    hardware: bool,
+    speed: u16,
+    pause: u16,
+    bounce: bool,
+    /// Index of the text character shown in the first column.
+    offset: usize,
+    /// If text moves left (in bounce mode).
+    forward: bool,
+    /// Ticks left to wait at the end.
+    wait: u16,
+    /// Ticks since the last move.
+    ticks: u16,
+    /// Offset as of the last draw, `None` if unknown.
+    shown: Option<usize>,
+}
+
+impl<'a> Marquee<'a> {
+    /// Create a new marquee scrolling the text (`text`) across the given row (`row`) of the display
+    /// of the given size (`geometry`), by rewriting the row.
+    pub fn row(geometry: Geometry, row: u8, text: &'a str) -> Self {
+        assert!(row < geometry.rows());
+        Marquee {
+            geometry,
+            row,
+            text: text.as_bytes(),
+            hardware: false,
+            speed: 1,
+            pause: 0,
+            bounce: false,
+            offset: 0,
+            forward: true,
+            wait: 0,
+            ticks: 0,
+            shown: None,
+        }
+    }
+
+    /// Create a new marquee scrolling the text (`text`, up to 40 characters) on the given row (`row`)
+    /// of the display of the given size (`geometry`, up to 2 rows), by shifting the whole display.
+    /// Other rows are shifted, too.
+    pub fn display(geometry: Geometry, row: u8, text: &'a str) -> Self {
+        assert!(geometry.rows() <= 2 && text.len() <= DDRAM_LINE);
+        Marquee {
+            hardware: true,
+            ..Marquee::row(geometry, row, text)
+        }
+    }
+
+    /// Move text by one character every `ticks` ticks (1 or more).
+    pub fn set_speed(&mut self, ticks: u16) {
+        assert!(ticks > 0);
+        self.speed = ticks;
+    }
+
+    /// Wait for the given number of ticks (`ticks`) with the text start (and, in bounce mode, the
+    /// text end) aligned to the display edge.
+    pub fn set_pause(&mut self, ticks: u16) {
+        self.pause = ticks;
+        self.wait = if self.offset == 0 { ticks } else { 0 };
+    }
+
+    /// Move text back and forth (`bounce`) instead of scrolling it around.
+    pub fn set_bounce(&mut self, bounce: bool) {
+        self.bounce = bounce;
+        self.restart();
+    }
+
+    /// Replace the text (`text`) and start from the beginning.
+    pub fn set_text(&mut self, text: &'a str) {
+        assert!(!self.hardware || text.len() <= DDRAM_LINE);
+        self.text = text.as_bytes();
+        self.shown = None;
+        self.restart();
+    }
+
+    /// Start from the beginning, with the text start aligned to the left edge.
+    pub fn restart(&mut self) {
+        self.offset = 0;
+        self.forward = true;
+        self.wait = self.pause;
+        self.ticks = 0;
+    }
+
+    /// Rewrite the visible part of the text on the next draw, even if it did not scroll.
+    pub fn invalidate(&mut self) {
+        self.shown = None;
+    }
+
+    /// Index of the text character shown in the first column (may point past the end of the text,
+    /// to the blank gap before the text starts again).
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Advance time by one tick. Returns `true` if text has moved. Text which fits the display
+    /// width does not move.
+    pub fn tick(&mut self) -> bool {
+        if self.text.len() <= usize::from(self.geometry.columns()) {
+            return false;
+        }
+        if self.wait > 0 {
+            self.wait -= 1;
+            return false;
+        }
+        self.ticks += 1;
+        if self.ticks < self.speed {
+            return false;
+        }
+        self.ticks = 0;
+
+        if !self.bounce {
+            self.offset = (self.offset + 1) % self.period();
+            if self.offset == 0 {
+                self.wait = self.pause;
+            }
+        } else if self.forward {
+            self.offset += 1;
+            if self.offset + usize::from(self.geometry.columns()) == self.text.len() {
+                self.forward = false;
+                self.wait = self.pause;
+            }
+        } else {
+            self.offset -= 1;
+            if self.offset == 0 {
+                self.forward = true;
+                self.wait = self.pause;
+            }
+        }
+        true
+    }
+
+    /// Update the display (`lcd`) if the text has moved since the last draw.
+    pub fn draw<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        if self.shown == Some(self.offset) {
+            return;
+        }
+        if self.hardware {
+            self.draw_shift(lcd);
+        } else {
+            let address = self.geometry.address(0, self.row);
+            lcd.instruction(Instruction::SetDDRamAddr(address));
+            for col in 0..usize::from(self.geometry.columns()) {
+                lcd.write_raw(self.character(self.offset + col));
+            }
+        }
+        self.shown = Some(self.offset);
+    }
+
+    fn draw_shift<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        let shown = match self.shown {
+            Some(shown) => shown,
+            None => {
+                // Return home also cancels the display shift
+                lcd.home();
+                let address = self.geometry.address(0, self.row);
+                lcd.instruction(Instruction::SetDDRamAddr(address));
+                for index in 0..DDRAM_LINE {
+                    lcd.write_raw(self.character(index));
+                }
+                0
+            }
+        };
+        let delta = (self.offset + DDRAM_LINE - shown) % DDRAM_LINE;
+        if delta <= DDRAM_LINE / 2 {
+            for _ in 0..delta {
+                lcd.scroll(Direction::Left);
+            }
+        } else {
+            for _ in delta..DDRAM_LINE {
+                lcd.scroll(Direction::Right);
+            }
+        }
+    }
+
+    /// Number of positions before the text comes back to the start: text followed by the blank
+    /// gap, as wide as the display (or the rest of the DDRAM line).
+    fn period(&self) -> usize {
+        if self.hardware {
+            DDRAM_LINE
+        } else {
+            self.text.len() + usize::from(self.geometry.columns())
+        }
+    }
+
+    fn character(&self, index: usize) -> u8 {
+        self.text
+            .get(index % self.period())
+            .copied()
+            .unwrap_or(b' ')
+    }
+}
@@ -0,0 +1,541 @@
+//! Menu system with nested menus and editable fields.
+//!
+//! Menu is a tree of [Item]s, which is immutable (so it could be a `static`); values of the
+//! editable fields live in [Cell]s owned by the application. [Menu] keeps the navigation state: it
+//! reacts to the input [Event]s (see [Menu::update]) and redraws the changed rows (see
+//! [Menu::draw]), one item per row, with the selection marker in the first column and the scroll
+//! indicators in the last one.
+//!
+//! Events come from anything implementing the [Input] trait: buttons, rotary encoder (turns map to
+//! [Event::Up] and [Event::Down], push to [Event::Select]) or an analog keypad. Closures returning
+//! `Option<Event>` implement it, too.
+//!
+//! Editing:
+//!
+//! * [Item::Toggle] is flipped by [Event::Select].
+//! * [Item::Number] and [Item::Choice] are edited in place (value is shown in brackets): up and
+//!   down change the value, select confirms it and back restores the previous value.
+//! * [Item::Text] is edited one character at a time, with the blinking cursor: up and down cycle
+//!   through the allowed characters, select moves to the next character (confirming the text after
+//!   the last one) and back moves to the previous one (restoring the previous text before the
+//!   first one).
+//!
+//! ```rust,no_run
+//! use core::cell::Cell;
+//! use lcd::*;
+//! use lcd::menu::{Event, Item, Menu, Response};
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! # fn read_button() -> Option<Event> { None }
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! lcd.display(DisplayMode::DisplayOn, DisplayCursor::CursorOff, DisplayBlink::BlinkOff);
+//!
+//! let volume = Cell::new(5);
+//! let mute = Cell::new(false);
+//! let sound = [
+//!     Item::Number { label: "Volume", value: &volume, min: 0, max: 10, step: 1 },
+//!     Item::Toggle { label: "Mute", value: &mute },
+//! ];
+//! let items = [Item::Menu("Sound", &sound), Item::Action("Reboot", 1)];
+//!
+//! let mut menu = Menu::new(Geometry::new(16, 2), &items);
+//! let mut input = || read_button();
+//! loop {
+//!     if let Response::Action(1) = menu.poll(&mut input) {
+//!         // Reboot
+//!     }
+//!     menu.draw(&mut lcd);
+//! #   break;
+//! }
+//! ```
+use crate::format::{format_i32, NUMBER_LENGTH};
+use crate::shadow::{Shadow, MAX_CHARACTERS};
+use crate::{
+    Delay, Display, DisplayBlink, DisplayCursor, DisplayMode, Geometry, Hardware, Instruction,
+};
+use core::cell::Cell;
+
+/// Maximum depth of the nested menus.
+const MAX_DEPTH: usize = 8;
+/// Maximum length of the editable text.
+const MAX_TEXT: usize = 40;
+
+/// Input event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Event {
+    /// Previous item or higher value.
+    Up,
+    /// Next item or lower value.
+    Down,
+    /// Enter the menu, start editing or confirm the value.
+    Select,
+    /// Leave the menu or cancel editing.
+    Back,
+}
+
+/// Source of the input events.
+pub trait Input {
+    /// Next event, `None` if there is none.
+    fn poll(&mut self) -> Option<Event>;
+}
+
+impl<F: FnMut() -> Option<Event>> Input for F {
+    fn poll(&mut self) -> Option<Event> {
+        self()
+    }
+}
+
+/// Menu item.
+pub enum Item<'a> {
+    /// Nested menu with the given label and items.
+    Menu(&'a str, &'a [Item<'a>]),
+    /// Item with the given label and identifier, returned as [Response::Action] when selected.
+    Action(&'a str, u16),
+    /// Integer value between `min` and `max`, changed by `step`.
+    Number {
+        label: &'a str,
+        value: &'a Cell<i32>,
+        min: i32,
+        max: i32,
+        step: i32,
+    },
+    /// Index of one of the `options` (there should be at least one).
+    Choice {
+        label: &'a str,
+        value: &'a Cell<usize>,
+        options: &'a [&'a str],
+    },
+    /// Value shown as "On" or "Off".
+    Toggle {
+        label: &'a str,
+        value: &'a Cell<bool>,
+    },
+    /// Text made of the given characters (`charset`); length of the text is the length of the
+    /// `value` (up to 40 characters). There should be at least one character in `charset`.
+    Text {
+        label: &'a str,
+        value: &'a [Cell<u8>],
+        charset: &'a [u8],
+    },
+}
+
+impl<'a> Item<'a> {
+    /// Label of the item.
+    pub fn label(&self) -> &'a str {
+        match *self {
+            Item::Menu(label, _) | Item::Action(label, _) => label,
+            Item::Number { label, .. }
+            | Item::Choice { label, .. }
+            | Item::Toggle { label, .. }
+            | Item::Text { label, .. } => label,
+        }
+    }
+}
+
+/// Result of handling an input event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Response<'a> {
+    /// Nothing the application should know about.
+    None,
+    /// Action item with the given identifier was selected.
+    Action(u16),
+    /// Value of the item with the given label was changed.
+    Changed(&'a str),
+    /// Back was pressed in the top level menu.
+    Exit,
+}
+
+/// Value being edited, with what is needed to restore it.
+enum Edit {
+    Number(i32),
+    Choice(usize),
+    Text {
+        cursor: u8,
+        original: [u8; MAX_TEXT],
+    },
+}
+
+/// Level of the nested menus.
+#[derive(Copy, Clone)]
+struct Level<'a> {
+    items: &'a [Item<'a>],
+    selected: usize,
+    top: usize,
+}
+
+/// Menu navigation state, see the [module](self) documentation.
+pub struct Menu<'a> {
+    geometry: Geometry,
+    levels: [Level<'a>; MAX_DEPTH],
+    depth: usize,
+    edit: Option<Edit>,
+    marker: u8,
+    indicators: (u8, u8),
+    shown: Shadow,
+    /// Position of the blinking cursor as of the last draw, `None` if cursor is off.
+    blink: Option<(u8, u8)>,
+}
+
+impl<'a> Menu<'a> {
+    /// Create a new menu with the given top level items (`items`), using the whole display of the
+    /// given size (`geometry`).
+    pub fn new(geometry: Geometry, items: &'a [Item<'a>]) -> Self {
+        assert!(!items.is_empty() && geometry.columns() >= 4);
+        let level = Level {
+            items,
+            selected: 0,
+            top: 0,
+        };
+        Menu {
+            geometry,
+            levels: [level; MAX_DEPTH],
+            depth: 0,
+            edit: None,
+            marker: b'>',
+            indicators: (b'^', b'v'),
+            shown: Shadow::new(geometry),
+            blink: None,
+        }
+    }
+
+    /// Set the character code of the selection marker (`marker`, `>` by default).
+    pub fn set_marker(&mut self, marker: u8) {
+        self.marker = marker;
+    }
+
+    /// Set the character codes of the indicators of the items above (`up`, `^` by default) and
+    /// below (`down`, `v` by default) the visible ones. Could be custom glyphs.
+    pub fn set_indicators(&mut self, up: u8, down: u8) {
+        self.indicators = (up, down);
+    }
+
+    /// Currently selected item.
+    pub fn selected(&self) -> &'a Item<'a> {
+        let level = self.levels[self.depth];
+        &level.items[level.selected]
+    }
+
+    /// Nesting level of the current menu, 0 for the top level one.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// If a value is being edited.
+    pub fn is_editing(&self) -> bool {
+        self.edit.is_some()
+    }
+
+    /// Go back to the first item of the top level menu, cancelling editing.
+    pub fn reset(&mut self) {
+        self.cancel();
+        self.depth = 0;
+        self.levels[0].selected = 0;
+        self.levels[0].top = 0;
+    }
+
+    /// Repaint all rows and the cursor on the next draw (for example, after display was used for
+    /// something else).
+    pub fn invalidate(&mut self) {
+        self.shown.invalidate();
+        self.blink = None;
+    }
+
+    /// Handle the next event of the given input (`input`), if there is one.
+    pub fn poll<I: Input>(&mut self, input: &mut I) -> Response<'a> {
+        match input.poll() {
+            Some(event) => self.update(event),
+            None => Response::None,
+        }
+    }
+
+    /// Handle the given input event (`event`).
+    pub fn update(&mut self, event: Event) -> Response<'a> {
+        let item = self.selected();
+        match self.edit.take() {
+            None => self.navigate(event),
+            Some(Edit::Number(original)) => {
+                if let Item::Number {
+                    value,
+                    min,
+                    max,
+                    step,
+                    ..
+                } = *item
+                {
+                    let current = value.get();
+                    match event {
+                        Event::Up => value.set(current.saturating_add(step).min(max)),
+                        Event::Down => value.set(current.saturating_sub(step).max(min)),
+                        Event::Select => return Response::Changed(item.label()),
+                        Event::Back => {
+                            value.set(original);
+                            return Response::None;
+                        }
+                    }
+                }
+                self.edit = Some(Edit::Number(original));
+                Response::None
+            }
+            Some(Edit::Choice(original)) => {
+                if let Item::Choice { value, options, .. } = *item {
+                    let current = value.get();
+                    match event {
+                        Event::Up => value.set((current + options.len() - 1) % options.len()),
+                        Event::Down => value.set((current + 1) % options.len()),
+                        Event::Select => return Response::Changed(item.label()),
+                        Event::Back => {
+                            value.set(original);
+                            return Response::None;
+                        }
+                    }
+                }
+                self.edit = Some(Edit::Choice(original));
+                Response::None
+            }
+            Some(Edit::Text { cursor, original }) => {
+                if let Item::Text { value, charset, .. } = *item {
+                    let cell = &value[usize::from(cursor)];
+                    let index = charset.iter().position(|&data| data == cell.get());
+                    let cursor = match event {
+                        Event::Up | Event::Down => {
+                            let next = match (index, event) {
+                                (Some(index), Event::Up) => (index + 1) % charset.len(),
+                                (Some(index), _) => (index + charset.len() - 1) % charset.len(),
+                                (None, _) => 0,
+                            };
+                            cell.set(charset[next]);
+                            cursor
+                        }
+                        Event::Select if usize::from(cursor) + 1 == value.len() => {
+                            return Response::Changed(item.label());
+                        }
+                        Event::Select => cursor + 1,
+                        Event::Back if cursor == 0 => {
+                            for (cell, &data) in value.iter().zip(original.iter()) {
+                                cell.set(data);
+                            }
+                            return Response::None;
+                        }
+                        Event::Back => cursor - 1,
+                    };
+                    self.edit = Some(Edit::Text { cursor, original });
+                }
+                Response::None
+            }
+        }
+    }
+
+    /// Update the display (`lcd`), rewriting only the rows which have changed since the last draw.
+    pub fn draw<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        let columns = usize::from(self.geometry.columns());
+        let rows = usize::from(self.geometry.rows());
+        let level = self.levels[self.depth];
+        let mut blink = None;
+        let mut rewritten = false;
+        for row in 0..rows {
+            let mut line = [b' '; MAX_CHARACTERS];
+            let index = level.top + row;
+            if let Some(item) = level.items.get(index) {
+                let cursor = self.render(item, index == level.selected, &mut line[..columns]);
+                if let Some(col) = cursor {
+                    blink = Some((col, row as u8));
+                }
+            }
+            if row == 0 && level.top > 0 {
+                line[columns - 1] = self.indicators.0;
+            }
+            if row == rows - 1 && level.top + rows < level.items.len() {
+                line[columns - 1] = self.indicators.1;
+            }
+
+            if self.shown.update(lcd, 0, row as u8, &line[..columns]) {
+                rewritten = true;
+            }
+        }
+
+        match blink {
+            Some((col, row)) => {
+                if self.blink.is_none() {
+                    lcd.display(
+                        DisplayMode::DisplayOn,
+                        DisplayCursor::CursorOff,
+                        DisplayBlink::BlinkOn,
+                    );
+                }
+                if rewritten || self.blink != blink {
+                    let address = self.geometry.address(col, row);
+                    lcd.instruction(Instruction::SetDDRamAddr(address));
+                }
+                self.blink = blink;
+            }
+            None => {
+                if self.blink.is_some() {
+                    lcd.display(
+                        DisplayMode::DisplayOn,
+                        DisplayCursor::CursorOff,
+                        DisplayBlink::BlinkOff,
+                    );
+                }
+                self.blink = None;
+            }
+        }
+    }
+
+    fn navigate(&mut self, event: Event) -> Response<'a> {
+        let rows = usize::from(self.geometry.rows());
+        let level = &mut self.levels[self.depth];
+        match event {
+            Event::Up => {
+                level.selected = level.selected.saturating_sub(1);
+                level.top = level.top.min(level.selected);
+            }
+            Event::Down => {
+                level.selected = (level.selected + 1).min(level.items.len() - 1);
+                if level.selected >= level.top + rows {
+                    level.top = level.selected + 1 - rows;
+                }
+            }
+            Event::Back if self.depth == 0 => return Response::Exit,
+            Event::Back => self.depth -= 1,
+            Event::Select => return self.select(),
+        }
+        Response::None
+    }
+
+    fn select(&mut self) -> Response<'a> {
+        let item = self.selected();
+        match *item {
+            Item::Menu(_, items) => {
+                if self.depth + 1 < MAX_DEPTH && !items.is_empty() {
+                    self.depth += 1;
+                    self.levels[self.depth] = Level {
+                        items,
+                        selected: 0,
+                        top: 0,
+                    };
+                }
+            }
+            Item::Action(_, id) => return Response::Action(id),
+            Item::Number { value, .. } => self.edit = Some(Edit::Number(value.get())),
+            Item::Choice { value, options, .. } => {
+                assert!(!options.is_empty());
+                self.edit = Some(Edit::Choice(value.get()));
+            }
+            Item::Toggle { value, .. } => {
+                value.set(!value.get());
+                return Response::Changed(item.label());
+            }
+            Item::Text { value, charset, .. } => {
+                assert!(!value.is_empty() && value.len() <= MAX_TEXT && !charset.is_empty());
+                let mut original = [0; MAX_TEXT];
+                for (data, cell) in original.iter_mut().zip(value.iter()) {
+                    *data = cell.get();
+                }
+                self.edit = Some(Edit::Text {
+                    cursor: 0,
+                    original,
+                });
+            }
+        }
+        Response::None
+    }
+
+    /// Restore the value being edited.
+    fn cancel(&mut self) {
+        // Text editing takes several steps back
+        while self.edit.is_some() {
+            self.update(Event::Back);
+        }
+    }
+
+    /// Render the item (`item`) into the row (`line`), with the selection marker if it is
+    /// `selected`. Returns the column of the blinking cursor, if text is edited.
+    fn render(&self, item: &Item, selected: bool, line: &mut [u8]) -> Option<u8> {
+        let columns = line.len();
+        let edit = if selected { self.edit.as_ref() } else { None };
+        if selected {
+            line[0] = self.marker;
+        }
+
+        let mut value = [b' '; MAX_TEXT + 2];
+        let mut length = 0;
+        if edit.is_some() {
+            append(&mut value, &mut length, b"[");
+        }
+        match *item {
+            Item::Menu(..) | Item::Action(..) => {}
+            Item::Number { value: cell, .. } => {
+                let mut buffer = [0; NUMBER_LENGTH];
+                append(
+                    &mut value,
+                    &mut length,
+                    format_i32(cell.get(), 0, &mut buffer),
+                );
+            }
+            Item::Choice {
+                value: cell,
+                options,
+                ..
+            } => {
+                if let Some(option) = options.get(cell.get()) {
+                    append(&mut value, &mut length, option.as_bytes());
+                }
+            }
+            Item::Toggle { value: cell, .. } => {
+                let text: &[u8] = if cell.get() { b"On" } else { b"Off" };
+                append(&mut value, &mut length, text);
+            }
+            Item::Text { value: cells, .. } => {
+                for cell in cells {
+                    append(&mut value, &mut length, &[cell.get()]);
+                }
+            }
+        }
+        if edit.is_some() {
+            append(&mut value, &mut length, b"]");
+        }
+
+        // Value is right-aligned, before the indicator column, and is cut to leave a space after
+        // the marker; label is cut to leave a space before the value
+        let length = length.min(columns - 3);
+        let start = columns - 1 - length;
+        line[start..columns - 1].copy_from_slice(&value[..length]);
+        let end = if length > 0 { start - 1 } else { columns - 1 };
+        for (data, &label) in line[1..end].iter_mut().zip(item.label().as_bytes()) {
+            *data = label;
+        }
+
+        match edit {
+            Some(Edit::Text { cursor, .. }) => {
+                let col = start + 1 + usize::from(*cursor);
+                if col < columns - 1 {
+                    Some(col as u8)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Append given characters (`data`) to the buffer (`buffer`) filled up to `length`, dropping
+/// those which do not fit.
+fn append(buffer: &mut [u8], length: &mut usize, data: &[u8]) {
+    for &data in data {
+        if *length < buffer.len() {
+            buffer[*length] = data;
+            *length += 1;
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Status screens made of static labels and changing fields.
+//!
+//! [Page] declares the layout: [Label]s which never change and [Field]s (position, width,
+//! alignment and number format) which are updated by the application. [Screen] keeps the copy of
+//! the display content, so updating a field only repaints it if its text has actually changed,
+//! and switching to another page only rewrites characters which differ, without clearing the
+//! display (which takes 1.52 ms and flickers).
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::screen::{Field, Format, Label, Page, Screen};
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! # fn read_temperature() -> i32 { 0 }
+//! static STATUS: Page = Page {
+//!     labels: &[
+//!         Label { col: 0, row: 0, text: "Temp:" },
+//!         Label { col: 11, row: 0, text: "C" },
+//!     ],
+//!     fields: &[Field { col: 5, row: 0, width: 6, align: Align::Right, format: Format::Decimal(1) }],
+//! };
+//! const TEMPERATURE: usize = 0;
+//!
+//! let geometry = Geometry::new(16, 2);
+//! let mut lcd = UninitializedDisplay::new(hw)
+//!     .init(InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8).geometry(geometry));
+//! let mut screen = Screen::new(geometry);
+//! screen.show(&mut lcd, &STATUS);
+//! loop {
+//!     // Tenths of degree, shown as "  21.5"
+//!     screen.set_number(&mut lcd, TEMPERATURE, read_temperature());
+//! #   break;
+//! }
+//! ```
+use crate::format::{align, format_hex, format_i32, NUMBER_LENGTH};
+use crate::shadow::{Shadow, MAX_CHARACTERS};
+use crate::{Align, Delay, Display, Geometry, Hardware};
+
+/// How numbers are shown in the field.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    /// Decimal digits.
+    Integer,
+    /// Decimal digits, with the decimal point before the given number of the last digits (0 to 9),
+    /// so `1234` is shown as `12.34` with `Decimal(2)`.
+    Decimal(u8),
+    /// Upper case hexadecimal digits, at least the given number of them (0 to 8).
+    Hex(u8),
+}
+
+/// Text which never changes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Label<'a> {
+    pub col: u8,
+    pub row: u8,
+    pub text: &'a str,
+}
+
+/// Place for the changing value, `width` characters wide.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Field {
+    pub col: u8,
+    pub row: u8,
+    pub width: u8,
+    pub align: Align,
+    /// Format of the numbers set by [Screen::set_number].
+    pub format: Format,
+}
+
+/// Layout of the screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Page<'a> {
+    pub labels: &'a [Label<'a>],
+    pub fields: &'a [Field],
+}
+
+/// Page manager, see the [module](self) documentation.
+pub struct Screen<'a> {
+    geometry: Geometry,
+    page: Option<&'a Page<'a>>,
+    shown: Shadow,
+}
+
+impl<'a> Screen<'a> {
+    /// Create a new screen for the display of the given size (`geometry`), showing no page.
+    pub fn new(geometry: Geometry) -> Self {
+        Screen {
+            geometry,
+            page: None,
+            shown: Shadow::new(geometry),
+        }
+    }
+
+    /// Page currently shown.
+    pub fn page(&self) -> Option<&'a Page<'a>> {
+        self.page
+    }
+
+    /// Show the given page (`page`) on the display (`lcd`), with all fields blank. Only characters
+    /// which differ from the current content are rewritten.
+    pub fn show<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>, page: &'a Page<'a>) {
+        let columns = usize::from(self.geometry.columns());
+        let mut content = [b' '; MAX_CHARACTERS];
+        for label in page.labels {
+            let start = self.index(label.col, label.row);
+            let end = start + (columns - usize::from(label.col)).min(label.text.len());
+            content[start..end].copy_from_slice(&label.text.as_bytes()[..end - start]);
+        }
+        for field in page.fields {
+            assert!(
+                u16::from(field.col) + u16::from(field.width) <= u16::from(self.geometry.columns())
+            );
+            assert!(field.row < self.geometry.rows());
+        }
+        self.page = Some(page);
+
+        for row in 0..self.geometry.rows() {
+            let start = self.index(0, row);
+            self.shown
+                .update(lcd, 0, row, &content[start..start + columns]);
+        }
+    }
+
+    /// Set the text (`text`) of the field with the given index (`field`) in the current page.
+    /// Text longer than the field is cut. Panics if no page is shown.
+    pub fn set_text<HW: Hardware + Delay>(
+        &mut self,
+        lcd: &mut Display<HW>,
+        field: usize,
+        text: &str,
+    ) {
+        self.set_field(lcd, field, text.as_bytes());
+    }
+
+    /// Set the number (`value`) of the field with the given index (`field`) in the current page,
+    /// formatted as defined by the field. Panics if no page is shown.
+    pub fn set_number<HW: Hardware + Delay>(
+        &mut self,
+        lcd: &mut Display<HW>,
+        field: usize,
+        value: i32,
+    ) {
+        let mut buffer = [0; NUMBER_LENGTH];
+        let text = match self.field(field).format {
+            Format::Integer => format_i32(value, 0, &mut buffer),
+            Format::Decimal(scale) => format_i32(value, scale, &mut buffer),
+            Format::Hex(width) => format_hex(value as u32, width, &mut buffer),
+        };
+        self.set_field(lcd, field, text);
+    }
+
+    /// Repaint the fields on their next update, and the whole page on the next [Screen::show]
+    /// (for example, after display was used for something else).
+    pub fn invalidate(&mut self) {
+        self.shown.invalidate();
+    }
+
+    fn set_field<HW: Hardware + Delay>(
+        &mut self,
+        lcd: &mut Display<HW>,
+        field: usize,
+        text: &[u8],
+    ) {
+        let field = *self.field(field);
+        let mut content = [b' '; MAX_CHARACTERS];
+        let content = &mut content[..usize::from(field.width)];
+        align(text, field.align, content);
+        self.shown.update(lcd, field.col, field.row, content);
+    }
+
+    fn field(&self, field: usize) -> &'a Field {
+        &self.page.unwrap().fields[field]
+    }
+
+    /// Index of the given column (`col`) and row (`row`) in the display content.
+    fn index(&self, col: u8, row: u8) -> usize {
+        assert!(col < self.geometry.columns() && row < self.geometry.rows());
+        usize::from(row) * usize::from(self.geometry.columns()) + usize::from(col)
+    }
+}
@@ -0,0 +1,68 @@
+//! Copy of the display content kept by the widgets, so only characters which have changed are
+//! rewritten.
+use crate::{Delay, Display, Geometry, Hardware, Instruction};
+
+/// Maximum number of characters on the display (DDRAM size).
+pub(crate) const MAX_CHARACTERS: usize = 80;
+/// Width of the character in pixels.
+pub(crate) const CHARACTER_WIDTH: u8 = 5;
+/// Height of the character in pixels.
+pub(crate) const CHARACTER_HEIGHT: u8 = 8;
+
+/// Characters written to the display, row by row.
+pub(crate) struct Shadow {
+    geometry: Geometry,
+    content: [u8; MAX_CHARACTERS],
+    /// Bit mask of the characters which are not known.
+    unknown: u128,
+}
+
+impl Shadow {
+    /// Create a new copy of the display of the given size (`geometry`), with all characters
+    /// unknown.
+    pub fn new(geometry: Geometry) -> Self {
+        Shadow {
+            geometry,
+            content: [0; MAX_CHARACTERS],
+            unknown: !0,
+        }
+    }
+
+    /// Mark all characters as unknown, so the next update rewrites them.
+    pub fn invalidate(&mut self) {
+        self.unknown = !0;
+    }
+
+    /// Write the given characters (`line`) to the display (`lcd`), starting at the given column
+    /// (`col`) and row (`row`). Only the span between the first and the last changed characters is
+    /// rewritten. Returns `true` if anything was written.
+    pub fn update<HW: Hardware + Delay>(
+        &mut self,
+        lcd: &mut Display<HW>,
+        col: u8,
+        row: u8,
+        line: &[u8],
+    ) -> bool {
+        let columns = usize::from(self.geometry.columns());
+        assert!(usize::from(col) + line.len() <= columns && row < self.geometry.rows());
+        let start = usize::from(row) * columns + usize::from(col);
+        let changed = |index: usize| {
+            self.unknown & (1 << (start + index)) != 0 || self.content[start + index] != line[index]
+        };
+        let first = (0..line.len()).find(|&index| changed(index));
+        let last = (0..line.len()).rfind(|&index| changed(index));
+        let (first, last) = match (first, last) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return false,
+        };
+
+        let address = self.geometry.address(col + first as u8, row);
+        lcd.instruction(Instruction::SetDDRamAddr(address));
+        for (index, &data) in line.iter().enumerate().take(last + 1).skip(first) {
+            lcd.write_raw(data);
+            self.content[start + index] = data;
+            self.unknown &= !(1 << (start + index));
+        }
+        true
+    }
+}
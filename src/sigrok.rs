@@ -0,0 +1,462 @@
+//! Decoder for the logic analyzer captures of the HD44780 bus.
+//!
+//! Captures are read from the CSV files exported by sigrok (PulseView or `sigrok-cli -O csv`).
+//! Enable falling edges are reconstructed from the samples, 4-bit transfers are paired (following
+//! the interface width set by the function set instructions) and decoded with [Instruction], the
+//! same encoding [Display](crate::Display) uses. Protocol errors are reported along with the
+//! decoded instructions, timing is checked with [TimingChecker].
+//!
+//! Capture should start before the display initialization, as interface width (8-bit after the
+//! power-on) is tracked from the function set instructions.
+//!
+//! ```rust
+//! use lcd::checker::Constraints;
+//! use lcd::sigrok::{decode_csv, EventKind, Pinout};
+//! use lcd::*;
+//!
+//! // Switch to 4-bit mode (single transfer), then write 'A' (two transfers)
+//! let csv = "\
+//! ; Samplerate: 10 kHz
+//! RS,E,D4,D5,D6,D7
+//! 0,0,0,1,0,0
+//! 0,1,0,1,0,0
+//! 0,0,0,1,0,0
+//! 1,0,0,0,1,0
+//! 1,1,0,0,1,0
+//! 1,0,0,0,1,0
+//! 1,1,1,0,0,0
+//! 1,0,1,0,0,0
+//! ";
+//!
+//! let pinout = Pinout::new("RS", "E", &["D4", "D5", "D6", "D7"]);
+//! let events = decode_csv(csv, &pinout, Constraints::datasheet()).unwrap();
+//! let kinds: Vec<EventKind> = events.iter().map(|event| event.kind).collect();
+//! assert_eq!(
+//!     kinds,
+//!     vec![
+//!         EventKind::Instruction(Instruction::FunctionSet(
+//!             FunctionMode::Bit4,
+//!             FunctionLine::Line1,
+//!             FunctionDots::Dots5x8
+//!         )),
+//!         EventKind::Instruction(Instruction::Write(b'A')),
+//!     ]
+//! );
+//! ```
+use crate::checker::{Constraints, Diagnostic, TimingChecker};
+use crate::instruction::{Assembler, Transfer};
+use crate::{Delay, FunctionMode, Hardware, Instruction};
+use core::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Nanoseconds in one second.
+const NS_PER_SECOND: f64 = 1e9;
+
+/// Mapping of the capture channels to the display pins.
+#[derive(Clone, Debug)]
+pub struct Pinout {
+    rs: String,
+    rw: Option<String>,
+    enable: String,
+    data: Vec<String>,
+}
+
+impl Pinout {
+    /// Create a new pinout from the names of the channels connected to R/S (`rs`), E (`enable`)
+    /// and data lines (`data`, either D0-D7 for 8-bit bus or D4-D7 for 4-bit bus, panics
+    /// otherwise). R/W is assumed to be tied low.
+    pub fn new(rs: &str, enable: &str, data: &[&str]) -> Self {
+        assert!(data.len() == 4 || data.len() == 8);
+        Pinout {
+            rs: rs.to_string(),
+            rw: None,
+            enable: enable.to_string(),
+            data: data.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    /// Name of the channel connected to R/W (`rw`).
+    pub fn rw(mut self, rw: &str) -> Self {
+        self.rw = Some(rw.to_string());
+        self
+    }
+
+    /// Width of the data bus.
+    pub fn mode(&self) -> FunctionMode {
+        if self.data.len() == 8 {
+            FunctionMode::Bit8
+        } else {
+            FunctionMode::Bit4
+        }
+    }
+}
+
+/// State of the display pins in a single sample. In 4-bit mode, `data` holds D7-D4 in the low
+/// nibble (same as [Hardware::data]).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Pins {
+    /// Register select, `true` for data.
+    pub rs: bool,
+    /// Read/write, `true` for reading (always `false` if the channel was not captured).
+    pub rw: bool,
+    /// Enable, data is latched on its falling edge.
+    pub enable: bool,
+    /// Data bus.
+    pub data: u8,
+}
+
+/// Error reading the CSV file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// There is no header line with the channel names.
+    NoHeader,
+    /// Channel with the given name is not in the capture.
+    MissingChannel(String),
+    /// There is neither time column nor samplerate comment.
+    NoTimebase,
+    /// Value on the given line (1-based) could not be parsed.
+    InvalidValue(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::NoHeader => write!(f, "no header with channel names"),
+            ParseError::MissingChannel(name) => write!(f, "channel '{}' is not found", name),
+            ParseError::NoTimebase => write!(f, "neither time column nor samplerate is given"),
+            ParseError::InvalidValue(line) => write!(f, "invalid value on line {}", line),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Protocol error found in the capture.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ProtocolError {
+    /// Instruction code `0x00`, which is not defined.
+    UndefinedInstruction,
+    /// R/S, R/W or data (during write) changed while E was high.
+    UnstableBus,
+    /// Second half of the 4-bit transfer is missing (R/S or R/W changed between the halves, or
+    /// capture ended).
+    IncompleteTransfer,
+    /// Timing constraint violation.
+    Timing(Diagnostic),
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::UndefinedInstruction => write!(f, "undefined instruction 0x00"),
+            ProtocolError::UnstableBus => write!(f, "bus changed while E is high"),
+            ProtocolError::IncompleteTransfer => write!(f, "incomplete 4-bit transfer"),
+            ProtocolError::Timing(diagnostic) => write!(f, "{}", diagnostic),
+        }
+    }
+}
+
+/// Decoded event.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EventKind {
+    /// Transfer was decoded.
+    Instruction(Instruction),
+    /// Protocol error was found.
+    Error(ProtocolError),
+}
+
+/// Decoded event with the time it happened at.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Event {
+    /// Time since the start of the capture, in nanoseconds.
+    pub time: u64,
+    pub kind: EventKind,
+}
+
+/// Passive bus model given to the [TimingChecker]: it only needs the bus width and the value
+/// read by the host.
+struct CaptureBus {
+    mode: FunctionMode,
+    read: u8,
+}
+
+impl Hardware for CaptureBus {
+    fn rs(&mut self, _bit: bool) {}
+
+    fn enable(&mut self, _bit: bool) {}
+
+    fn data(&mut self, _data: u8) {}
+
+    fn mode(&self) -> FunctionMode {
+        self.mode
+    }
+
+    fn can_read(&self) -> bool {
+        true
+    }
+
+    fn rw(&mut self, _bit: bool) {}
+
+    fn read_data(&mut self) -> u8 {
+        self.read
+    }
+}
+
+impl Delay for CaptureBus {
+    fn delay_us(&mut self, _delay_usec: u32) {}
+}
+
+/// Decoder of the pin samples into [Event]s.
+pub struct Decoder {
+    checker: TimingChecker<CaptureBus>,
+    events: Vec<Event>,
+    now: u64,
+    pins: Option<Pins>,
+    unstable: bool,
+    assembler: Assembler,
+}
+
+impl Decoder {
+    /// Create a new decoder for the data bus of the given width (`bus`), checking timing against
+    /// the given constraints (`constraints`).
+    pub fn new(bus: FunctionMode, constraints: Constraints) -> Self {
+        let mut checker = TimingChecker::new(CaptureBus { mode: bus, read: 0 }, constraints);
+        // Capture does not start at the power-on
+        checker.assume_initialized();
+        Decoder {
+            checker,
+            events: Vec::new(),
+            now: 0,
+            pins: None,
+            unstable: false,
+            assembler: Assembler::new(bus),
+        }
+    }
+
+    /// Process the next sample (`pins`) taken at the given time (`time`, in nanoseconds since the
+    /// start of the capture).
+    pub fn sample(&mut self, time: u64, pins: Pins) {
+        self.now = time;
+        self.checker.advance_to(time);
+        let previous = match self.pins.replace(pins) {
+            Some(previous) => previous,
+            None => {
+                // First sample only gives the initial state
+                self.checker.rs(pins.rs);
+                self.checker.rw(pins.rw);
+                self.checker.data(pins.data);
+                self.checker.take_diagnostics();
+                return;
+            }
+        };
+
+        if previous.enable && pins.enable {
+            let changed = pins.rs != previous.rs
+                || pins.rw != previous.rw
+                || (!pins.rw && pins.data != previous.data);
+            if changed && !self.unstable {
+                self.unstable = true;
+                self.push(ProtocolError::UnstableBus);
+            }
+        }
+        if pins.rs != previous.rs {
+            self.checker.rs(pins.rs);
+        }
+        if pins.rw != previous.rw {
+            self.checker.rw(pins.rw);
+        }
+        if pins.data != previous.data && !pins.rw {
+            self.checker.data(pins.data);
+        }
+
+        if pins.enable && !previous.enable {
+            self.unstable = false;
+            self.checker.enable(true);
+        } else if !pins.enable && previous.enable {
+            if pins.rw {
+                // Value read from the display is on the bus while E is high
+                self.checker.hw_mut().read = previous.data;
+                self.checker.read_data();
+            }
+            self.checker.enable(false);
+            self.latch(previous.rs, previous.rw, previous.data);
+        }
+        self.drain_diagnostics();
+    }
+
+    /// Finish decoding and return all events.
+    pub fn finish(mut self) -> Vec<Event> {
+        if self.assembler.is_pending() {
+            self.push(ProtocolError::IncompleteTransfer);
+        }
+        self.events
+    }
+
+    fn latch(&mut self, rs: bool, rw: bool, data: u8) {
+        match self.assembler.latch(rs, rw, data) {
+            Transfer::Half => {}
+            Transfer::Complete(Some(instruction)) => self.events.push(Event {
+                time: self.now,
+                kind: EventKind::Instruction(instruction),
+            }),
+            Transfer::Complete(None) => self.push(ProtocolError::UndefinedInstruction),
+            Transfer::Incomplete => self.push(ProtocolError::IncompleteTransfer),
+        }
+    }
+
+    fn push(&mut self, error: ProtocolError) {
+        self.events.push(Event {
+            time: self.now,
+            kind: EventKind::Error(error),
+        });
+    }
+
+    fn drain_diagnostics(&mut self) {
+        for diagnostic in self.checker.take_diagnostics() {
+            self.events.push(Event {
+                time: diagnostic.time,
+                kind: EventKind::Error(ProtocolError::Timing(diagnostic)),
+            });
+        }
+    }
+}
+
+/// Read samples from the sigrok CSV export (`input`) using the given pinout (`pinout`).
+///
+/// Lines starting with `;` are comments, `; Samplerate: <value> <unit>` comment gives the
+/// samplerate. First other line is the header with the channel names. If there is a column with
+/// the name starting with `Time`, its values (in seconds) are used as sample times, otherwise
+/// samples are assumed to be taken at the samplerate.
+pub fn parse_csv(input: &str, pinout: &Pinout) -> Result<Vec<(u64, Pins)>, ParseError> {
+    let mut period = None;
+    let mut columns: Option<Columns> = None;
+    let mut samples = Vec::new();
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix(';') {
+            if let Some(rate) = comment.trim().strip_prefix("Samplerate:") {
+                period = parse_samplerate(rate.trim()).map(|rate| NS_PER_SECOND / rate);
+            }
+            continue;
+        }
+        let values: Vec<&str> = line.split(',').map(str::trim).collect();
+        let columns = match &columns {
+            Some(columns) => columns,
+            None => {
+                columns = Some(Columns::new(&values, pinout, period.is_some())?);
+                continue;
+            }
+        };
+
+        let invalid = || ParseError::InvalidValue(index + 1);
+        let bit = |column: usize| match values.get(column) {
+            Some(&"1") => Ok(true),
+            Some(&"0") => Ok(false),
+            _ => Err(invalid()),
+        };
+        let time = match columns.time {
+            Some(column) => {
+                let seconds: f64 = values
+                    .get(column)
+                    .and_then(|value| value.parse().ok())
+                    .ok_or_else(invalid)?;
+                (seconds * NS_PER_SECOND).round() as u64
+            }
+            None => (samples.len() as f64 * period.unwrap_or_default()).round() as u64,
+        };
+        let mut data = 0;
+        for (bit_index, &column) in columns.data.iter().enumerate() {
+            if bit(column)? {
+                data |= 1 << bit_index;
+            }
+        }
+        let pins = Pins {
+            rs: bit(columns.rs)?,
+            rw: match columns.rw {
+                Some(column) => bit(column)?,
+                None => false,
+            },
+            enable: bit(columns.enable)?,
+            data,
+        };
+        samples.push((time, pins));
+    }
+    if columns.is_none() {
+        return Err(ParseError::NoHeader);
+    }
+    Ok(samples)
+}
+
+/// Read and decode the sigrok CSV export (`input`) using the given pinout (`pinout`), checking
+/// timing against the given constraints (`constraints`).
+pub fn decode_csv(
+    input: &str,
+    pinout: &Pinout,
+    constraints: Constraints,
+) -> Result<Vec<Event>, ParseError> {
+    let mut decoder = Decoder::new(pinout.mode(), constraints);
+    for (time, pins) in parse_csv(input, pinout)? {
+        decoder.sample(time, pins);
+    }
+    Ok(decoder.finish())
+}
+
+/// Indices of the columns in the CSV file.
+struct Columns {
+    time: Option<usize>,
+    rs: usize,
+    rw: Option<usize>,
+    enable: usize,
+    data: Vec<usize>,
+}
+
+impl Columns {
+    fn new(header: &[&str], pinout: &Pinout, has_samplerate: bool) -> Result<Self, ParseError> {
+        let find = |name: &str| {
+            header
+                .iter()
+                .position(|column| *column == name)
+                .ok_or_else(|| ParseError::MissingChannel(name.to_string()))
+        };
+        let time = header
+            .iter()
+            .position(|column| column.to_ascii_lowercase().starts_with("time"));
+        if time.is_none() && !has_samplerate {
+            return Err(ParseError::NoTimebase);
+        }
+        Ok(Columns {
+            time,
+            rs: find(&pinout.rs)?,
+            rw: match &pinout.rw {
+                Some(rw) => Some(find(rw)?),
+                None => None,
+            },
+            enable: find(&pinout.enable)?,
+            data: pinout
+                .data
+                .iter()
+                .map(|name| find(name))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Parse samplerate (`rate`, like `1 MHz` or `500 kHz`) into samples per second.
+fn parse_samplerate(rate: &str) -> Option<f64> {
+    let split = rate
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rate.len());
+    let value: f64 = rate[..split].trim().parse().ok()?;
+    let multiplier = match rate[split..].trim() {
+        "Hz" | "" => 1.0,
+        "kHz" => 1e3,
+        "MHz" => 1e6,
+        "GHz" => 1e9,
+        _ => return None,
+    };
+    Some(value * multiplier)
+}
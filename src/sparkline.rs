@@ -0,0 +1,270 @@
+//! Sparkline, a tiny chart of the last samples in one row.
+//!
+//! [Sparkline] keeps the last samples (one per pixel column by default, but no more than 80, see
+//! [Sparkline::set_samples_per_character]) and draws them as vertical bars, scaled to the range of
+//! the samples shown. Each character of the chart is a custom glyph; characters with the same
+//! image share the glyph, so the chart usually fits into the 8 CGRAM locations. When it does not,
+//! the vertical resolution is reduced, and then each character shows the highest of its samples as
+//! a single bar.
+//!
+//! Drawing is incremental: only the glyphs which have changed are uploaded and only the characters
+//! which have changed are rewritten.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::sparkline::Sparkline;
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! # fn read_temperature() -> i32 { 0 }
+//! let mut lcd = Display::new(hw);
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! // 8 characters (40 samples) at the end of the first row
+//! let mut chart = Sparkline::new(Geometry::new(16, 2), 8, 0, 8);
+//! loop {
+//!     chart.push(read_temperature());
+//!     chart.draw(&mut lcd);
+//! #   break;
+//! }
+//! ```
+use crate::shadow::{Shadow, CHARACTER_HEIGHT, CHARACTER_WIDTH, MAX_CHARACTERS};
+use crate::{Delay, Display, Geometry, Hardware};
+
+/// Number of CGRAM glyphs.
+const GLYPHS: usize = 8;
+/// Maximum number of samples kept.
+const MAX_SAMPLES: usize = 80;
+
+/// Sparkline chart, see the [module](self) documentation.
+pub struct Sparkline {
+    col: u8,
+    row: u8,
+    width: u8,
+    per_character: u8,
+    locations: core::ops::Range<u8>,
+    range: Option<(i32, i32)>,
+    /// Ring buffer of samples, `head` is the oldest one.
+    samples: [i32; MAX_SAMPLES],
+    head: usize,
+    count: usize,
+    /// Glyph in each CGRAM location, `None` if unknown.
+    uploaded: [Option<[u8; 8]>; GLYPHS],
+    shown: Shadow,
+}
+
+impl Sparkline {
+    /// Create a new empty chart in the given row (`row`), starting at the given column (`col`),
+    /// `width` characters wide, on the display of the given size (`geometry`).
+    pub fn new(geometry: Geometry, col: u8, row: u8, width: u8) -> Self {
+        assert!(
+            width > 0
+                && u16::from(col) + u16::from(width) <= u16::from(geometry.columns())
+                && row < geometry.rows()
+        );
+        let mut sparkline = Sparkline {
+            col,
+            row,
+            width,
+            per_character: 1,
+            locations: 0..GLYPHS as u8,
+            range: None,
+            samples: [0; MAX_SAMPLES],
+            head: 0,
+            count: 0,
+            uploaded: [None; GLYPHS],
+            shown: Shadow::new(geometry),
+        };
+        sparkline.set_samples_per_character(CHARACTER_WIDTH.min(MAX_SAMPLES as u8 / width));
+        sparkline
+    }
+
+    /// Set the number of samples shown in each character (`samples`, 1 to 5), each one is shown
+    /// as `5 / samples` pixels wide bar. Up to 80 samples are kept. Clears the chart.
+    pub fn set_samples_per_character(&mut self, samples: u8) {
+        assert!(samples > 0 && samples <= CHARACTER_WIDTH);
+        assert!(usize::from(samples) * usize::from(self.width) <= MAX_SAMPLES);
+        self.per_character = samples;
+        self.clear();
+    }
+
+    /// Use only the given CGRAM locations (`locations`) for the glyphs, so the rest of CGRAM stays
+    /// available for other glyphs.
+    pub fn set_glyph_locations(&mut self, locations: core::ops::Range<u8>) {
+        assert!(locations.start < locations.end && usize::from(locations.end) <= GLYPHS);
+        self.locations = locations;
+        self.invalidate();
+    }
+
+    /// Scale samples to the given range (`min` shown as the lowest bar, `max` as the full height)
+    /// instead of the range of the samples shown.
+    pub fn set_range(&mut self, min: i32, max: i32) {
+        assert!(min < max);
+        self.range = Some((min, max));
+    }
+
+    /// Scale samples to the range of the samples shown (default).
+    pub fn set_auto_range(&mut self) {
+        self.range = None;
+    }
+
+    /// Maximum number of samples shown.
+    pub fn capacity(&self) -> usize {
+        usize::from(self.width) * usize::from(self.per_character)
+    }
+
+    /// Add the sample (`sample`) on the right, dropping the oldest one if the chart is full.
+    pub fn push(&mut self, sample: i32) {
+        let capacity = self.capacity();
+        self.samples[(self.head + self.count) % capacity] = sample;
+        if self.count < capacity {
+            self.count += 1;
+        } else {
+            self.head = (self.head + 1) % capacity;
+        }
+    }
+
+    /// Remove all samples.
+    pub fn clear(&mut self) {
+        self.head = 0;
+        self.count = 0;
+    }
+
+    /// Minimum and maximum of the samples shown, `None` if there are none.
+    pub fn sample_range(&self) -> Option<(i32, i32)> {
+        let mut range = None;
+        for index in 0..self.count {
+            let sample = self.sample(index);
+            range = Some(match range {
+                Some((min, max)) => (sample.min(min), sample.max(max)),
+                None => (sample, sample),
+            });
+        }
+        range
+    }
+
+    /// Upload all glyphs and rewrite the whole chart on the next draw (for example, after CGRAM
+    /// was used by another widget).
+    pub fn invalidate(&mut self) {
+        self.uploaded = [None; GLYPHS];
+        self.shown.invalidate();
+    }
+
+    /// Update the display (`lcd`): upload changed glyphs and rewrite changed characters.
+    pub fn draw<HW: Hardware + Delay>(&mut self, lcd: &mut Display<HW>) {
+        let width = usize::from(self.width);
+        let slots = self.locations.len();
+        let mut images = [[0u8; 8]; MAX_CHARACTERS];
+        let mut distinct = [[0u8; 8]; GLYPHS];
+        let mut count = 0;
+        // Lower the resolution until distinct images fit into the available locations
+        'resolution: for &merge in &[false, true] {
+            'shift: for shift in 0..4 {
+                count = 0;
+                for (index, image) in images.iter_mut().take(width).enumerate() {
+                    *image = self.image(index, merge, shift);
+                    if *image == [0; 8] || distinct[..count].contains(image) {
+                        continue;
+                    }
+                    if count == slots {
+                        continue 'shift;
+                    }
+                    distinct[count] = *image;
+                    count += 1;
+                }
+                break 'resolution;
+            }
+        }
+
+        // Keep glyphs which are already uploaded, put others into the unused locations
+        let mut assigned = [None; GLYPHS];
+        for (index, image) in distinct[..count].iter().enumerate() {
+            assigned[index] = self
+                .locations
+                .clone()
+                .find(|&location| self.uploaded[usize::from(location)] == Some(*image));
+        }
+        for index in 0..count {
+            if assigned[index].is_none() {
+                let location = self
+                    .locations
+                    .clone()
+                    .find(|location| !assigned.contains(&Some(*location)))
+                    .unwrap();
+                lcd.upload_character(location, distinct[index]);
+                self.uploaded[usize::from(location)] = Some(distinct[index]);
+                assigned[index] = Some(location);
+            }
+        }
+
+        let mut codes = [b' '; MAX_CHARACTERS];
+        for (code, image) in codes.iter_mut().zip(images.iter()).take(width) {
+            if let Some(index) = distinct[..count].iter().position(|item| item == image) {
+                *code = assigned[index].unwrap();
+            }
+        }
+        self.shown.update(lcd, self.col, self.row, &codes[..width]);
+    }
+
+    /// Sample at the given index (`index`, 0 is the oldest one).
+    fn sample(&self, index: usize) -> i32 {
+        self.samples[(self.head + index) % self.capacity()]
+    }
+
+    /// Bar height (0 to 8) of the sample at the given position (`position`, 0 is the leftmost),
+    /// 0 if there is no sample there.
+    fn level(&self, position: usize, range: (i32, i32)) -> u8 {
+        let empty = self.capacity() - self.count;
+        if position < empty {
+            return 0;
+        }
+        let sample = self.sample(position - empty).clamp(range.0, range.1);
+        if range.0 == range.1 {
+            return 1;
+        }
+        let steps = i64::from(CHARACTER_HEIGHT - 1);
+        let scaled = (i64::from(sample) - i64::from(range.0)) * steps
+            / (i64::from(range.1) - i64::from(range.0));
+        1 + scaled as u8
+    }
+
+    /// Image of the given character (`index`). Bar heights are rounded up to multiples of
+    /// `1 << shift`, with `merge` character shows its highest sample as a single bar.
+    fn image(&self, index: usize, merge: bool, shift: u8) -> [u8; 8] {
+        let range = match self.range.or_else(|| self.sample_range()) {
+            Some(range) => range,
+            None => return [0; 8],
+        };
+        let per_character = usize::from(self.per_character);
+        let mut levels = [0; CHARACTER_WIDTH as usize];
+        for (col, level) in levels.iter_mut().enumerate() {
+            let position =
+                index * per_character + col * per_character / usize::from(CHARACTER_WIDTH);
+            *level = self.level(position, range);
+        }
+        if merge {
+            let highest = levels.iter().copied().max().unwrap_or(0);
+            levels = [highest; CHARACTER_WIDTH as usize];
+        }
+
+        let mut image = [0; 8];
+        for (col, &level) in levels.iter().enumerate() {
+            let level = if level == 0 {
+                0
+            } else {
+                (((level - 1) >> shift) + 1) << shift
+            };
+            for line in &mut image[usize::from(CHARACTER_HEIGHT - level.min(CHARACTER_HEIGHT))..] {
+                *line |= 0x10 >> col;
+            }
+        }
+        image
+    }
+}
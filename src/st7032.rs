@@ -0,0 +1,282 @@
+//! Support for the Sitronix ST7032 (and compatible AIP31068) controllers with native I2C
+//! interface.
+//!
+//! These controllers use HD44780 instruction set, but every byte is sent over I2C prefixed by
+//! the control byte (carrying Co and RS bits). They also support software contrast control,
+//! internal voltage booster / follower and icon RAM via the extended instruction table (IS=1).
+//!
+//! [St7032] implements both [Hardware] and [Delay], so it is used with the regular [Display]:
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::st7032::St7032;
+//!
+//! struct Bus;
+//! impl I2c for Bus {
+//!     fn write(&mut self, address: u8, bytes: &[u8]) {
+//!         // should write given bytes to the I2C device
+//!     }
+//! }
+//!
+//! struct Sleep;
+//! impl Delay for Sleep {
+//!     fn delay_us(&mut self, delay_usec: u32) {
+//!         // should sleep for the given amount of microseconds
+//!     }
+//! }
+//!
+//! let mut lcd = Display::new(St7032::new(Bus, Sleep));
+//! lcd.init_st7032(FunctionLine::Line2);
+//! lcd.set_contrast(40);
+//! lcd.display(DisplayMode::DisplayOn, DisplayCursor::CursorOff, DisplayBlink::BlinkOff);
+//! lcd.print("Hello");
+//! ```
+use crate::transport::I2cTransport;
+use crate::{Command, Delay, Display, FunctionDots, FunctionLine, FunctionMode, Hardware, I2c};
+
+/// Default 7-bit I2C address of ST7032 controller.
+pub const DEFAULT_ADDRESS: u8 = 0x3e;
+
+/// Number of addresses in the icon RAM.
+pub const ICON_ADDRESSES: u8 = 16;
+
+/// Selects extended instruction table in the function set instruction.
+const FUNCTION_IS: u8 = 0x01;
+
+/// Extended instructions (available when IS=1).
+#[derive(Copy, Clone, Debug)]
+enum ExtendedCommand {
+    InternalOsc = 0x10,
+    SetIconAddr = 0x40,
+    PowerIconContrast = 0x50,
+    FollowerControl = 0x60,
+    ContrastSet = 0x70,
+}
+
+/// Internal oscillator frequency adjustment recommended by the datasheet (BS = 0, F = 4).
+const OSC_DEFAULT: u8 = 0x04;
+/// Default contrast (middle of the range).
+const CONTRAST_DEFAULT: u8 = 32;
+/// Default follower amplified ratio (Rab2..0).
+const FOLLOWER_RATIO_DEFAULT: u8 = 4;
+/// Time for the power to stabilize after enabling the follower circuit (200ms).
+const FOLLOWER_DELAY_US: u32 = 200_000;
+
+/// [Hardware] and [Delay] implementation for ST7032-based display attached via I2C.
+pub struct St7032<I2C, D> {
+    transport: I2cTransport<I2C, D>,
+    function: u8,
+    contrast: u8,
+    booster: bool,
+    icons_enabled: bool,
+    follower: bool,
+    follower_ratio: u8,
+    icons: [u8; ICON_ADDRESSES as usize],
+}
+
+impl<I2C: I2c, D: Delay> St7032<I2C, D> {
+    /// Create a new [St7032] from the given I2C bus and [Delay] implementation, using the default
+    /// I2C address (`0x3e`).
+    pub fn new(bus: I2C, delay: D) -> Self {
+        Self::with_address(bus, delay, DEFAULT_ADDRESS)
+    }
+
+    /// Create a new [St7032] from the given I2C bus and [Delay] implementation, using the given
+    /// 7-bit I2C address (`address`).
+    pub fn with_address(bus: I2C, delay: D, address: u8) -> Self {
+        St7032 {
+            transport: I2cTransport::new(bus, delay, address),
+            function: (FunctionMode::Bit8 as u8) | (FunctionLine::Line2 as u8),
+            contrast: CONTRAST_DEFAULT,
+            booster: true,
+            icons_enabled: false,
+            follower: true,
+            follower_ratio: FOLLOWER_RATIO_DEFAULT,
+            icons: [0; ICON_ADDRESSES as usize],
+        }
+    }
+
+    /// Current contrast value (0-63).
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    /// Unwrap back to the I2C bus and [Delay] implementations.
+    pub fn unwrap(self) -> (I2C, D) {
+        self.transport.unwrap()
+    }
+
+    fn power_icon_contrast(&self) -> u8 {
+        let mut cmd = (ExtendedCommand::PowerIconContrast as u8) | ((self.contrast >> 4) & 0x3);
+        if self.icons_enabled {
+            cmd |= 0x08;
+        }
+        if self.booster {
+            cmd |= 0x04;
+        }
+        cmd
+    }
+
+    fn follower_control(&self) -> u8 {
+        let mut cmd = (ExtendedCommand::FollowerControl as u8) | (self.follower_ratio & 0x7);
+        if self.follower {
+            cmd |= 0x08;
+        }
+        cmd
+    }
+}
+
+impl<I2C: I2c, D: Delay> Hardware for St7032<I2C, D> {
+    #[inline(always)]
+    fn rs(&mut self, bit: bool) {
+        self.transport.rs(bit)
+    }
+
+    #[inline(always)]
+    fn enable(&mut self, bit: bool) {
+        self.transport.enable(bit)
+    }
+
+    #[inline(always)]
+    fn data(&mut self, data: u8) {
+        self.transport.data(data)
+    }
+
+    #[inline(always)]
+    fn mode(&self) -> FunctionMode {
+        self.transport.mode()
+    }
+
+    #[inline(always)]
+    fn apply(&mut self) {
+        self.transport.apply()
+    }
+}
+
+impl<I2C, D: Delay> Delay for St7032<I2C, D> {
+    #[inline(always)]
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.transport.delay_us(delay_usec)
+    }
+}
+
+impl<I2C: I2c, D: Delay> Display<St7032<I2C, D>> {
+    /// Initialize ST7032 display with the given number of lines (`line`). Runs the regular
+    /// [Display::init] sequence (with 5x8 font, as the font size bit of HD44780 selects double
+    /// height font on these controllers), then configures the internal oscillator, contrast,
+    /// voltage booster and follower circuit via the extended instruction table. Display is left
+    /// turned off, same as with [Display::init].
+    pub fn init_st7032(&mut self, line: FunctionLine) {
+        self.init(line, FunctionDots::Dots5x8);
+        self.hw.function = (FunctionMode::Bit8 as u8) | (line as u8);
+
+        let power = self.hw.power_icon_contrast();
+        let follower = self.hw.follower_control();
+        self.extended(|lcd| {
+            lcd.command((ExtendedCommand::InternalOsc as u8) | OSC_DEFAULT);
+            lcd.command((ExtendedCommand::ContrastSet as u8) | (lcd.hw.contrast & 0xf));
+            lcd.command(power);
+            lcd.command(follower);
+            // Wait for the power to stabilize
+            lcd.hw.delay_us(FOLLOWER_DELAY_US);
+        });
+    }
+
+    /// Set display contrast (`contrast`), in the range of 0-63 (panics otherwise).
+    pub fn set_contrast(&mut self, contrast: u8) -> &Self {
+        assert!(contrast <= 63);
+        self.hw.contrast = contrast;
+        let power = self.hw.power_icon_contrast();
+        self.extended(|lcd| {
+            lcd.command((ExtendedCommand::ContrastSet as u8) | (contrast & 0xf));
+            lcd.command(power);
+        });
+        self
+    }
+
+    /// Turn internal voltage booster on or off. Booster should be turned off when the display is
+    /// powered by 5V.
+    pub fn set_booster(&mut self, enabled: bool) -> &Self {
+        self.hw.booster = enabled;
+        let power = self.hw.power_icon_contrast();
+        self.extended(|lcd| {
+            lcd.command(power);
+        });
+        self
+    }
+
+    /// Turn internal follower circuit on or off (`enabled`) and set its amplified ratio
+    /// (`ratio`, 0-7, panics otherwise).
+    pub fn set_follower(&mut self, enabled: bool, ratio: u8) -> &Self {
+        assert!(ratio <= 7);
+        self.hw.follower = enabled;
+        self.hw.follower_ratio = ratio;
+        let follower = self.hw.follower_control();
+        self.extended(|lcd| {
+            lcd.command(follower);
+        });
+        self
+    }
+
+    /// Turn icon display on or off.
+    pub fn set_icons_enabled(&mut self, enabled: bool) -> &Self {
+        self.hw.icons_enabled = enabled;
+        let power = self.hw.power_icon_contrast();
+        self.extended(|lcd| {
+            lcd.command(power);
+        });
+        self
+    }
+
+    /// Set icon at the given icon RAM address (`address`, 0-15) and bit (`bit`, 0-4). Panics if
+    /// address or bit are out of range.
+    ///
+    /// Note that icon RAM shares the address counter with DDRAM, so cursor position needs to be
+    /// set again before printing.
+    pub fn set_icon(&mut self, address: u8, bit: u8) -> &Self {
+        assert!(bit <= 4);
+        let value = self.icon_value(address) | (1 << bit);
+        self.write_icon(address, value)
+    }
+
+    /// Clear icon at the given icon RAM address (`address`, 0-15) and bit (`bit`, 0-4). Panics
+    /// if address or bit are out of range.
+    ///
+    /// Note that icon RAM shares the address counter with DDRAM, so cursor position needs to be
+    /// set again before printing.
+    pub fn clear_icon(&mut self, address: u8, bit: u8) -> &Self {
+        assert!(bit <= 4);
+        let value = self.icon_value(address) & !(1 << bit);
+        self.write_icon(address, value)
+    }
+
+    /// Write all 5 bits (`bits`) of the icon RAM at the given address (`address`, 0-15, panics
+    /// otherwise).
+    ///
+    /// Note that icon RAM shares the address counter with DDRAM, so cursor position needs to be
+    /// set again before printing.
+    pub fn write_icon(&mut self, address: u8, bits: u8) -> &Self {
+        assert!(address < ICON_ADDRESSES);
+        let bits = bits & 0x1f;
+        self.hw.icons[address as usize] = bits;
+        self.extended(|lcd| {
+            lcd.command((ExtendedCommand::SetIconAddr as u8) | address);
+            lcd.write_raw(bits);
+        });
+        self
+    }
+
+    fn icon_value(&self, address: u8) -> u8 {
+        assert!(address < ICON_ADDRESSES);
+        self.hw.icons[address as usize]
+    }
+
+    /// Run given closure with extended instruction table selected (IS=1), then switch back to the
+    /// normal instruction table.
+    fn extended(&mut self, f: impl FnOnce(&mut Self)) {
+        let function = (Command::FunctionSet as u8) | self.hw.function;
+        self.command(function | FUNCTION_IS);
+        f(self);
+        self.command(function);
+    }
+}
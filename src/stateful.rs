@@ -0,0 +1,275 @@
+//! Tracking of the display state, so display control, entry mode and function set options could be
+//! changed one at a time.
+//!
+//! [Stateful] wrapper decodes instructions sent to the display and remembers the last display
+//! control, entry mode set and function set values (regardless of whether they were sent by
+//! [Display::display], [Display::init] or any other method). Methods like
+//! [set_cursor](Display::set_cursor) only change one option and only send an instruction if the
+//! option has actually changed.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::stateful::Stateful;
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(Stateful::new(hw));
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! lcd.set_display(true);
+//! lcd.set_blink(true);
+//! // Blink stays on
+//! lcd.set_cursor(true);
+//! ```
+use crate::instruction::{Assembler, Transfer};
+use crate::{
+    Delay, Display, DisplayBlink, DisplayCursor, DisplayMode, EntryModeDirection, EntryModeShift,
+    FunctionDots, FunctionLine, FunctionMode, Hardware, Instruction,
+};
+
+/// Last display control, entry mode set and function set values sent to the display.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct State {
+    pub display: DisplayMode,
+    pub cursor: DisplayCursor,
+    pub blink: DisplayBlink,
+    pub direction: EntryModeDirection,
+    pub shift: EntryModeShift,
+    pub mode: FunctionMode,
+    pub line: FunctionLine,
+    pub dots: FunctionDots,
+}
+
+impl State {
+    /// State after the internal reset circuit initialization, with the given interface width
+    /// (`mode`).
+    fn reset(mode: FunctionMode) -> Self {
+        State {
+            display: DisplayMode::DisplayOff,
+            cursor: DisplayCursor::CursorOff,
+            blink: DisplayBlink::BlinkOff,
+            direction: EntryModeDirection::EntryRight,
+            shift: EntryModeShift::NoShift,
+            mode,
+            line: FunctionLine::Line1,
+            dots: FunctionDots::Dots5x8,
+        }
+    }
+
+    fn update(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::DisplayControl(display, cursor, blink) => {
+                self.display = display;
+                self.cursor = cursor;
+                self.blink = blink;
+            }
+            Instruction::EntryModeSet(direction, shift) => {
+                self.direction = direction;
+                self.shift = shift;
+            }
+            Instruction::FunctionSet(mode, line, dots) => {
+                self.mode = mode;
+                self.line = line;
+                self.dots = dots;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// `Hardware + Delay` wrapper tracking the display state, see the [module](self) documentation.
+///
+/// State starts as after the internal reset (display off, cursor moving right, 1 line, 5x8 font),
+/// except for the interface width, which is assumed to match the bus. Extended instruction sets
+/// (like the one of [Ks0073](crate::ks0073::Ks0073)) reuse the same instruction codes, so this
+/// wrapper should not be used with them.
+pub struct Stateful<HW> {
+    hw: HW,
+    state: State,
+    assembler: Assembler,
+    rs: bool,
+    rw: bool,
+    enable: bool,
+    data: u8,
+}
+
+impl<HW: Hardware + Delay> Stateful<HW> {
+    /// Create a new [Stateful] from the given `Hardware + Delay` implementation.
+    pub fn new(hw: HW) -> Self {
+        let mode = hw.mode();
+        let mut assembler = Assembler::new(mode);
+        assembler.assume_initialized();
+        Stateful {
+            hw,
+            state: State::reset(mode),
+            assembler,
+            rs: false,
+            rw: false,
+            enable: false,
+            data: 0,
+        }
+    }
+
+    /// Unwrap back to the `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
+}
+
+impl<HW: Hardware> Hardware for Stateful<HW> {
+    #[inline(always)]
+    fn rs(&mut self, bit: bool) {
+        self.rs = bit;
+        self.hw.rs(bit)
+    }
+
+    fn enable(&mut self, bit: bool) {
+        if self.enable && !bit {
+            if let Transfer::Complete(Some(instruction)) =
+                self.assembler.latch(self.rs, self.rw, self.data)
+            {
+                self.state.update(instruction);
+            }
+        }
+        self.enable = bit;
+        self.hw.enable(bit)
+    }
+
+    #[inline(always)]
+    fn data(&mut self, data: u8) {
+        self.data = data;
+        self.hw.data(data)
+    }
+
+    #[inline(always)]
+    fn wait_address(&mut self) {
+        self.hw.wait_address()
+    }
+
+    #[inline(always)]
+    fn mode(&self) -> FunctionMode {
+        self.hw.mode()
+    }
+
+    #[inline(always)]
+    fn can_read(&self) -> bool {
+        self.hw.can_read()
+    }
+
+    #[inline(always)]
+    fn rw(&mut self, bit: bool) {
+        self.rw = bit;
+        self.hw.rw(bit)
+    }
+
+    #[inline(always)]
+    fn read_data(&mut self) -> u8 {
+        self.data = self.hw.read_data();
+        self.data
+    }
+
+    #[inline(always)]
+    fn apply(&mut self) {
+        self.hw.apply()
+    }
+}
+
+impl<HW: Delay> Delay for Stateful<HW> {
+    #[inline(always)]
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.hw.delay_us(delay_usec)
+    }
+}
+
+impl<HW: Hardware + Delay> Display<Stateful<HW>> {
+    /// Last display control, entry mode set and function set values sent to the display.
+    pub fn state(&self) -> &State {
+        &self.hw.state
+    }
+
+    /// Turn the display on or off.
+    pub fn set_display(&mut self, on: bool) -> &Self {
+        let display = if on {
+            DisplayMode::DisplayOn
+        } else {
+            DisplayMode::DisplayOff
+        };
+        let state = self.hw.state;
+        self.update(Instruction::DisplayControl(
+            display,
+            state.cursor,
+            state.blink,
+        ))
+    }
+
+    /// Turn the cursor on or off.
+    pub fn set_cursor(&mut self, on: bool) -> &Self {
+        let cursor = if on {
+            DisplayCursor::CursorOn
+        } else {
+            DisplayCursor::CursorOff
+        };
+        let state = self.hw.state;
+        self.update(Instruction::DisplayControl(
+            state.display,
+            cursor,
+            state.blink,
+        ))
+    }
+
+    /// Turn blinking of the cursor position character on or off.
+    pub fn set_blink(&mut self, on: bool) -> &Self {
+        let blink = if on {
+            DisplayBlink::BlinkOn
+        } else {
+            DisplayBlink::BlinkOff
+        };
+        let state = self.hw.state;
+        self.update(Instruction::DisplayControl(
+            state.display,
+            state.cursor,
+            blink,
+        ))
+    }
+
+    /// Set cursor move direction (`dir`).
+    pub fn set_entry_direction(&mut self, dir: EntryModeDirection) -> &Self {
+        let state = self.hw.state;
+        self.update(Instruction::EntryModeSet(dir, state.shift))
+    }
+
+    /// Set if display is shifted during data write (`shift`).
+    pub fn set_entry_shift(&mut self, shift: EntryModeShift) -> &Self {
+        let state = self.hw.state;
+        self.update(Instruction::EntryModeSet(state.direction, shift))
+    }
+
+    /// Set number of display lines (`line`).
+    pub fn set_lines(&mut self, line: FunctionLine) -> &Self {
+        let state = self.hw.state;
+        self.update(Instruction::FunctionSet(state.mode, line, state.dots))
+    }
+
+    /// Set font size (`dots`).
+    pub fn set_dots(&mut self, dots: FunctionDots) -> &Self {
+        let state = self.hw.state;
+        self.update(Instruction::FunctionSet(state.mode, state.line, dots))
+    }
+
+    /// Send given instruction (`instruction`), unless it would not change the state.
+    fn update(&mut self, instruction: Instruction) -> &Self {
+        let mut state = self.hw.state;
+        state.update(instruction);
+        if state != self.hw.state {
+            self.instruction(instruction);
+        }
+        self
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{
+    Delay, Direction, Display, DisplayBlink, DisplayCursor, DisplayMode, Hardware, TextMode,
+};
+
+/// Escape character, starts escape sequences.
+const ESC: u8 = 0x1b;
+/// Backspace character, moves cursor back and erases the character.
+const BACKSPACE: u8 = 0x08;
+/// Form feed character, clears the display.
+const FORM_FEED: u8 = 0x0c;
+/// Maximum length of the escape sequence (after ESC); longer sequences are dropped.
+const MAX_ESCAPE: usize = 24;
+/// Distance between tab stops, in columns.
+const TAB_WIDTH: u8 = 4;
+
+/// Escape sequence being received.
+#[derive(Copy, Clone)]
+pub(crate) struct EscapeBuffer {
+    bytes: [u8; MAX_ESCAPE],
+    len: usize,
+    active: bool,
+}
+
+impl EscapeBuffer {
+    pub fn new() -> Self {
+        EscapeBuffer {
+            bytes: [0; MAX_ESCAPE],
+            len: 0,
+            active: false,
+        }
+    }
+}
+
+/// Action requested by the escape sequence.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Sequence {
+    /// Move cursor to the given column and row (0-based); `None` keeps the current one.
+    Goto(Option<u8>, Option<u8>),
+    ClearScreen,
+    ClearLine,
+    Display(bool),
+    Cursor(bool),
+    Blink(bool),
+    ShiftCursor(Direction),
+    ShiftDisplay(Direction),
+    Reinitialize,
+    /// Upload custom character (location, glyph).
+    Glyph(u8, [u8; 8]),
+    /// Known, but unsupported sequence (or unknown CSI sequence), which is consumed.
+    Ignored,
+}
+
+/// Result of parsing the escape sequence received so far.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Parsed {
+    Incomplete,
+    Invalid,
+    Complete(Sequence),
+}
+
+/// Parse escape sequence (`seq`, without the leading ESC).
+fn parse(seq: &[u8]) -> Parsed {
+    match seq {
+        [] | [b'['] | [b'[', b'L'] => Parsed::Incomplete,
+        [b'[', b'L', rest @ ..] => parse_charlcd(rest),
+        [b'[', rest @ ..] => parse_csi(rest),
+        _ => Parsed::Invalid,
+    }
+}
+
+/// Parse ANSI / VT100 control sequence (`seq`, after `ESC[`).
+fn parse_csi(seq: &[u8]) -> Parsed {
+    let (last, params) = match seq.split_last() {
+        Some((&last, params)) if (0x40..=0x7e).contains(&last) => (last, params),
+        Some((&last, _)) if last.is_ascii_digit() || last == b';' || last == b'?' => {
+            return Parsed::Incomplete
+        }
+        _ => return Parsed::Invalid,
+    };
+    let (private, params) = match params {
+        [b'?', rest @ ..] => (true, rest),
+        _ => (false, params),
+    };
+    let mut values = [None; 2];
+    for (index, param) in params.split(|&b| b == b';').enumerate() {
+        match (values.get_mut(index), parse_number(param)) {
+            (Some(value), Some(number)) => *value = Some(number),
+            (Some(_), None) if param.is_empty() => {}
+            _ => return Parsed::Invalid,
+        }
+    }
+
+    // Parameters are 1-based
+    let position = |value: Option<u16>| value.unwrap_or(1).clamp(1, 0xff) as u8 - 1;
+    let sequence = match (private, last, values) {
+        (false, b'H', [row, col]) | (false, b'f', [row, col]) => {
+            Sequence::Goto(Some(position(col)), Some(position(row)))
+        }
+        (false, b'J', [Some(2), None]) => Sequence::ClearScreen,
+        (false, b'K', [None, None]) | (false, b'K', [Some(0), None]) => Sequence::ClearLine,
+        (true, b'h', [Some(25), None]) => Sequence::Cursor(true),
+        (true, b'l', [Some(25), None]) => Sequence::Cursor(false),
+        _ => Sequence::Ignored,
+    };
+    Parsed::Complete(sequence)
+}
+
+/// Parse Linux `charlcd` sequence (`seq`, after `ESC[L`).
+fn parse_charlcd(seq: &[u8]) -> Parsed {
+    let sequence = match seq {
+        [b'D'] => Sequence::Display(true),
+        [b'd'] => Sequence::Display(false),
+        [b'C'] => Sequence::Cursor(true),
+        [b'c'] => Sequence::Cursor(false),
+        [b'B'] => Sequence::Blink(true),
+        [b'b'] => Sequence::Blink(false),
+        [b'l'] => Sequence::ShiftCursor(Direction::Left),
+        [b'r'] => Sequence::ShiftCursor(Direction::Right),
+        [b'L'] => Sequence::ShiftDisplay(Direction::Left),
+        [b'R'] => Sequence::ShiftDisplay(Direction::Right),
+        [b'k'] => Sequence::ClearLine,
+        [b'I'] => Sequence::Reinitialize,
+        // Backlight, font and number of lines are not supported
+        [b'+'] | [b'-'] | [b'*'] | [b'f'] | [b'F'] | [b'n'] | [b'N'] => Sequence::Ignored,
+        [b'G', rest @ ..] => return parse_glyph(rest),
+        [b'x', ..] | [b'y', ..] => return parse_goto(seq),
+        _ => return Parsed::Invalid,
+    };
+    Parsed::Complete(sequence)
+}
+
+/// Parse `charlcd` go to sequence (`seq`, `x<col>y<row>;` with both parts optional).
+fn parse_goto(seq: &[u8]) -> Parsed {
+    let seq = match seq.split_last() {
+        Some((b';', seq)) => seq,
+        Some((&last, _)) if last.is_ascii_digit() || last == b'x' || last == b'y' => {
+            return Parsed::Incomplete
+        }
+        _ => return Parsed::Invalid,
+    };
+    let (mut col, mut row) = (None, None);
+    let mut rest = seq;
+    while let [axis, tail @ ..] = rest {
+        let digits = tail.iter().take_while(|b| b.is_ascii_digit()).count();
+        let value = match parse_number(&tail[..digits]) {
+            Some(value) if value <= 0xff => value as u8,
+            _ => return Parsed::Invalid,
+        };
+        match axis {
+            b'x' => col = Some(value),
+            b'y' => row = Some(value),
+            _ => return Parsed::Invalid,
+        }
+        rest = &tail[digits..];
+    }
+    Parsed::Complete(Sequence::Goto(col, row))
+}
+
+/// Parse `charlcd` custom character sequence (`seq`, `<location><hex digits>;`, up to 16 hex
+/// digits, two per row).
+fn parse_glyph(seq: &[u8]) -> Parsed {
+    let (location, digits) = match seq {
+        [] => return Parsed::Incomplete,
+        [location @ b'0'..=b'7', digits @ ..] => (location - b'0', digits),
+        _ => return Parsed::Invalid,
+    };
+    let (digits, complete) = match digits.split_last() {
+        Some((b';', digits)) => (digits, true),
+        _ => (digits, false),
+    };
+    if digits.len() > 16 || !digits.iter().all(u8::is_ascii_hexdigit) {
+        return Parsed::Invalid;
+    }
+    if !complete {
+        return Parsed::Incomplete;
+    }
+    let mut glyph = [0; 8];
+    for (index, &digit) in digits.iter().enumerate() {
+        let value = (digit as char).to_digit(16).unwrap_or(0) as u8;
+        glyph[index / 2] |= if index % 2 == 0 { value << 4 } else { value };
+    }
+    Parsed::Complete(Sequence::Glyph(location, glyph))
+}
+
+/// Parse decimal number (`digits`), `None` if empty or too large.
+fn parse_number(digits: &[u8]) -> Option<u16> {
+    if digits.is_empty() {
+        return None;
+    }
+    digits.iter().try_fold(0u16, |acc, &digit| {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        acc.checked_mul(10)?.checked_add(u16::from(digit - b'0'))
+    })
+}
+
+impl<HW: Hardware + Delay> Display<HW> {
+    /// Write given string (`s`) at the text cursor, interpreting control characters and escape
+    /// sequences.
+    pub(crate) fn write_terminal(&mut self, s: &str) {
+        for &byte in s.as_bytes() {
+            self.write_terminal_byte(byte);
+        }
+    }
+
+    fn write_terminal_byte(&mut self, byte: u8) {
+        let escape = match &mut self.text {
+            Some(text) => &mut text.escape,
+            None => return,
+        };
+        if escape.active {
+            if escape.len == MAX_ESCAPE {
+                // Too long, drop it
+                escape.active = false;
+                return;
+            }
+            escape.bytes[escape.len] = byte;
+            escape.len += 1;
+            match parse(&escape.bytes[..escape.len]) {
+                Parsed::Incomplete => {}
+                Parsed::Invalid => escape.active = false,
+                Parsed::Complete(sequence) => {
+                    escape.active = false;
+                    self.run_sequence(sequence);
+                }
+            }
+            return;
+        }
+        if byte == ESC {
+            escape.active = true;
+            escape.len = 0;
+            return;
+        }
+
+        let (col, row) = self.text_position().unwrap_or((0, 0));
+        match byte {
+            b'\n' => self.new_line(),
+            b'\r' => self.terminal_goto(0, row),
+            b'\t' => {
+                let columns = self.geometry().map_or(0, |geometry| geometry.columns());
+                let col = ((col / TAB_WIDTH + 1) * TAB_WIDTH).min(columns);
+                self.terminal_goto(col, row);
+            }
+            BACKSPACE => {
+                if col > 0 {
+                    self.terminal_goto(col - 1, row);
+                    self.write_text(b' ');
+                    self.terminal_goto(col - 1, row);
+                }
+            }
+            FORM_FEED => {
+                self.clear();
+            }
+            _ => self.write_text(byte),
+        }
+    }
+
+    fn run_sequence(&mut self, sequence: Sequence) {
+        let (col, row) = self.text_position().unwrap_or((0, 0));
+        let (columns, rows) = self
+            .geometry()
+            .map_or((0, 0), |geometry| (geometry.columns(), geometry.rows()));
+        match sequence {
+            Sequence::Goto(new_col, new_row) => {
+                let new_col = new_col.unwrap_or(col).min(columns.saturating_sub(1));
+                let new_row = new_row.unwrap_or(row).min(rows.saturating_sub(1));
+                self.terminal_goto(new_col, new_row);
+            }
+            Sequence::ClearScreen => {
+                self.clear();
+            }
+            Sequence::ClearLine => {
+                for _ in col..columns {
+                    self.write_text(b' ');
+                }
+                self.terminal_goto(col, row);
+            }
+            Sequence::Display(on) => self.update_control(|control| {
+                control.0 = if on {
+                    DisplayMode::DisplayOn
+                } else {
+                    DisplayMode::DisplayOff
+                }
+            }),
+            Sequence::Cursor(on) => self.update_control(|control| {
+                control.1 = if on {
+                    DisplayCursor::CursorOn
+                } else {
+                    DisplayCursor::CursorOff
+                }
+            }),
+            Sequence::Blink(on) => self.update_control(|control| {
+                control.2 = if on {
+                    DisplayBlink::BlinkOn
+                } else {
+                    DisplayBlink::BlinkOff
+                }
+            }),
+            Sequence::ShiftCursor(dir) => {
+                self.cursor(dir);
+            }
+            Sequence::ShiftDisplay(dir) => {
+                self.scroll(dir);
+            }
+            Sequence::Reinitialize => {
+                if let Some(config) = self.config {
+                    self.init_with(config.power_on_wait(false));
+                }
+            }
+            Sequence::Glyph(location, glyph) => {
+                self.upload_character(location, glyph);
+            }
+            Sequence::Ignored => {}
+        }
+    }
+
+    /// Move to the beginning of the next row, scrolling up in [TextMode::Scroll] mode.
+    fn new_line(&mut self) {
+        let (_, row) = self.text_position().unwrap_or((0, 0));
+        let rows = self.geometry().map_or(1, |geometry| geometry.rows());
+        if row + 1 < rows {
+            self.terminal_goto(0, row + 1);
+        } else if self.text_mode() == TextMode::Scroll {
+            self.scroll_up();
+            self.terminal_goto(0, row);
+        } else {
+            self.terminal_goto(0, 0);
+        }
+    }
+
+    /// Move text cursor to the given column (`col`) and row (`row`). Cursor is moved immediately,
+    /// unless it is past the end of the row.
+    fn terminal_goto(&mut self, col: u8, row: u8) {
+        let columns = self.geometry().map_or(0, |geometry| geometry.columns());
+        if col < columns {
+            self.position(col, row);
+        } else {
+            self.text_move(col, row, false);
+        }
+    }
+
+    /// Change display control (display, cursor and blink) with the given function (`f`).
+    fn update_control(&mut self, f: impl FnOnce(&mut (DisplayMode, DisplayCursor, DisplayBlink))) {
+        let mut control = self.control;
+        f(&mut control);
+        self.display(control.0, control.1, control.2);
+    }
+}
@@ -0,0 +1,251 @@
+//! Test support: `Hardware + Delay` implementation recording every call, and helpers to run
+//! [Display] operations against it.
+//!
+//! [BufferHardware] records the raw pin activity as a list of strings (`"R/S true"`,
+//! `"DATA 0b0100"`, `"EN false"`, ...) and, at the same time, decodes it into [Instruction]s, so
+//! tests could assert either on the exact pin sequence or on the high-level instructions.
+//!
+//! ```rust
+//! use lcd::testing;
+//! use lcd::*;
+//!
+//! let instructions = testing::test_instructions(FunctionMode::Bit4, None, |lcd| {
+//!     lcd.position(0, 1);
+//!     lcd.print("Hi");
+//! });
+//! assert_eq!(
+//!     instructions,
+//!     vec![
+//!         Instruction::SetDDRamAddr(0x40),
+//!         Instruction::Write(b'H'),
+//!         Instruction::Write(b'i'),
+//!     ]
+//! );
+//! ```
+#[cfg(feature = "emulator")]
+use crate::emulator::Hd44780Emulator;
+use crate::instruction::{Assembler, Transfer};
+use crate::{
+    Backlight, Delay, Display, FunctionDots, FunctionLine, FunctionMode, Hardware, HardwareDelay,
+    Instruction, Timing,
+};
+#[cfg(all(feature = "emulator", feature = "text"))]
+use crate::{
+    DisplayBlink, DisplayCursor, DisplayMode, Geometry, InitConfig, TextMode, UninitializedDisplay,
+};
+use std::format;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// `Hardware + Delay` implementation recording every call.
+pub struct BufferHardware {
+    /// Recorded calls, in order.
+    pub commands: Vec<String>,
+    /// Values returned by [Hardware::read_data]; reading is not supported if `None`.
+    pub input: Option<Vec<u8>>,
+    /// Width of the data bus.
+    pub mode: FunctionMode,
+
+    rs: bool,
+    rw: bool,
+    enable: bool,
+    data: u8,
+    assembler: Assembler,
+    instructions: Vec<Instruction>,
+    /// First transfer which could not be decoded, reported by [BufferHardware::instructions].
+    error: Option<String>,
+}
+
+impl BufferHardware {
+    /// Create a new recording hardware with the given data bus width (`mode`). If `input` is
+    /// given, reading is supported and values are taken from it (panics if there are no values
+    /// left).
+    ///
+    /// Display is assumed to be in the interface width matching the bus, so instructions are
+    /// decoded even without initialization.
+    pub fn new(mode: FunctionMode, input: Option<Vec<u8>>) -> Self {
+        let mut assembler = Assembler::new(mode);
+        assembler.assume_initialized();
+        BufferHardware {
+            commands: Vec::new(),
+            input,
+            mode,
+            rs: false,
+            rw: false,
+            enable: false,
+            data: 0,
+            assembler,
+            instructions: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Record the given call (`cmd`).
+    pub fn command(&mut self, cmd: String) {
+        self.commands.push(cmd);
+    }
+
+    /// Instructions decoded from the pin activity so far. Busy flag polling is omitted. Panics
+    /// if undefined instruction or incomplete 4-bit transfer was sent.
+    pub fn instructions(&self) -> &[Instruction] {
+        if let Some(error) = &self.error {
+            panic!("{}", error);
+        }
+        &self.instructions
+    }
+
+    fn latch(&mut self) {
+        let error = match self.assembler.latch(self.rs, self.rw, self.data) {
+            Transfer::Half => return,
+            Transfer::Complete(Some(Instruction::ReadBusyAddress { .. })) => return,
+            Transfer::Complete(Some(instruction)) => {
+                self.instructions.push(instruction);
+                return;
+            }
+            Transfer::Complete(None) => "undefined instruction 0x00 was sent",
+            Transfer::Incomplete => "incomplete 4-bit transfer was sent",
+        };
+        if self.error.is_none() {
+            // Position of the enable pulse in the recorded calls, to find the offending step
+            let position = self.commands.len() - 1;
+            self.error = Some(format!("{} (command #{})", error, position));
+        }
+    }
+}
+
+impl Hardware for BufferHardware {
+    fn rs(&mut self, bit: bool) {
+        self.command(format!("R/S {}", bit));
+        self.rs = bit;
+    }
+
+    fn enable(&mut self, bit: bool) {
+        self.command(format!("EN {}", bit));
+        let falling = self.enable && !bit;
+        self.enable = bit;
+        if falling {
+            self.latch();
+        }
+    }
+
+    fn data(&mut self, data: u8) {
+        let str = match self.mode {
+            FunctionMode::Bit4 => format!("DATA 0b{:04b}", data),
+            FunctionMode::Bit8 => format!("DATA 0b{:08b}", data),
+        };
+        self.command(str);
+        self.data = data;
+    }
+
+    fn mode(&self) -> FunctionMode {
+        self.mode
+    }
+
+    fn can_read(&self) -> bool {
+        self.input.is_some()
+    }
+
+    fn rw(&mut self, bit: bool) {
+        self.command(format!("RW {}", bit));
+        self.rw = bit;
+    }
+
+    fn read_data(&mut self) -> u8 {
+        self.command("IS BUSY?".to_string());
+        let data = self.input.as_mut().unwrap().remove(0);
+        self.data = data;
+        data
+    }
+}
+
+impl Delay for BufferHardware {
+    fn delay_us(&mut self, delay: u32) {
+        self.command(format!("DELAY {}", delay));
+    }
+}
+
+impl Backlight for BufferHardware {
+    fn set_backlight(&mut self, enable: bool) {
+        self.command(format!("BACKLIGHT {}", enable));
+    }
+}
+
+/// [Delay] implementation which does nothing.
+pub struct IgnoredDelay;
+
+impl Delay for IgnoredDelay {
+    fn delay_us(&mut self, _delay: u32) {}
+}
+
+/// Run operations (`ops`) against [BufferHardware] with the given data bus width (`mode`) and
+/// input (`input`), returning recorded calls.
+pub fn test(
+    mode: FunctionMode,
+    input: Option<Vec<u8>>,
+    ops: impl Fn(&mut Display<BufferHardware>),
+) -> Vec<String> {
+    let mut display = Display::new(BufferHardware::new(mode, input));
+    ops(&mut display);
+    display.unwrap().commands
+}
+
+/// Same as [test()], but with the given timing profile (`timing`).
+pub fn test_timing(
+    mode: FunctionMode,
+    timing: Timing,
+    ops: impl Fn(&mut Display<BufferHardware>),
+) -> Vec<String> {
+    let mut display = Display::with_timing(BufferHardware::new(mode, None), timing);
+    ops(&mut display);
+    display.unwrap().commands
+}
+
+/// Same as [test()], but against the [HardwareDelay] combiner, using [IgnoredDelay].
+pub fn test_ignored_delay(
+    mode: FunctionMode,
+    input: Option<Vec<u8>>,
+    ops: impl Fn(&mut Display<HardwareDelay<BufferHardware, IgnoredDelay>>),
+) -> Vec<String> {
+    let hw = BufferHardware::new(mode, input);
+    let mut display = Display::new(HardwareDelay::new(hw, IgnoredDelay));
+    ops(&mut display);
+    display.unwrap().unwrap().0.commands
+}
+
+/// Same as [test()], but returning decoded instructions (see [BufferHardware::instructions]).
+pub fn test_instructions(
+    mode: FunctionMode,
+    input: Option<Vec<u8>>,
+    ops: impl Fn(&mut Display<BufferHardware>),
+) -> Vec<Instruction> {
+    let mut display = Display::new(BufferHardware::new(mode, input));
+    ops(&mut display);
+    display.unwrap().instructions().to_vec()
+}
+
+/// Create display backed by [Hd44780Emulator] with 4-bit bus, initialized as 2-line display with
+/// 5x8 font (with the `emulator` feature).
+#[cfg(feature = "emulator")]
+pub fn emulator_display() -> Display<Hd44780Emulator> {
+    let mut display = Display::new(Hd44780Emulator::new(FunctionMode::Bit4));
+    display.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+    display
+}
+
+/// Create display backed by [Hd44780Emulator] with 4-bit bus, initialized as 2-line display with
+/// 5x8 font of the given size (`geometry`) and turned on, with the given text mode (`mode`) (with
+/// the `emulator` and `text` features).
+#[cfg(all(feature = "emulator", feature = "text"))]
+pub fn emulator_text_display(geometry: Geometry, mode: TextMode) -> Display<Hd44780Emulator> {
+    let mut display = UninitializedDisplay::new(Hd44780Emulator::new(FunctionMode::Bit4)).init(
+        InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+            .geometry(geometry)
+            .display(
+                DisplayMode::DisplayOn,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff,
+            ),
+    );
+    display.set_text_mode(mode);
+    display
+}
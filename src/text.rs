@@ -0,0 +1,168 @@
+use crate::shadow::MAX_CHARACTERS;
+use crate::terminal::EscapeBuffer;
+use crate::{Delay, Direction, Display, Hardware, Instruction};
+
+/// How text written by [Display::write] and [Display::print] is placed on the display (with the
+/// `text` feature).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TextMode {
+    /// Characters are written to the consecutive DDRAM addresses, so on 4-row displays text
+    /// continues from row 0 to row 2.
+    #[default]
+    Raw,
+    /// When row is filled, text continues on the next row (first row after the last one).
+    Wrap,
+    /// Characters past the end of the row are dropped.
+    Truncate,
+    /// When row is filled, text continues on the next row; text is scrolled up when the last row
+    /// is filled.
+    Scroll,
+}
+
+/// Logical text cursor, with the copy of the display content.
+#[derive(Copy, Clone)]
+pub(crate) struct TextCursor {
+    mode: TextMode,
+    pub col: u8,
+    pub row: u8,
+    /// If address counter of the display points to the cursor position.
+    pub addressed: bool,
+    pub buffer: [u8; MAX_CHARACTERS],
+    pub escape: EscapeBuffer,
+}
+
+impl TextCursor {
+    fn new(mode: TextMode) -> Self {
+        TextCursor {
+            mode,
+            col: 0,
+            row: 0,
+            addressed: false,
+            buffer: [b' '; MAX_CHARACTERS],
+            escape: EscapeBuffer::new(),
+        }
+    }
+}
+
+impl<HW: Hardware + Delay> Display<HW> {
+    /// Set text mode (`mode`). In any mode other than [TextMode::Raw], text cursor is tracked
+    /// using the display geometry (panics if it was not given in [InitConfig::geometry]), starting
+    /// at the top left corner. Display content is assumed to be blank, so the display should be
+    /// cleared first. Entry mode should be left as cursor moving right, with no display shift.
+    ///
+    /// Positioning ([Display::position], [Display::home], [Display::cursor]) and clearing move the
+    /// text cursor, too.
+    ///
+    /// [InitConfig::geometry]: crate::InitConfig::geometry
+    pub fn set_text_mode(&mut self, mode: TextMode) -> &Self {
+        self.text = if mode == TextMode::Raw {
+            None
+        } else {
+            assert!(self.geometry().is_some());
+            Some(TextCursor::new(mode))
+        };
+        self
+    }
+
+    /// Text mode set by [Display::set_text_mode].
+    pub fn text_mode(&self) -> TextMode {
+        self.text.as_ref().map_or(TextMode::Raw, |text| text.mode)
+    }
+
+    /// Column and row of the text cursor, `None` in [TextMode::Raw] mode. Column is equal to the
+    /// number of columns if row was just filled.
+    pub fn text_position(&self) -> Option<(u8, u8)> {
+        self.text.as_ref().map(|text| (text.col, text.row))
+    }
+
+    /// Move text cursor to the given column (`col`) and row (`row`).
+    pub(crate) fn text_move(&mut self, col: u8, row: u8, addressed: bool) {
+        if let Some(text) = &mut self.text {
+            text.col = col;
+            text.row = row;
+            text.addressed = addressed;
+        }
+    }
+
+    /// Move text cursor after the cursor shift in the given direction (`dir`).
+    pub(crate) fn text_shift(&mut self, dir: Direction) {
+        let columns = self.text_geometry().0;
+        if let Some(text) = &mut self.text {
+            text.col = match dir {
+                Direction::Left => text.col.saturating_sub(1),
+                Direction::Right => (text.col + 1).min(columns),
+            };
+        }
+    }
+
+    /// Forget the display content after it was cleared.
+    pub(crate) fn text_clear(&mut self) {
+        if let Some(text) = &mut self.text {
+            text.buffer = [b' '; MAX_CHARACTERS];
+        }
+        self.text_move(0, 0, true);
+    }
+
+    /// Write given character (`data`) at the text cursor.
+    pub(crate) fn write_text(&mut self, data: u8) {
+        let (columns, rows) = self.text_geometry();
+        let (mode, mut col, mut row, mut addressed) = match &self.text {
+            Some(text) => (text.mode, text.col, text.row, text.addressed),
+            None => return,
+        };
+        if col >= columns {
+            match mode {
+                TextMode::Truncate => return,
+                TextMode::Scroll if row + 1 == rows => self.scroll_up(),
+                _ => row = (row + 1) % rows,
+            }
+            col = 0;
+            addressed = false;
+        }
+
+        if !addressed {
+            self.text_address(col, row);
+        }
+        self.write_raw(data);
+        if let Some(text) = &mut self.text {
+            text.buffer[usize::from(row) * usize::from(columns) + usize::from(col)] = data;
+            text.col = col + 1;
+        }
+    }
+
+    /// Scroll text up by one row, leaving last row blank.
+    pub(crate) fn scroll_up(&mut self) {
+        let (columns, rows) = self.text_geometry();
+        let (columns, rows) = (usize::from(columns), usize::from(rows));
+        let mut buffer = match &self.text {
+            Some(text) => text.buffer,
+            None => return,
+        };
+        buffer.copy_within(columns..columns * rows, 0);
+        for item in &mut buffer[columns * (rows - 1)..columns * rows] {
+            *item = b' ';
+        }
+        for row in 0..rows {
+            self.text_address(0, row as u8);
+            for &data in &buffer[row * columns..(row + 1) * columns] {
+                self.write_raw(data);
+            }
+        }
+        if let Some(text) = &mut self.text {
+            text.buffer = buffer;
+        }
+    }
+
+    /// Set address counter to the given column (`col`) and row (`row`) of the text cursor.
+    fn text_address(&mut self, col: u8, row: u8) {
+        if let Some(geometry) = self.geometry() {
+            self.instruction(Instruction::SetDDRamAddr(geometry.address(col, row)));
+        }
+        self.text_move(col, row, true);
+    }
+
+    fn text_geometry(&self) -> (u8, u8) {
+        self.geometry()
+            .map_or((0, 0), |geometry| (geometry.columns(), geometry.rows()))
+    }
+}
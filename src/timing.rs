@@ -0,0 +1,76 @@
+/// Frequency of the oscillator the default timings are specified for, in kHz.
+const TYPICAL_OSCILLATOR_KHZ: u32 = 270;
+/// Lowest oscillator frequency allowed by the datasheet, in kHz.
+const SLOWEST_OSCILLATOR_KHZ: u32 = 190;
+
+/// Timing profile of the display. All values are in microseconds.
+///
+/// Execution times depend on the frequency of the display oscillator, which varies between
+/// the controllers (and with temperature and supply voltage). Default values are specified for
+/// the typical 270 kHz oscillator (with some margin); [Timing::for_oscillator] could be used to
+/// scale them for the different frequency.
+///
+/// Execution times are only used when busy flag cannot be read (see [Hardware::can_read]).
+///
+/// [Hardware::can_read]: crate::Hardware::can_read
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Timing {
+    /// Time to wait after power-on before initialization (more than 40ms after Vcc rises to 2.7V).
+    pub power_on: u32,
+    /// Time to wait after the first function set of the reset-by-instruction sequence (more than
+    /// 4.1ms).
+    pub reset_first: u32,
+    /// Time to wait after the second function set of the reset-by-instruction sequence (more than
+    /// 100us).
+    pub reset_second: u32,
+    /// Execution time of the clear display and return home instructions (1.52ms).
+    pub clear_home: u32,
+    /// Execution time of all other instructions (37us).
+    pub command: u32,
+    /// Time to update the address counter after the data write (tADD, 4us).
+    pub address_update: u32,
+    /// Enable pulse width, also used as data delay time when reading (PW_EH, 450ns).
+    pub enable_pulse: u32,
+}
+
+impl Timing {
+    /// Timing profile for the typical 270 kHz oscillator.
+    pub const fn typical() -> Self {
+        Timing {
+            power_on: 50_000,
+            reset_first: 4500,
+            reset_second: 150,
+            clear_home: 2000,
+            command: 50,
+            address_update: 5,
+            enable_pulse: 1,
+        }
+    }
+
+    /// Timing profile for the slowest oscillator allowed by the datasheet (190 kHz). Should be
+    /// used with slow clones or at the low temperature.
+    pub fn worst_case() -> Self {
+        Timing::for_oscillator(SLOWEST_OSCILLATOR_KHZ)
+    }
+
+    /// Timing profile for the given oscillator frequency (`khz`, in kHz). Execution times of the
+    /// [Timing::typical] profile are scaled proportionally (rounding up); waits which do not
+    /// depend on the oscillator are not changed.
+    pub fn for_oscillator(khz: u32) -> Self {
+        assert!(khz > 0);
+        let scale = |usec: u32| (usec * TYPICAL_OSCILLATOR_KHZ).div_ceil(khz);
+        let typical = Timing::typical();
+        Timing {
+            clear_home: scale(typical.clear_home),
+            command: scale(typical.command),
+            address_update: scale(typical.address_update),
+            ..typical
+        }
+    }
+}
+
+impl Default for Timing {
+    fn default() -> Self {
+        Timing::typical()
+    }
+}
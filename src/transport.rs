@@ -0,0 +1,168 @@
+//! Serial transports for controllers with native I2C or SPI interface.
+//!
+//! Transports implement [Hardware] (always in 8-bit mode) and [Delay], so they could be used
+//! directly with the [Display](crate::Display). Byte is sent over the bus at the moment the
+//! controller would latch it on the parallel bus, that is, on the falling edge of the enable signal.
+//!
+//! Neither transport supports reading, so delays are always used instead of polling busy flag.
+use crate::{Delay, FunctionMode, Hardware, I2c, Spi};
+
+/// Control byte for instruction write (Co = 0, D/C = 0).
+const I2C_CONTROL_COMMAND: u8 = 0x00;
+/// Control byte for data write (Co = 0, D/C = 1).
+const I2C_CONTROL_DATA: u8 = 0x40;
+
+/// Start byte of the serial transfer (five synchronization bits, R/W = 0), least significant bit
+/// first.
+const SPI_START: u8 = 0x1f;
+/// R/S bit of the start byte, least significant bit first.
+const SPI_START_RS: u8 = 0x40;
+
+/// State of the control and data lines, as set by the [Display](crate::Display).
+#[derive(Default)]
+struct Latch {
+    rs: bool,
+    enable: bool,
+    pending: bool,
+    data: u8,
+}
+
+impl Latch {
+    /// Returns R/S flag and the data byte if the data was latched (enable went low).
+    fn apply(&mut self) -> Option<(bool, u8)> {
+        if self.enable {
+            self.pending = true;
+            None
+        } else if self.pending {
+            self.pending = false;
+            Some((self.rs, self.data))
+        } else {
+            None
+        }
+    }
+}
+
+/// I2C transport. Every byte is sent in a separate transaction, prefixed by a control byte
+/// (Co = 0, D/C = R/S).
+pub struct I2cTransport<I2C, D> {
+    bus: I2C,
+    delay: D,
+    address: u8,
+    latch: Latch,
+}
+
+impl<I2C, D> I2cTransport<I2C, D> {
+    /// Create a new [I2cTransport] from the given I2C bus and [Delay] implementation, talking to
+    /// the device at the given 7-bit address (`address`).
+    pub fn new(bus: I2C, delay: D, address: u8) -> Self {
+        I2cTransport {
+            bus,
+            delay,
+            address,
+            latch: Latch::default(),
+        }
+    }
+
+    /// Unwrap back to the I2C bus and [Delay] implementations.
+    pub fn unwrap(self) -> (I2C, D) {
+        (self.bus, self.delay)
+    }
+}
+
+impl<I2C: I2c, D> Hardware for I2cTransport<I2C, D> {
+    fn rs(&mut self, bit: bool) {
+        self.latch.rs = bit;
+    }
+
+    fn enable(&mut self, bit: bool) {
+        self.latch.enable = bit;
+    }
+
+    fn data(&mut self, data: u8) {
+        self.latch.data = data;
+    }
+
+    fn mode(&self) -> FunctionMode {
+        FunctionMode::Bit8
+    }
+
+    fn apply(&mut self) {
+        if let Some((rs, data)) = self.latch.apply() {
+            let control = if rs {
+                I2C_CONTROL_DATA
+            } else {
+                I2C_CONTROL_COMMAND
+            };
+            self.bus.write(self.address, &[control, data]);
+        }
+    }
+}
+
+impl<I2C, D: Delay> Delay for I2cTransport<I2C, D> {
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.delay.delay_us(delay_usec)
+    }
+}
+
+/// SPI transport, using the synchronous serial format of US2066 / SSD1311 and similar
+/// controllers: start byte (with R/W and R/S bits) followed by the lower and the upper halves of
+/// the data byte.
+///
+/// Bytes are given to the [Spi] implementation in the order they are transmitted least
+/// significant bit first, so SPI peripheral should be configured for the LSB-first transfers.
+pub struct SpiTransport<SPI, D> {
+    bus: SPI,
+    delay: D,
+    latch: Latch,
+}
+
+impl<SPI, D> SpiTransport<SPI, D> {
+    /// Create a new [SpiTransport] from the given SPI bus and [Delay] implementation.
+    pub fn new(bus: SPI, delay: D) -> Self {
+        SpiTransport {
+            bus,
+            delay,
+            latch: Latch::default(),
+        }
+    }
+
+    /// Unwrap back to the SPI bus and [Delay] implementations.
+    pub fn unwrap(self) -> (SPI, D) {
+        (self.bus, self.delay)
+    }
+}
+
+impl<SPI: Spi, D> Hardware for SpiTransport<SPI, D> {
+    fn rs(&mut self, bit: bool) {
+        self.latch.rs = bit;
+    }
+
+    fn enable(&mut self, bit: bool) {
+        self.latch.enable = bit;
+    }
+
+    fn data(&mut self, data: u8) {
+        self.latch.data = data;
+    }
+
+    fn mode(&self) -> FunctionMode {
+        FunctionMode::Bit8
+    }
+
+    fn apply(&mut self) {
+        if let Some((rs, data)) = self.latch.apply() {
+            let start = if rs {
+                SPI_START | SPI_START_RS
+            } else {
+                SPI_START
+            };
+            self.bus.write(&[start, data & 0xf, data >> 4]);
+        }
+    }
+}
+
+impl<SPI, D: Delay> Delay for SpiTransport<SPI, D> {
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.delay.delay_us(delay_usec)
+    }
+}
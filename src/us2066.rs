@@ -0,0 +1,376 @@
+//! Support for the US2066 / SSD1311 character OLED controllers (Winstar, Newhaven and
+//! similar modules).
+//!
+//! These controllers are mostly compatible with HD44780 instruction set, but require different
+//! initialization sequence and provide additional features (brightness, double height lines,
+//! fade-out and blinking modes, CGROM selection) via the extended instruction sets (RE and SD
+//! bits).
+//!
+//! [Us2066] wraps any `Hardware + Delay` implementation: either parallel (4-bit or 8-bit) one or
+//! serial one from the [transport](crate::transport) module.
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::transport::I2cTransport;
+//! use lcd::us2066::{self, Lines, Regulator, Rom, Us2066};
+//!
+//! struct Bus;
+//! impl I2c for Bus {
+//!     fn write(&mut self, address: u8, bytes: &[u8]) {
+//!         // should write given bytes to the I2C device
+//!     }
+//! }
+//!
+//! struct Sleep;
+//! impl Delay for Sleep {
+//!     fn delay_us(&mut self, delay_usec: u32) {
+//!         // should sleep for the given amount of microseconds
+//!     }
+//! }
+//!
+//! let transport = I2cTransport::new(Bus, Sleep, us2066::DEFAULT_ADDRESS);
+//! let mut lcd = Display::new(Us2066::new(transport));
+//! lcd.init_us2066(Lines::Line4, Rom::A, Regulator::Internal);
+//! lcd.set_brightness(0xff);
+//! lcd.display(DisplayMode::DisplayOn, DisplayCursor::CursorOff, DisplayBlink::BlinkOff);
+//! lcd.print("Hello");
+//! ```
+use crate::{
+    Command, Delay, Display, DisplayBlink, DisplayCursor, DisplayMode, EntryModeDirection,
+    EntryModeShift, FunctionMode, Hardware,
+};
+
+/// Default 7-bit I2C address of US2066 controller (SA0 pin is low).
+pub const DEFAULT_ADDRESS: u8 = 0x3c;
+
+/// Number of lines bit of the function set instruction.
+const FUNCTION_N: u8 = 0x08;
+/// Double height bit of the function set instruction (RE = 0).
+const FUNCTION_DH: u8 = 0x04;
+/// Extended instruction set bit of the function set instruction.
+const FUNCTION_RE: u8 = 0x02;
+/// Reverse display bit of the function set instruction (RE = 1).
+const FUNCTION_REV: u8 = 0x01;
+
+/// Extended instructions (available when RE = 1).
+#[derive(Copy, Clone, Debug)]
+enum ExtendedCommand {
+    /// Common / segment direction (instead of entry mode set).
+    ComSegDirection = 0x04,
+    /// Extended function set (instead of display control).
+    ExtendedFunctionSet = 0x08,
+    /// Double height / display-dot shift (instead of cursor or display shift).
+    DoubleHeight = 0x10,
+    FunctionSelectionA = 0x71,
+    FunctionSelectionB = 0x72,
+    OledCommandSetDisabled = 0x78,
+    OledCommandSetEnabled = 0x79,
+}
+
+/// OLED instructions (available when RE = 1 and SD = 1). All of them take one argument byte.
+#[derive(Copy, Clone, Debug)]
+enum OledCommand {
+    FadeOutBlinking = 0x23,
+    ContrastControl = 0x81,
+    DisplayClock = 0xd5,
+    PhaseLength = 0xd9,
+    SegPinsConfiguration = 0xda,
+    VcomhDeselectLevel = 0xdb,
+    FunctionSelectionC = 0xdc,
+}
+
+/// Number of display lines.
+#[derive(Copy, Clone, Debug)]
+pub enum Lines {
+    Line1 = 0x00,
+    Line2 = 0x08,
+    Line3 = 0x01,
+    Line4 = 0x09,
+}
+
+/// Character ROM selection.
+#[derive(Copy, Clone, Debug)]
+pub enum Rom {
+    A = 0x00,
+    B = 0x04,
+    C = 0x08,
+}
+
+/// Split of the 256 character codes between character ROM and character RAM.
+#[derive(Copy, Clone, Debug)]
+pub enum RomSplit {
+    Rom240Ram8 = 0x00,
+    Rom248Ram8 = 0x01,
+    Rom250Ram6 = 0x02,
+    Rom256Ram0 = 0x03,
+}
+
+/// Internal VDD regulator configuration.
+#[derive(Copy, Clone, Debug)]
+pub enum Regulator {
+    /// Internal regulator is disabled (low-voltage I/O).
+    External = 0x00,
+    /// Internal regulator is enabled (5V I/O).
+    Internal = 0x5c,
+}
+
+/// Double height mode (applicable to 3-line and 2-line modes).
+#[derive(Copy, Clone, Debug)]
+pub enum DoubleHeight {
+    /// All lines are of the normal height.
+    Off,
+    /// Two normal lines on top of one double height line.
+    Bottom,
+    /// Double height line between two normal lines.
+    Middle,
+    /// Two double height lines.
+    Both,
+    /// Double height line on top of two normal lines.
+    Top,
+}
+
+/// Fade-out and blinking mode.
+#[derive(Copy, Clone, Debug)]
+pub enum Fade {
+    Disabled = 0x00,
+    /// Brightness is gradually decreased down to the lowest level, then stays there.
+    FadeOut = 0x20,
+    /// Brightness is gradually decreased and then increased again, continuously.
+    Blinking = 0x30,
+}
+
+/// Display clock divide ratio / oscillator frequency recommended by the datasheet.
+const DISPLAY_CLOCK: u8 = 0x70;
+/// SEG pins hardware configuration (alternative configuration, disable left / right remap).
+const SEG_PINS: u8 = 0x10;
+/// Function selection C (internal VSL, GPIO disabled).
+const FUNCTION_C: u8 = 0x00;
+/// Default contrast (brightness).
+const CONTRAST_DEFAULT: u8 = 0x7f;
+/// Phase length recommended by the datasheet.
+const PHASE_LENGTH: u8 = 0xf1;
+/// VCOMH deselect level recommended by the datasheet.
+const VCOMH_LEVEL: u8 = 0x40;
+/// Common bidirectional function (COM31 to COM0), segment bidirectional function (SEG0 to SEG99).
+const COM_SEG_DIRECTION: u8 = 0x02;
+
+/// `Hardware + Delay` wrapper for displays with US2066 controller. Keeps the state of the
+/// function set instruction, which needs to be re-sent every time extended instruction set is
+/// selected.
+pub struct Us2066<HW> {
+    hw: HW,
+    function: u8,
+    reverse: bool,
+}
+
+impl<HW: Hardware + Delay> Us2066<HW> {
+    /// Create a new [Us2066] from the given `Hardware + Delay` implementation.
+    pub fn new(hw: HW) -> Self {
+        let function = (hw.mode() as u8) | FUNCTION_N;
+        Us2066 {
+            hw,
+            function,
+            reverse: false,
+        }
+    }
+
+    /// Unwrap back to the `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
+
+    fn function_set(&self) -> u8 {
+        (Command::FunctionSet as u8) | self.function
+    }
+
+    fn extended_function_set(&self) -> u8 {
+        let mut cmd = (Command::FunctionSet as u8) | (self.function & !FUNCTION_DH) | FUNCTION_RE;
+        if self.reverse {
+            cmd |= FUNCTION_REV;
+        }
+        cmd
+    }
+}
+
+impl<HW: Hardware> Hardware for Us2066<HW> {
+    #[inline(always)]
+    fn rs(&mut self, bit: bool) {
+        self.hw.rs(bit)
+    }
+
+    #[inline(always)]
+    fn enable(&mut self, bit: bool) {
+        self.hw.enable(bit)
+    }
+
+    #[inline(always)]
+    fn data(&mut self, data: u8) {
+        self.hw.data(data)
+    }
+
+    #[inline(always)]
+    fn wait_address(&mut self) {
+        self.hw.wait_address()
+    }
+
+    #[inline(always)]
+    fn mode(&self) -> FunctionMode {
+        self.hw.mode()
+    }
+
+    #[inline(always)]
+    fn can_read(&self) -> bool {
+        self.hw.can_read()
+    }
+
+    #[inline(always)]
+    fn rw(&mut self, bit: bool) {
+        self.hw.rw(bit)
+    }
+
+    #[inline(always)]
+    fn read_data(&mut self) -> u8 {
+        self.hw.read_data()
+    }
+
+    #[inline(always)]
+    fn apply(&mut self) {
+        self.hw.apply()
+    }
+}
+
+impl<HW: Delay> Delay for Us2066<HW> {
+    #[inline(always)]
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.hw.delay_us(delay_usec)
+    }
+}
+
+impl<HW: Hardware + Delay> Display<Us2066<HW>> {
+    /// Initialize US2066 display for the given number of lines (`lines`), character ROM (`rom`)
+    /// and internal regulator configuration (`regulator`).
+    ///
+    /// Unlike HD44780, US2066 interface mode is selected by the hardware pins, so no
+    /// reset-by-instruction sequence is necessary. Display is left turned off, same as with
+    /// [Display::init].
+    #[inline(never)]
+    pub fn init_us2066(&mut self, lines: Lines, rom: Rom, regulator: Regulator) {
+        // Lines bit is the N bit of the function set and NW bit of the extended function set
+        let lines = lines as u8;
+        self.hw.function = (self.hw.hw.mode() as u8) | (lines & FUNCTION_N);
+        self.hw.reverse = false;
+
+        self.extended(|lcd| {
+            lcd.command(ExtendedCommand::FunctionSelectionA as u8);
+            lcd.write_raw(regulator as u8);
+        });
+        self.display(
+            DisplayMode::DisplayOff,
+            DisplayCursor::CursorOff,
+            DisplayBlink::BlinkOff,
+        );
+        self.oled(|lcd| {
+            lcd.command(OledCommand::DisplayClock as u8);
+            lcd.command(DISPLAY_CLOCK);
+        });
+        self.extended(|lcd| {
+            lcd.command((ExtendedCommand::ExtendedFunctionSet as u8) | (lines & 0x1));
+            lcd.command((ExtendedCommand::ComSegDirection as u8) | COM_SEG_DIRECTION);
+            lcd.command(ExtendedCommand::FunctionSelectionB as u8);
+            lcd.write_raw((rom as u8) | (RomSplit::Rom240Ram8 as u8));
+        });
+        self.oled(|lcd| {
+            lcd.command(OledCommand::SegPinsConfiguration as u8);
+            lcd.command(SEG_PINS);
+            lcd.command(OledCommand::FunctionSelectionC as u8);
+            lcd.command(FUNCTION_C);
+            lcd.command(OledCommand::ContrastControl as u8);
+            lcd.command(CONTRAST_DEFAULT);
+            lcd.command(OledCommand::PhaseLength as u8);
+            lcd.command(PHASE_LENGTH);
+            lcd.command(OledCommand::VcomhDeselectLevel as u8);
+            lcd.command(VCOMH_LEVEL);
+        });
+        self.clear();
+        self.entry_mode(EntryModeDirection::EntryRight, EntryModeShift::NoShift);
+    }
+
+    /// Set display brightness (contrast), 0-255.
+    pub fn set_brightness(&mut self, brightness: u8) -> &Self {
+        self.oled(|lcd| {
+            lcd.command(OledCommand::ContrastControl as u8);
+            lcd.command(brightness);
+        });
+        self
+    }
+
+    /// Set fade-out or blinking mode (`mode`) with the given time interval (`interval`, 0-15,
+    /// panics otherwise). Each step of the brightness change takes 8 × (`interval` + 1) frames.
+    pub fn set_fade(&mut self, mode: Fade, interval: u8) -> &Self {
+        assert!(interval <= 15);
+        self.oled(|lcd| {
+            lcd.command(OledCommand::FadeOutBlinking as u8);
+            lcd.command((mode as u8) | interval);
+        });
+        self
+    }
+
+    /// Set double height mode (`mode`).
+    pub fn set_double_height(&mut self, mode: DoubleHeight) -> &Self {
+        let ud = match mode {
+            DoubleHeight::Off => None,
+            DoubleHeight::Bottom => Some(0x00),
+            DoubleHeight::Middle => Some(0x04),
+            DoubleHeight::Both => Some(0x08),
+            DoubleHeight::Top => Some(0x0c),
+        };
+        if let Some(ud) = ud {
+            self.hw.function |= FUNCTION_DH;
+            self.extended(|lcd| {
+                lcd.command((ExtendedCommand::DoubleHeight as u8) | ud);
+            });
+        } else {
+            self.hw.function &= !FUNCTION_DH;
+            let function = self.hw.function_set();
+            self.command(function);
+        }
+        self
+    }
+
+    /// Select character ROM (`rom`) and the split between character ROM and character RAM
+    /// (`split`).
+    pub fn select_rom(&mut self, rom: Rom, split: RomSplit) -> &Self {
+        self.extended(|lcd| {
+            lcd.command(ExtendedCommand::FunctionSelectionB as u8);
+            lcd.write_raw((rom as u8) | (split as u8));
+        });
+        self
+    }
+
+    /// Turn reverse display (dark characters on the lit background) on or off.
+    pub fn set_reverse(&mut self, reverse: bool) -> &Self {
+        self.hw.reverse = reverse;
+        self.extended(|_| {});
+        self
+    }
+
+    /// Run given closure with extended instruction set selected (RE = 1), then switch back to the
+    /// fundamental instruction set.
+    fn extended(&mut self, f: impl FnOnce(&mut Self)) {
+        let extended = self.hw.extended_function_set();
+        self.command(extended);
+        f(self);
+        let function = self.hw.function_set();
+        self.command(function);
+    }
+
+    /// Run given closure with OLED instruction set selected (RE = 1, SD = 1), then switch back to
+    /// the fundamental instruction set.
+    fn oled(&mut self, f: impl FnOnce(&mut Self)) {
+        self.extended(|lcd| {
+            lcd.command(ExtendedCommand::OledCommandSetEnabled as u8);
+            f(lcd);
+            lcd.command(ExtendedCommand::OledCommandSetDisabled as u8);
+        });
+    }
+}
@@ -0,0 +1,263 @@
+//! Recording of the pin activity into the VCD (IEEE 1364 Value Change Dump) files.
+//!
+//! [VcdRecorder] wraps any `Hardware + Delay` implementation and records every call with the
+//! virtual time, which is advanced by [Delay::delay_us] calls. Result could be opened in GTKWave
+//! (or any other waveform viewer) and compared to the logic analyzer capture.
+//!
+//! Signals are `rs`, `rw`, `e` and `data` (vector of D7-D0, or D7-D4 in 4-bit mode), value read
+//! from the display is shown on the `data` bus, too. Calls to [Hardware::apply] are recorded as
+//! `apply` events, requested delays are recorded as `delay_us` integer. If the same signal
+//! changes twice without a delay in between, second change is moved forward by 1ns, so short
+//! pulses are not lost.
+//!
+//! ```rust
+//! use lcd::*;
+//! use lcd::emulator::Hd44780Emulator;
+//! use lcd::vcd::VcdRecorder;
+//!
+//! let mut lcd = Display::new(VcdRecorder::new(Hd44780Emulator::new(FunctionMode::Bit4)));
+//! lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+//! lcd.print("Hello");
+//!
+//! let vcd = lcd.unwrap().to_vcd();
+//! assert!(vcd.contains("$var wire 4 $ data [7:4] $end"));
+//! ```
+use crate::{Delay, FunctionMode, Hardware};
+use std::fmt::Write as _;
+use std::io;
+use std::string::String;
+use std::vec::Vec;
+
+/// Nanoseconds in one microsecond (timescale of the VCD file is 1ns).
+const NS_PER_US: u64 = 1000;
+
+/// Recorded signal.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Signal {
+    Rs,
+    Rw,
+    Enable,
+    Data,
+    Apply,
+    Delay,
+}
+
+/// All recorded signals, in the order of declaration.
+const SIGNALS: [Signal; 6] = [
+    Signal::Rs,
+    Signal::Rw,
+    Signal::Enable,
+    Signal::Data,
+    Signal::Apply,
+    Signal::Delay,
+];
+
+impl Signal {
+    /// Identifier code of the signal in the VCD file.
+    fn id(self) -> char {
+        match self {
+            Signal::Rs => '!',
+            Signal::Rw => '"',
+            Signal::Enable => '#',
+            Signal::Data => '$',
+            Signal::Apply => '%',
+            Signal::Delay => '&',
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Single value change.
+#[derive(Copy, Clone, Debug)]
+struct Change {
+    time: u64,
+    signal: Signal,
+    value: u32,
+}
+
+/// `Hardware + Delay` wrapper recording the pin activity, see the [module](self) documentation.
+pub struct VcdRecorder<HW> {
+    hw: HW,
+    now: u64,
+    changes: Vec<Change>,
+    last: [Option<Change>; SIGNALS.len()],
+}
+
+impl<HW: Hardware + Delay> VcdRecorder<HW> {
+    /// Create a new recorder wrapping the given `Hardware + Delay` implementation (`hw`).
+    pub fn new(hw: HW) -> Self {
+        VcdRecorder {
+            hw,
+            now: 0,
+            changes: Vec::new(),
+            last: [None; SIGNALS.len()],
+        }
+    }
+
+    /// Virtual time since the start of the recording, in nanoseconds.
+    pub fn now_ns(&self) -> u64 {
+        self.now
+    }
+
+    /// Unwrap back to the wrapped `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
+
+    /// Recording in the VCD format.
+    pub fn to_vcd(&self) -> String {
+        let mut vcd = String::new();
+        self.write_header(&mut vcd);
+        let mut time = None;
+        for change in &self.changes {
+            if time != Some(change.time) {
+                time = Some(change.time);
+                writeln!(vcd, "#{}", change.time).unwrap();
+            }
+            self.write_value(&mut vcd, change.signal, Some(change.value));
+        }
+        vcd
+    }
+
+    /// Write recording in the VCD format to the given writer (`out`).
+    pub fn write_vcd<W: io::Write>(&self, mut out: W) -> io::Result<()> {
+        out.write_all(self.to_vcd().as_bytes())
+    }
+
+    fn data_width(&self) -> u32 {
+        match self.hw.mode() {
+            FunctionMode::Bit8 => 8,
+            FunctionMode::Bit4 => 4,
+        }
+    }
+
+    fn write_header(&self, vcd: &mut String) {
+        writeln!(vcd, "$version lcd {} $end", env!("CARGO_PKG_VERSION")).unwrap();
+        writeln!(vcd, "$timescale 1ns $end").unwrap();
+        writeln!(vcd, "$scope module lcd $end").unwrap();
+        for &signal in SIGNALS.iter() {
+            let (kind, width, name) = match signal {
+                Signal::Rs => ("wire", 1, "rs"),
+                Signal::Rw => ("wire", 1, "rw"),
+                Signal::Enable => ("wire", 1, "e"),
+                Signal::Data if self.data_width() == 8 => ("wire", 8, "data [7:0]"),
+                Signal::Data => ("wire", 4, "data [7:4]"),
+                Signal::Apply => ("event", 1, "apply"),
+                Signal::Delay => ("integer", 32, "delay_us"),
+            };
+            writeln!(vcd, "$var {} {} {} {} $end", kind, width, signal.id(), name).unwrap();
+        }
+        writeln!(vcd, "$upscope $end").unwrap();
+        writeln!(vcd, "$enddefinitions $end").unwrap();
+
+        // All pins are unknown until set for the first time
+        writeln!(vcd, "$dumpvars").unwrap();
+        for &signal in SIGNALS.iter() {
+            if signal != Signal::Apply {
+                self.write_value(vcd, signal, None);
+            }
+        }
+        writeln!(vcd, "$end").unwrap();
+    }
+
+    fn write_value(&self, vcd: &mut String, signal: Signal, value: Option<u32>) {
+        match (signal, value) {
+            (Signal::Data, Some(value)) => {
+                let width = self.data_width() as usize;
+                write!(vcd, "b{:0w$b} ", value, w = width).unwrap()
+            }
+            (Signal::Data, None) => {
+                vcd.push('b');
+                (0..self.data_width()).for_each(|_| vcd.push('x'));
+                vcd.push(' ');
+            }
+            (Signal::Delay, Some(value)) => write!(vcd, "b{:b} ", value).unwrap(),
+            (Signal::Delay, None) => vcd.push_str("bx "),
+            (_, Some(value)) => write!(vcd, "{}", value).unwrap(),
+            (_, None) => vcd.push('x'),
+        }
+        vcd.push(signal.id());
+        vcd.push('\n');
+    }
+
+    fn record(&mut self, signal: Signal, value: u32) {
+        let last = self.last[signal.index()];
+        if let Some(last) = last {
+            // Pins are only recorded when changed, but every call is an event
+            let event = signal == Signal::Apply || signal == Signal::Delay;
+            if !event && last.value == value {
+                return;
+            }
+            if last.time == self.now {
+                // Second change of the same signal at the same time would be lost (or merged
+                // into one event), so move forward by one time unit
+                self.now += 1;
+            }
+        }
+        let change = Change {
+            time: self.now,
+            signal,
+            value,
+        };
+        self.last[signal.index()] = Some(change);
+        self.changes.push(change);
+    }
+}
+
+impl<HW: Hardware + Delay> Hardware for VcdRecorder<HW> {
+    fn rs(&mut self, bit: bool) {
+        self.record(Signal::Rs, u32::from(bit));
+        self.hw.rs(bit)
+    }
+
+    fn enable(&mut self, bit: bool) {
+        self.record(Signal::Enable, u32::from(bit));
+        self.hw.enable(bit)
+    }
+
+    fn data(&mut self, data: u8) {
+        let mask = (1 << self.data_width()) - 1;
+        self.record(Signal::Data, u32::from(data) & mask);
+        self.hw.data(data)
+    }
+
+    fn wait_address(&mut self) {
+        self.hw.wait_address()
+    }
+
+    fn mode(&self) -> FunctionMode {
+        self.hw.mode()
+    }
+
+    fn can_read(&self) -> bool {
+        self.hw.can_read()
+    }
+
+    fn rw(&mut self, bit: bool) {
+        self.record(Signal::Rw, u32::from(bit));
+        self.hw.rw(bit)
+    }
+
+    fn read_data(&mut self) -> u8 {
+        let data = self.hw.read_data();
+        let mask = (1 << self.data_width()) - 1;
+        self.record(Signal::Data, u32::from(data) & mask);
+        data
+    }
+
+    fn apply(&mut self) {
+        self.record(Signal::Apply, 1);
+        self.hw.apply()
+    }
+}
+
+impl<HW: Hardware + Delay> Delay for VcdRecorder<HW> {
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.record(Signal::Delay, delay_usec);
+        self.now += u64::from(delay_usec) * NS_PER_US;
+        self.hw.delay_us(delay_usec)
+    }
+}
@@ -0,0 +1,288 @@
+//! Support for the Winstar WS0010 character OLED controller.
+//!
+//! WS0010 is compatible with HD44780 instruction set, but additionally has internal power
+//! control, selectable font tables and a graphic mode, in which display is addressed as 100x16
+//! pixel matrix. Graphic mode is organized as two rows of 100 columns, each column being a byte
+//! of 8 vertical pixels (bit 0 is the top pixel).
+//!
+//! ```rust,no_run
+//! use lcd::*;
+//! use lcd::ws0010::{FontTable, GraphicBuffer, Ws0010};
+//!
+//! # struct HW {}
+//! # impl Hardware for HW {
+//! #   fn rs(&mut self, bit: bool) { }
+//! #   fn enable(&mut self, bit: bool) { }
+//! #   fn data(&mut self, data: u8) { }
+//! # }
+//! # impl Delay for HW {
+//! #   fn delay_us(&mut self, delay_usec: u32) { }
+//! # }
+//! # let hw = HW {};
+//! let mut lcd = Display::new(Ws0010::new(hw));
+//! lcd.init_ws0010(FunctionLine::Line2, FontTable::EnglishJapanese);
+//! lcd.set_graphic_mode(true);
+//!
+//! let mut buffer = GraphicBuffer::new();
+//! buffer.set_pixel(10, 5, true);
+//! lcd.draw(&mut buffer);
+//! ```
+use crate::{
+    Command, Delay, Display, DisplayBlink, DisplayCursor, DisplayMode, EntryModeDirection,
+    EntryModeShift, FunctionLine, FunctionMode, Hardware,
+};
+
+/// Width of the graphic mode display, in pixels.
+pub const GRAPHIC_WIDTH: u8 = 100;
+/// Height of the graphic mode display, in pixels.
+pub const GRAPHIC_HEIGHT: u8 = 16;
+/// Number of the graphic mode rows (each row is 8 pixels high).
+const GRAPHIC_ROWS: u8 = GRAPHIC_HEIGHT / 8;
+
+/// Mode/power instruction (shares the code with cursor or display shift instruction).
+const MODE_POWER: u8 = 0x13;
+/// Graphic mode bit of the mode/power instruction.
+const MODE_GRAPHIC: u8 = 0x08;
+/// Internal power bit of the mode/power instruction.
+const MODE_POWER_ON: u8 = 0x04;
+
+/// Number of zero nibbles which align the 4-bit interface (see WS0010 datasheet).
+const SYNC_NIBBLES: u8 = 5;
+
+/// Set graphic row address (GYA) instruction (shares the code with set CGRAM address).
+const SET_GRAPHIC_ROW: u8 = 0x40;
+/// Set graphic column address (GXA) instruction (shares the code with set DDRAM address).
+const SET_GRAPHIC_COLUMN: u8 = 0x80;
+
+/// Font table selected by the function set instruction.
+#[derive(Copy, Clone, Debug)]
+pub enum FontTable {
+    EnglishJapanese = 0x00,
+    WesternEuropean1 = 0x01,
+    EnglishRussian = 0x02,
+    WesternEuropean2 = 0x03,
+}
+
+/// `Hardware + Delay` wrapper for displays with WS0010 controller. Keeps the state of the
+/// mode/power instruction, so graphic mode and internal power could be changed independently.
+pub struct Ws0010<HW> {
+    hw: HW,
+    graphic: bool,
+    power: bool,
+}
+
+impl<HW: Hardware + Delay> Ws0010<HW> {
+    /// Create a new [Ws0010] from the given `Hardware + Delay` implementation.
+    pub fn new(hw: HW) -> Self {
+        Ws0010 {
+            hw,
+            graphic: false,
+            power: true,
+        }
+    }
+
+    /// Unwrap back to the `Hardware + Delay` implementation.
+    pub fn unwrap(self) -> HW {
+        self.hw
+    }
+
+    fn mode_power(&self) -> u8 {
+        let mut cmd = MODE_POWER;
+        if self.graphic {
+            cmd |= MODE_GRAPHIC;
+        }
+        if self.power {
+            cmd |= MODE_POWER_ON;
+        }
+        cmd
+    }
+}
+
+impl<HW: Hardware> Hardware for Ws0010<HW> {
+    #[inline(always)]
+    fn rs(&mut self, bit: bool) {
+        self.hw.rs(bit)
+    }
+
+    #[inline(always)]
+    fn enable(&mut self, bit: bool) {
+        self.hw.enable(bit)
+    }
+
+    #[inline(always)]
+    fn data(&mut self, data: u8) {
+        self.hw.data(data)
+    }
+
+    #[inline(always)]
+    fn wait_address(&mut self) {
+        self.hw.wait_address()
+    }
+
+    #[inline(always)]
+    fn mode(&self) -> FunctionMode {
+        self.hw.mode()
+    }
+
+    #[inline(always)]
+    fn can_read(&self) -> bool {
+        self.hw.can_read()
+    }
+
+    #[inline(always)]
+    fn rw(&mut self, bit: bool) {
+        self.hw.rw(bit)
+    }
+
+    #[inline(always)]
+    fn read_data(&mut self) -> u8 {
+        self.hw.read_data()
+    }
+
+    #[inline(always)]
+    fn apply(&mut self) {
+        self.hw.apply()
+    }
+}
+
+impl<HW: Delay> Delay for Ws0010<HW> {
+    #[inline(always)]
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.hw.delay_us(delay_usec)
+    }
+}
+
+impl<HW: Hardware + Delay> Display<Ws0010<HW>> {
+    /// Initialize WS0010 display in character mode with internal power on, for the given number
+    /// of lines (`line`) and font table (`font`). Display is left turned off, same as with
+    /// [Display::init].
+    ///
+    /// Controller could be left in 4-bit mode or in the middle of the 4-bit transfer (after MCU
+    /// reset, for example), so in 4-bit mode the nibble phase is first re-aligned by sending zero
+    /// nibbles, then the interface is re-synchronized with the reset-by-instruction sequence.
+    #[inline(never)]
+    pub fn init_ws0010(&mut self, line: FunctionLine, font: FontTable) {
+        let mode = self.hw.mode();
+        if mode == FunctionMode::Bit4 {
+            self.hw.rs(false);
+            self.hw.apply();
+            self.hw.wait_address();
+            for _ in 0..SYNC_NIBBLES {
+                self.send_data(0);
+                self.hw.delay_us(self.timing.command);
+            }
+        }
+        self.reset(mode);
+
+        self.command((Command::FunctionSet as u8) | (mode as u8) | (line as u8) | (font as u8));
+        self.display(
+            DisplayMode::DisplayOff,
+            DisplayCursor::CursorOff,
+            DisplayBlink::BlinkOff,
+        );
+
+        self.hw.graphic = false;
+        self.hw.power = true;
+        let mode_power = self.hw.mode_power();
+        self.command(mode_power);
+
+        self.clear();
+        self.entry_mode(EntryModeDirection::EntryRight, EntryModeShift::NoShift);
+        self.home();
+    }
+
+    /// Turn internal power (DC/DC converter and display) on or off. Display RAM content is
+    /// preserved while the power is off.
+    pub fn set_power(&mut self, power: bool) -> &Self {
+        self.hw.power = power;
+        let mode_power = self.hw.mode_power();
+        self.command(mode_power)
+    }
+
+    /// Switch between graphic mode (`true`) and character mode (`false`).
+    pub fn set_graphic_mode(&mut self, graphic: bool) -> &Self {
+        self.hw.graphic = graphic;
+        let mode_power = self.hw.mode_power();
+        self.command(mode_power)
+    }
+
+    /// Set graphic mode address to the given column (`x`, 0-99) and row (`row`, 0-1). Panics if
+    /// out of range.
+    pub fn graphic_position(&mut self, x: u8, row: u8) -> &Self {
+        assert!(x < GRAPHIC_WIDTH && row < GRAPHIC_ROWS);
+        self.command(SET_GRAPHIC_COLUMN | x);
+        self.command(SET_GRAPHIC_ROW | row)
+    }
+
+    /// Write column of 8 vertical pixels (`pixels`, bit 0 is the top pixel) at the current graphic
+    /// address. Column address is incremented after the write.
+    pub fn write_column(&mut self, pixels: u8) -> &Self {
+        self.write_raw(pixels)
+    }
+
+    /// Draw dirty part of the given graphic buffer (`buffer`) and mark buffer as clean.
+    pub fn draw(&mut self, buffer: &mut GraphicBuffer) -> &Self {
+        for row in 0..GRAPHIC_ROWS {
+            if let Some((start, end)) = buffer.dirty[row as usize].take() {
+                self.graphic_position(start, row);
+                for x in start..=end {
+                    self.write_column(buffer.columns[row as usize][x as usize]);
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Buffer for the whole graphic mode display, tracking which columns need to be re-drawn.
+pub struct GraphicBuffer {
+    columns: [[u8; GRAPHIC_WIDTH as usize]; GRAPHIC_ROWS as usize],
+    dirty: [Option<(u8, u8)>; GRAPHIC_ROWS as usize],
+}
+
+impl GraphicBuffer {
+    /// Create a new buffer with all pixels off. Whole buffer is considered dirty.
+    pub fn new() -> Self {
+        GraphicBuffer {
+            columns: [[0; GRAPHIC_WIDTH as usize]; GRAPHIC_ROWS as usize],
+            dirty: [Some((0, GRAPHIC_WIDTH - 1)); GRAPHIC_ROWS as usize],
+        }
+    }
+
+    /// Turn all pixels off.
+    pub fn clear(&mut self) {
+        *self = GraphicBuffer::new();
+    }
+
+    /// Set pixel at the given coordinates (`x`, 0-99 and `y`, 0-15) on or off (`on`). Panics if out
+    /// of range.
+    pub fn set_pixel(&mut self, x: u8, y: u8, on: bool) {
+        assert!(x < GRAPHIC_WIDTH && y < GRAPHIC_HEIGHT);
+        let row = (y / 8) as usize;
+        let column = &mut self.columns[row][x as usize];
+        let value = if on {
+            *column | (1 << (y % 8))
+        } else {
+            *column & !(1 << (y % 8))
+        };
+        if value != *column {
+            *column = value;
+            self.dirty[row] = match self.dirty[row] {
+                Some((start, end)) => Some((start.min(x), end.max(x))),
+                None => Some((x, x)),
+            };
+        }
+    }
+
+    /// Get pixel at the given coordinates (`x`, 0-99 and `y`, 0-15). Panics if out of range.
+    pub fn pixel(&self, x: u8, y: u8) -> bool {
+        assert!(x < GRAPHIC_WIDTH && y < GRAPHIC_HEIGHT);
+        self.columns[(y / 8) as usize][x as usize] & (1 << (y % 8)) != 0
+    }
+}
+
+impl Default for GraphicBuffer {
+    fn default() -> Self {
+        GraphicBuffer::new()
+    }
+}
@@ -0,0 +1,117 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::bargraph::BarGraph;
+use lcd::testing;
+use lcd::*;
+
+#[test]
+fn horizontal() {
+    let mut lcd = testing::emulator_display();
+    let mut bar = BarGraph::horizontal(Geometry::new(4, 4), 0, 1, 4, 2);
+    bar.set_glyph_location(3);
+    assert_eq!(bar.glyph_locations(), 3..7);
+    bar.set_range(0, 20);
+    bar.set_value(7);
+    bar.draw(&mut lcd);
+    assert_eq!(bar.pixels(), 7);
+    let emulator = lcd.unwrap();
+    assert_eq!(
+        emulator.snapshot(4, 4),
+        "|    |\n\
+         |█④  |\n\
+         |█④  |\n\
+         |    |\n"
+    );
+    assert_eq!(emulator.custom_glyph(4), [0x18; 8]);
+    assert_eq!(emulator.custom_glyph(6), [0x1e; 8]);
+}
+
+#[test]
+fn vertical() {
+    let mut lcd = testing::emulator_display();
+    let mut bar = BarGraph::vertical(Geometry::new(4, 4), 1, 0, 2, 4);
+    bar.set_glyph_location(1);
+    assert_eq!(bar.glyph_locations(), 1..8);
+    bar.set_range(0, 32);
+    bar.set_value(11);
+    bar.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(
+        emulator.snapshot(4, 4),
+        "|    |\n\
+         |    |\n\
+         | ③③ |\n\
+         | ██ |\n"
+    );
+    assert_eq!(emulator.custom_glyph(3), [0, 0, 0, 0, 0, 0x1f, 0x1f, 0x1f]);
+}
+
+#[test]
+fn bipolar() {
+    let mut lcd = testing::emulator_display();
+    let mut bar = BarGraph::horizontal(Geometry::new(4, 4), 0, 0, 4, 1);
+    bar.set_range(-10, 10);
+    bar.set_bipolar(true);
+    bar.set_value(-3);
+    bar.draw(&mut lcd);
+    assert_eq!(bar.pixels(), -3);
+    lcd = {
+        let emulator = lcd.unwrap();
+        assert_eq!(emulator.snapshot(4, 1), "| ②  |\n");
+        assert_eq!(emulator.custom_glyph(2), [0x07; 8]);
+        Display::new(emulator)
+    };
+
+    bar.set_value(6);
+    bar.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 1), "|  █⓪|\n");
+    assert_eq!(emulator.custom_glyph(0), [0x10; 8]);
+}
+
+#[test]
+#[should_panic]
+fn bipolar_range_without_zero() {
+    let mut bar = BarGraph::horizontal(Geometry::new(4, 4), 0, 0, 4, 1);
+    bar.set_range(-10, 10);
+    bar.set_bipolar(true);
+    bar.set_range(-10, 0);
+}
+
+#[test]
+#[should_panic]
+fn vertical_glyph_location() {
+    let mut bar = BarGraph::vertical(Geometry::new(4, 4), 0, 0, 1, 4);
+    bar.set_glyph_location(2);
+}
+
+#[test]
+#[should_panic]
+fn outside_display() {
+    BarGraph::horizontal(Geometry::new(4, 4), 2, 0, 255, 1);
+}
+
+#[test]
+fn peak_hold() {
+    let mut lcd = testing::emulator_display();
+    let mut bar = BarGraph::horizontal(Geometry::new(4, 4), 0, 0, 4, 1);
+    bar.set_range(0, 20);
+    bar.set_peak_hold(2);
+    bar.set_value(17);
+    bar.draw(&mut lcd);
+    bar.set_value(3);
+    bar.draw(&mut lcd);
+    bar.set_value(3);
+    bar.draw(&mut lcd);
+    lcd = {
+        let emulator = lcd.unwrap();
+        assert_eq!(emulator.snapshot(4, 1), "|②  ①|\n");
+        Display::new(emulator)
+    };
+
+    bar.set_value(3);
+    bar.draw(&mut lcd);
+    assert_eq!(lcd.unwrap().snapshot(4, 1), "|②   |\n");
+}
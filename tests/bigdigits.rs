@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::bigdigits::{BigDigits, Font};
+use lcd::testing;
+use lcd::*;
+
+#[test]
+fn two_rows() {
+    let mut lcd = testing::emulator_display();
+    let mut digits = BigDigits::new(Geometry::new(20, 4), &Font::TWO_ROWS);
+    assert_eq!(digits.width("-1.5"), 12);
+    assert_eq!(digits.print(&mut lcd, 0, 1, "-1.5"), 12);
+    let emulator = lcd.unwrap();
+    assert_eq!(
+        emulator.snapshot(20, 4),
+        "|                    |\n\
+         |④④ ①②    ⑦⑥⑥        |\n\
+         |   ④⑦④ ④ ④④⑤        |\n\
+         |                    |\n"
+    );
+    assert_eq!(
+        emulator.custom_glyph(6),
+        [0x1f, 0x1f, 0x1f, 0, 0, 0, 0x1f, 0x1f]
+    );
+}
+
+#[test]
+fn four_rows() {
+    let mut lcd = testing::emulator_display();
+    let mut digits = BigDigits::new(Geometry::new(20, 4), &Font::FOUR_ROWS);
+    digits.set_spacing(0);
+    digits.print(&mut lcd, 1, 0, "0:7");
+    assert_eq!(
+        lcd.unwrap().snapshot(20, 4),
+        "| ③⓪④ ⓪⓪②            |\n\
+         | ② ②⑦  ②            |\n\
+         | ② ②⑦  ②            |\n\
+         | ⑤①⑥   ②            |\n"
+    );
+}
+
+#[test]
+fn three_rows() {
+    let mut lcd = testing::emulator_display();
+    let mut digits = BigDigits::new(Geometry::new(20, 4), &Font::THREE_ROWS);
+    assert_eq!(digits.width("2:9"), 9);
+    digits.print(&mut lcd, 0, 1, "2:9");
+    assert_eq!(
+        lcd.unwrap().snapshot(20, 4),
+        "|                    |\n\
+         |⓪⓪④ ⑦ ③⓪④           |\n\
+         |③⓪⑥   ⑤⓪②           |\n\
+         |②①① ⑦ ①①⑥           |\n"
+    );
+}
+
+#[test]
+fn overwrite_and_clip() {
+    let mut lcd = testing::emulator_display();
+    let mut digits = BigDigits::new(Geometry::new(20, 4), &Font::TWO_ROWS);
+    digits.print(&mut lcd, 14, 0, "88");
+    // Space blanks the previous digit, last digit is clipped
+    assert_eq!(digits.print(&mut lcd, 14, 0, " 4"), 21);
+    assert_eq!(
+        lcd.unwrap().snapshot(20, 4),
+        "|                  ③④|\n\
+         |                    |\n\
+         |                    |\n\
+         |                    |\n"
+    );
+}
@@ -2,7 +2,7 @@
 extern crate pretty_assertions;
 extern crate lcd;
 
-mod util;
+use lcd::testing as util;
 
 use lcd::{FunctionDots, FunctionLine, FunctionMode};
 
@@ -217,7 +217,7 @@ fn write_4bit() {
 #[test]
 fn write_4bit_delay() {
     let input = vec![0, 0];
-    let vec = crate::util::test_ignored_delay(FunctionMode::Bit4, Some(input), |lcd| {
+    let vec = util::test_ignored_delay(FunctionMode::Bit4, Some(input), |lcd| {
         lcd.write(b'a');
     });
 
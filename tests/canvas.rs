@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::canvas::Canvas;
+use lcd::testing::{self, BufferHardware};
+use lcd::*;
+
+#[test]
+fn draw() {
+    let mut lcd = testing::emulator_display();
+    let mut canvas = Canvas::new(2, 2);
+    assert_eq!((canvas.width(), canvas.height()), (10, 16));
+    canvas.place(&mut lcd, 1, 0);
+    canvas.rect(0, 0, 10, 16, true);
+    canvas.line(1, 1, 8, 14, true);
+    // Outside of the canvas
+    canvas.set_pixel(10, 0, true);
+    canvas.flush(&mut lcd);
+    assert!(canvas.pixel(4, 7));
+    assert!(!canvas.pixel(10, 0));
+
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 2), "| ⓪① |\n| ②③ |\n");
+    assert_eq!(
+        emulator.custom_glyph(0),
+        [0x1f, 0x18, 0x14, 0x14, 0x12, 0x12, 0x11, 0x11]
+    );
+    assert_eq!(
+        emulator.custom_glyph(3),
+        [0x11, 0x11, 0x09, 0x09, 0x05, 0x05, 0x03, 0x1f]
+    );
+}
+
+#[test]
+fn clip() {
+    let mut canvas = Canvas::new(2, 2);
+    canvas.line(i32::MIN, 0, i32::MAX, 0, true);
+    canvas.line(-5, -5, 20, 20, true);
+    canvas.fill_rect(8, 14, i32::MAX, i32::MAX, true);
+    assert!((0..10).all(|x| canvas.pixel(x, 0)));
+    assert!((0..10).all(|x| canvas.pixel(x, x)));
+    assert!(!canvas.pixel(3, 4));
+    assert!(canvas.pixel(9, 15) && canvas.pixel(8, 14) && !canvas.pixel(7, 14));
+}
+
+#[test]
+#[should_panic]
+fn too_many_characters() {
+    Canvas::new(16, 16);
+}
+
+#[test]
+fn blit() {
+    let mut lcd = testing::emulator_display();
+    let mut canvas = Canvas::new(2, 1);
+    canvas.set_glyph_location(6);
+    assert_eq!(canvas.glyph_locations(), 6..8);
+    // 7x2 bitmap, crossing the character boundary
+    canvas.blit(2, 3, 7, &[0b1010_1010, 0b0111_1100]);
+    canvas.flush(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.custom_glyph(6), [0, 0, 0, 0x05, 0x03, 0, 0, 0]);
+    assert_eq!(emulator.custom_glyph(7), [0, 0, 0, 0x0a, 0x1c, 0, 0, 0]);
+}
+
+#[test]
+fn flush_changed() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    let mut canvas = Canvas::new(4, 2);
+    canvas.flush(&mut lcd);
+    canvas.fill_rect(5, 8, 5, 8, true);
+    // Already on
+    canvas.set_pixel(5, 8, true);
+    canvas.flush(&mut lcd);
+    let uploads: Vec<_> = lcd
+        .unwrap()
+        .instructions()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            Instruction::SetCGRamAddr(address) => Some(address >> 3),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(uploads, [0, 1, 2, 3, 4, 5, 6, 7, 5]);
+}
+
+#[test]
+fn draw_target() {
+    use embedded_graphics_core::pixelcolor::BinaryColor;
+    use embedded_graphics_core::prelude::*;
+
+    let mut canvas = Canvas::new(4, 2);
+    assert_eq!(canvas.size(), Size::new(20, 16));
+    canvas
+        .draw_iter([
+            Pixel(Point::new(0, 0), BinaryColor::On),
+            Pixel(Point::new(19, 15), BinaryColor::On),
+            Pixel(Point::new(20, 0), BinaryColor::On),
+        ])
+        .unwrap();
+    assert!(canvas.pixel(0, 0) && canvas.pixel(19, 15));
+}
@@ -0,0 +1,247 @@
+extern crate lcd;
+
+use lcd::checker::{Constraints, Diagnostic, TimingChecker, Violation};
+use lcd::emulator::Hd44780Emulator;
+use lcd::*;
+
+fn check(
+    mode: FunctionMode,
+    readable: bool,
+    constraints: Constraints,
+    timing: Timing,
+    ops: impl Fn(&mut Display<TimingChecker<Hd44780Emulator>>),
+) -> Vec<Diagnostic> {
+    let mut emulator = Hd44780Emulator::new(mode);
+    emulator.set_readable(readable);
+    let mut lcd = Display::with_timing(TimingChecker::new(emulator, constraints), timing);
+    ops(&mut lcd);
+    lcd.unwrap().take_diagnostics()
+}
+
+fn violations(diagnostics: &[Diagnostic]) -> Vec<Violation> {
+    diagnostics.iter().map(|d| d.violation).collect()
+}
+
+fn config() -> InitConfig {
+    InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+}
+
+#[test]
+fn default_timing() {
+    for &mode in &[FunctionMode::Bit4, FunctionMode::Bit8] {
+        for &readable in &[false, true] {
+            let diagnostics = check(
+                mode,
+                readable,
+                Constraints::datasheet(),
+                Timing::default(),
+                |lcd| {
+                    lcd.init_with(config());
+                    lcd.print("Hello");
+                    lcd.home();
+                    lcd.upload_character(1, [0; 8]);
+                },
+            );
+            assert_eq!(diagnostics, vec![]);
+        }
+    }
+}
+
+#[test]
+fn power_on_wait() {
+    let diagnostics = check(
+        FunctionMode::Bit8,
+        false,
+        Constraints::datasheet(),
+        Timing::default(),
+        |lcd| {
+            lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+        },
+    );
+    assert_eq!(violations(&diagnostics), vec![Violation::PowerOnWait]);
+    assert_eq!(diagnostics[0].actual, 40);
+    assert_eq!(diagnostics[0].required, 40_000_000);
+}
+
+#[test]
+fn execution_time() {
+    let timing = Timing {
+        command: 20,
+        ..Timing::default()
+    };
+    let diagnostics = check(
+        FunctionMode::Bit8,
+        false,
+        Constraints::datasheet(),
+        timing,
+        |lcd| {
+            lcd.init_with(config());
+            lcd.print("A");
+        },
+    );
+    assert_eq!(
+        violations(&diagnostics),
+        vec![
+            // Function set after the last function set of the reset sequence
+            Violation::ExecutionTime {
+                previous: 0x3c,
+                data: false
+            },
+            // Display control after function set
+            Violation::ExecutionTime {
+                previous: 0x38,
+                data: false
+            },
+            // Clear after display control
+            Violation::ExecutionTime {
+                previous: 0x08,
+                data: false
+            },
+            // Data write after entry mode set
+            Violation::ExecutionTime {
+                previous: 0x06,
+                data: false
+            },
+        ]
+    );
+    assert_eq!(diagnostics[0].actual, 20_040);
+    assert_eq!(diagnostics[0].required, 37_000);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "at 54673080ns: previous instruction 0x3c did not finish executing \
+         (20040ns, required 37000ns)"
+    );
+}
+
+#[test]
+fn slow_oscillator() {
+    // Default timing is not enough for the slowest oscillator allowed by the datasheet...
+    let diagnostics = check(
+        FunctionMode::Bit4,
+        false,
+        Constraints::for_oscillator(190),
+        Timing::default(),
+        |lcd| {
+            lcd.init_with(config());
+        },
+    );
+    assert!(!diagnostics.is_empty());
+
+    // ...but worst case timing is
+    let diagnostics = check(
+        FunctionMode::Bit4,
+        false,
+        Constraints::for_oscillator(190),
+        Timing::worst_case(),
+        |lcd| {
+            lcd.init_with(config());
+            lcd.print("Hello");
+        },
+    );
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn enable_pulse() {
+    let timing = Timing {
+        enable_pulse: 0,
+        ..Timing::default()
+    };
+    let diagnostics = check(
+        FunctionMode::Bit8,
+        false,
+        Constraints::datasheet(),
+        timing,
+        |lcd| {
+            lcd.init_with(config().power_on_wait(false).warm(true));
+        },
+    );
+    assert!(violations(&diagnostics).contains(&Violation::EnablePulseWidth));
+}
+
+#[test]
+fn address_setup() {
+    let mut checker = TimingChecker::new(
+        Hd44780Emulator::new(FunctionMode::Bit8),
+        Constraints::datasheet(),
+    );
+    checker.assume_initialized();
+    checker.rs(true);
+    checker.data(b'A');
+    checker.enable(true);
+    checker.delay_us(1);
+    checker.enable(false);
+    assert_eq!(
+        violations(checker.diagnostics()),
+        vec![Violation::AddressSetup]
+    );
+}
+
+#[test]
+fn busy() {
+    let mut emulator = Hd44780Emulator::new(FunctionMode::Bit8);
+    emulator.set_readable(true);
+    let mut checker = TimingChecker::new(emulator, Constraints::datasheet());
+    checker.assume_initialized();
+
+    // Clear display, then read busy flag once and ignore it
+    checker.data(0x01);
+    checker.enable(true);
+    checker.delay_us(1);
+    checker.enable(false);
+    checker.rw(true);
+    checker.wait_address();
+    checker.enable(true);
+    checker.delay_us(1);
+    assert_eq!(checker.read_data() & 0x80, 0x80);
+    checker.enable(false);
+    checker.rw(false);
+    checker.wait_address();
+    checker.delay_us(2000);
+    checker.data(0x02);
+    checker.enable(true);
+    checker.delay_us(1);
+    checker.enable(false);
+    assert_eq!(violations(checker.diagnostics()), vec![Violation::Busy]);
+}
+
+#[test]
+fn address_update_longer_than_command() {
+    let mut emulator = Hd44780Emulator::new(FunctionMode::Bit8);
+    emulator.set_readable(true);
+    let constraints = Constraints {
+        command: 10_000,
+        address_update: 100_000,
+        ..Constraints::datasheet()
+    };
+    let mut checker = TimingChecker::new(emulator, constraints);
+    checker.assume_initialized();
+
+    // Read data, then send a command right away
+    checker.rs(true);
+    checker.rw(true);
+    checker.wait_address();
+    checker.enable(true);
+    checker.delay_us(1);
+    checker.read_data();
+    checker.enable(false);
+    checker.rs(false);
+    checker.rw(false);
+    checker.wait_address();
+    checker.data(0x0c);
+    checker.enable(true);
+    checker.delay_us(1);
+    checker.enable(false);
+    let diagnostics = checker.take_diagnostics();
+    let execution = diagnostics
+        .iter()
+        .find(|d| {
+            d.violation
+                == Violation::ExecutionTime {
+                    previous: 0,
+                    data: false,
+                }
+        })
+        .unwrap();
+    assert_eq!((execution.actual, execution.required), (0, 10_000));
+}
@@ -0,0 +1,89 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use core::fmt::Write;
+use lcd::console::{Console, Overflow};
+use lcd::testing::{self, BufferHardware};
+use lcd::*;
+
+#[test]
+fn scrolling() {
+    let mut lcd = testing::emulator_display();
+    let mut console = Console::new(Geometry::new(8, 4));
+    for index in 1..=3 {
+        writeln!(console, "line {}", index).unwrap();
+    }
+    console.draw(&mut lcd);
+    writeln!(console, "\nline 5 is long").unwrap();
+    console.draw(&mut lcd);
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|line 3  |\n\
+         |        |\n\
+         |line 5 i|\n\
+         |s long  |\n"
+    );
+}
+
+#[test]
+fn truncate_and_timestamp() {
+    let mut lcd = testing::emulator_display();
+    let mut console = Console::new(Geometry::new(12, 2));
+    console.set_overflow(Overflow::Truncate);
+    console.set_timestamp(true);
+    console.set_time(61);
+    writeln!(console, "first line").unwrap();
+    console.set_time(3599);
+    write!(console, "second").unwrap();
+    console.draw(&mut lcd);
+    assert_eq!(
+        lcd.unwrap().snapshot(12, 2),
+        "|01:01 first |\n\
+         |59:59 second|\n"
+    );
+}
+
+#[test]
+fn repaint_only_changes() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    let mut console = Console::new(Geometry::new(8, 2));
+    writeln!(console, "abc").unwrap();
+    console.draw(&mut lcd);
+    write!(console, "abd").unwrap();
+    console.draw(&mut lcd);
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[instructions.len() - 6..],
+        &[
+            // Top row: "abc" scrolled up over blank characters
+            Instruction::SetDDRamAddr(0x00),
+            Instruction::Write(b'a'),
+            Instruction::Write(b'b'),
+            Instruction::Write(b'c'),
+            // Bottom row: only the last character changed
+            Instruction::SetDDRamAddr(0x42),
+            Instruction::Write(b'd'),
+        ]
+    );
+}
+
+#[test]
+fn pause() {
+    let mut lcd = testing::emulator_display();
+    let mut console = Console::new(Geometry::new(8, 2));
+    writeln!(console, "one").unwrap();
+    console.draw(&mut lcd);
+    console.pause();
+    writeln!(console, "two").unwrap();
+    console.draw(&mut lcd);
+    assert_eq!(console.row(1), b"two     ");
+    lcd = {
+        let emulator = lcd.unwrap();
+        assert_eq!(emulator.snapshot(8, 2), "|        |\n|one     |\n");
+        Display::new(emulator)
+    };
+    console.resume();
+    console.draw(&mut lcd);
+    assert_eq!(lcd.unwrap().snapshot(8, 2), "|one     |\n|two     |\n");
+}
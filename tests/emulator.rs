@@ -0,0 +1,183 @@
+extern crate lcd;
+
+use lcd::emulator::Hd44780Emulator;
+use lcd::*;
+
+fn display(mode: FunctionMode, readable: bool) -> Display<Hd44780Emulator> {
+    let mut emulator = Hd44780Emulator::new(mode);
+    emulator.set_readable(readable);
+    let mut lcd = Display::new(emulator);
+    lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd
+}
+
+fn row(emulator: &Hd44780Emulator, row: u8, columns: u8) -> String {
+    (0..columns)
+        .map(|col| emulator.character_at(col, row, columns) as char)
+        .collect()
+}
+
+#[test]
+fn init() {
+    for &mode in &[FunctionMode::Bit4, FunctionMode::Bit8] {
+        for &readable in &[false, true] {
+            let emulator = display(mode, readable).unwrap();
+            assert!(!emulator.is_eight_bit() == matches!(mode, FunctionMode::Bit4));
+            assert!(emulator.is_two_lines());
+            assert!(!emulator.is_large_font());
+            assert!(!emulator.is_display_on());
+            assert!(emulator.is_increment());
+            assert!(!emulator.is_shift());
+            assert_eq!(emulator.address(), 0);
+            assert_eq!(row(&emulator, 0, 16), "                ");
+        }
+    }
+}
+
+#[test]
+fn print() {
+    for &mode in &[FunctionMode::Bit4, FunctionMode::Bit8] {
+        for &readable in &[false, true] {
+            let mut lcd = display(mode, readable);
+            lcd.display(
+                DisplayMode::DisplayOn,
+                DisplayCursor::CursorOn,
+                DisplayBlink::BlinkOn,
+            );
+            lcd.print("Hello");
+            lcd.position(2, 1);
+            lcd.print("world");
+            lcd.position(0, 2);
+            lcd.print("third");
+            lcd.position(15, 3);
+            lcd.print("4");
+
+            let emulator = lcd.unwrap();
+            assert!(emulator.is_display_on());
+            assert!(emulator.is_cursor_on());
+            assert!(emulator.is_blink_on());
+            assert_eq!(row(&emulator, 0, 20), "Hello               ");
+            assert_eq!(row(&emulator, 1, 20), "  world             ");
+            assert_eq!(row(&emulator, 2, 20), "third               ");
+            assert_eq!(row(&emulator, 3, 20), "               4    ");
+            assert_eq!(emulator.cursor_position(20, 4), Some((16, 3)));
+        }
+    }
+}
+
+#[test]
+fn wrap_and_entry_mode() {
+    let mut lcd = display(FunctionMode::Bit4, true);
+    lcd.position(38, 0);
+    lcd.print("abc");
+    lcd.position(39, 1);
+    lcd.print("de");
+    lcd.entry_mode(EntryModeDirection::EntryLeft, EntryModeShift::NoShift);
+    lcd.position(5, 0);
+    lcd.print("xyz");
+
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.ddram_at(0x26), b'a');
+    assert_eq!(emulator.ddram_at(0x27), b'b');
+    assert_eq!(emulator.ddram_at(0x40), b'c');
+    assert_eq!(emulator.ddram_at(0x67), b'd');
+    assert_eq!(emulator.ddram_at(0x00), b'e');
+    assert_eq!(&emulator.ddram()[3..6], b"zyx");
+    assert_eq!(emulator.address(), 0x02);
+}
+
+#[test]
+fn address_outside_of_ddram() {
+    let mut lcd = display(FunctionMode::Bit4, true);
+    // 0x54 + 0x2b = 0x7f, past the end of the second line
+    lcd.position(0x2b, 3);
+    lcd.write(b'a');
+    assert_eq!(lcd.unwrap().address(), 0x00);
+}
+
+#[test]
+fn shift() {
+    let mut lcd = display(FunctionMode::Bit8, false);
+    lcd.print("0123456789");
+    lcd.scroll(Direction::Left);
+    lcd.scroll(Direction::Left);
+    assert_eq!(lcd.unwrap().display_shift(), 2);
+
+    let mut lcd = display(FunctionMode::Bit8, false);
+    lcd.print("0123456789");
+    lcd.scroll(Direction::Right);
+    lcd.cursor(Direction::Left);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.display_shift(), 39);
+    assert_eq!(row(&emulator, 0, 8), " 0123456");
+    assert_eq!(emulator.address(), 9);
+    assert_eq!(emulator.cursor_position(8, 2), None);
+
+    // Display shift on write
+    let mut lcd = display(FunctionMode::Bit4, false);
+    lcd.position(16, 0);
+    lcd.entry_mode(EntryModeDirection::EntryRight, EntryModeShift::Shift);
+    lcd.print("ab");
+    lcd.home();
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.display_shift(), 0);
+    assert_eq!(emulator.address(), 0);
+}
+
+#[test]
+fn custom_character() {
+    let glyph = [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0xff];
+    let mut lcd = display(FunctionMode::Bit4, true);
+    lcd.upload_character(3, glyph);
+    lcd.position(0, 0);
+    lcd.write(3);
+
+    let emulator = lcd.unwrap();
+    assert_eq!(
+        emulator.custom_glyph(3),
+        [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x1f]
+    );
+    assert_eq!(emulator.ddram_at(0), 3);
+}
+
+#[test]
+fn busy_and_read() {
+    let mut lcd = display(FunctionMode::Bit4, true);
+    lcd.print("AB");
+    lcd.position(0, 0);
+    let mut emulator = lcd.unwrap();
+
+    // Clear takes 1.52ms
+    emulator.delay_us(100);
+    let mut display = Display::new(emulator);
+    display.clear();
+    let emulator = display.unwrap();
+    assert!(emulator.now_us() >= 1520);
+
+    // Data read: write then read back through the pins
+    let mut lcd = Display::new(emulator);
+    lcd.print("Q");
+    lcd.position(0, 0);
+    let mut emulator = lcd.unwrap();
+    emulator.rs(true);
+    emulator.rw(true);
+    emulator.enable(true);
+    let high = emulator.read_data();
+    emulator.enable(false);
+    emulator.enable(true);
+    let low = emulator.read_data();
+    emulator.enable(false);
+    assert_eq!((high << 4) | low, b'Q');
+    assert_eq!(emulator.address(), 1);
+
+    // Busy flag and address
+    emulator.rs(false);
+    emulator.enable(true);
+    let high = emulator.read_data();
+    emulator.enable(false);
+    emulator.enable(true);
+    let low = emulator.read_data();
+    emulator.enable(false);
+    assert!(emulator.is_busy());
+    assert_eq!((high << 4) | low, 0x81);
+}
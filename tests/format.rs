@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::testing;
+use lcd::*;
+
+#[test]
+fn numbers() {
+    let mut lcd = testing::emulator_display();
+    lcd.print_u32(4_294_967_295);
+    lcd.print(" ");
+    lcd.print_i32(-2_147_483_648);
+    lcd.position(0, 1);
+    lcd.print_i32(0);
+    lcd.print(" ");
+    lcd.print_hex(0xbeef, 0);
+    lcd.print(" ");
+    lcd.print_hex(0x1f, 4);
+    lcd.print(" ");
+    lcd.print_hex(0, 0);
+    assert_eq!(
+        lcd.unwrap().snapshot(22, 2),
+        "|4294967295 -2147483648|\n|0 BEEF 001F 0         |\n"
+    );
+}
+
+#[test]
+fn decimal() {
+    let mut lcd = testing::emulator_display();
+    lcd.print_decimal(-1234, 2);
+    lcd.print(" ");
+    lcd.print_decimal(5, 3);
+    lcd.print(" ");
+    lcd.print_decimal(-5, 1);
+    lcd.print(" ");
+    lcd.print_decimal(i32::MIN, 9);
+    assert_eq!(
+        lcd.unwrap().snapshot(32, 1),
+        "|-12.34 0.005 -0.5 -2.147483648  |\n"
+    );
+}
+
+#[test]
+fn aligned() {
+    let mut lcd = testing::emulator_display();
+    lcd.print("[");
+    lcd.print_right("abc", 6);
+    lcd.print("][");
+    lcd.print_centered("abc", 6);
+    lcd.print("][");
+    lcd.print_right("abcdef", 4);
+    lcd.print("]");
+    lcd.position(0, 1);
+    lcd.print("[");
+    lcd.print_number(215, 1, 6, Align::Right);
+    lcd.print("][");
+    lcd.print_number(-7, 0, 5, Align::Center);
+    lcd.print("][");
+    lcd.print_number(42, 0, 4, Align::Left);
+    lcd.print("]");
+    assert_eq!(
+        lcd.unwrap().snapshot(20, 2),
+        "|[   abc][ abc  ][abc|\n|[  21.5][ -7  ][42  |\n"
+    );
+}
+
+#[test]
+fn ufmt() {
+    use ufmt_write::uWrite;
+
+    let mut lcd = testing::emulator_display();
+    lcd.write_str("Temp: ").unwrap();
+    lcd.print_decimal(215, 1);
+    assert_eq!(lcd.unwrap().snapshot(10, 1), "|Temp: 21.5|\n");
+}
+
+#[test]
+fn number_overflow() {
+    let mut lcd = testing::emulator_display();
+    lcd.print("[");
+    lcd.print_number(-1234, 1, 5, Align::Right);
+    lcd.print("][");
+    lcd.print_number(-1234, 1, 6, Align::Right);
+    lcd.print("]");
+    assert_eq!(lcd.unwrap().snapshot(15, 1), "|[#####][-123.4]|\n");
+}
@@ -0,0 +1,136 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::testing as util;
+
+use lcd::ks0073::{Controller, DoubleHeight, Ks0073, Lines};
+use lcd::*;
+
+fn test(
+    controller: Controller,
+    ops: impl Fn(&mut Display<Ks0073<util::BufferHardware>>),
+) -> Vec<String> {
+    let hw = util::BufferHardware::new(FunctionMode::Bit8, None);
+    let mut display = Display::new(Ks0073::new(hw, controller));
+    ops(&mut display);
+    display
+        .unwrap()
+        .unwrap()
+        .commands
+        .into_iter()
+        .filter(|cmd| cmd.starts_with("DATA"))
+        .collect()
+}
+
+#[test]
+fn init_4line() {
+    let vec = test(Controller::Ks0073, |lcd| {
+        lcd.init_extended(Lines::Line4);
+    });
+    assert_eq!(
+        vec[vec.len() - 3..].to_vec(),
+        vec!["DATA 0b00111100", "DATA 0b00001001", "DATA 0b00111000"]
+    );
+}
+
+#[test]
+fn position_4line() {
+    let vec = test(Controller::Ks0073, |lcd| {
+        lcd.init_extended(Lines::Line4);
+        lcd.position(5, 0);
+        lcd.position(5, 1);
+        lcd.position(5, 2);
+        lcd.position(5, 3);
+    });
+    assert_eq!(
+        vec[vec.len() - 4..].to_vec(),
+        vec![
+            "DATA 0b10000101",
+            "DATA 0b10100101",
+            "DATA 0b11000101",
+            "DATA 0b11100101",
+        ]
+    );
+
+    // Regular layout in 2-line mode
+    let vec = test(Controller::Ks0073, |lcd| {
+        lcd.init_extended(Lines::Line2);
+        lcd.position(5, 1);
+    });
+    assert_eq!(vec[vec.len() - 1], "DATA 0b11000101");
+}
+
+#[test]
+#[should_panic]
+fn position_4line_outside() {
+    test(Controller::Ks0073, |lcd| {
+        lcd.init_extended(Lines::Line4);
+        lcd.position(20, 0);
+    });
+}
+
+#[test]
+fn geometry_4line() {
+    let geometry = Geometry::four_line(20);
+    assert_eq!(geometry.rows(), 4);
+    assert_eq!(geometry.address(19, 0), 0x13);
+    assert_eq!(geometry.address(0, 3), 0x60);
+    test(Controller::Ks0073, |lcd| {
+        lcd.init_extended(Lines::Line4);
+        assert_eq!(lcd.geometry(), Some(geometry));
+    });
+}
+
+#[test]
+fn scroll() {
+    let vec = test(Controller::Ks0073, |lcd| {
+        lcd.set_scroll_enable(0b0101);
+        lcd.set_scroll_quantity(12);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "DATA 0b00111100",
+            "DATA 0b00010101",
+            "DATA 0b00111000",
+            "DATA 0b00111100",
+            "DATA 0b10001100",
+            "DATA 0b00111000",
+        ]
+    );
+
+    let vec = test(Controller::Ssd1803a, |lcd| {
+        lcd.set_scroll_enable(0b0101);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "DATA 0b00111001",
+            "DATA 0b00111010",
+            "DATA 0b00010101",
+            "DATA 0b00111000",
+        ]
+    );
+}
+
+#[test]
+fn double_height() {
+    let vec = test(Controller::Ssd1803a, |lcd| {
+        lcd.set_double_height(DoubleHeight::Middle);
+        lcd.set_bidirectional(true, false);
+        lcd.set_double_height(DoubleHeight::Off);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "DATA 0b00111010",
+            "DATA 0b00010100",
+            "DATA 0b00111100",
+            "DATA 0b00111010",
+            "DATA 0b00000110",
+            "DATA 0b00111100",
+            "DATA 0b00111000",
+        ]
+    );
+}
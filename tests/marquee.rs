@@ -0,0 +1,76 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::emulator::Hd44780Emulator;
+use lcd::marquee::Marquee;
+use lcd::testing;
+use lcd::*;
+
+fn run(marquee: &mut Marquee, lcd: &mut Display<Hd44780Emulator>, ticks: usize) -> Vec<usize> {
+    (0..ticks)
+        .map(|_| {
+            marquee.tick();
+            marquee.draw(lcd);
+            marquee.offset()
+        })
+        .collect()
+}
+
+#[test]
+fn software() {
+    let mut lcd = testing::emulator_display();
+    lcd.position(0, 0);
+    lcd.print("static");
+    let mut marquee = Marquee::row(Geometry::new(4, 2), 1, "abcdef");
+    marquee.set_speed(2);
+    marquee.draw(&mut lcd);
+    assert_eq!(run(&mut marquee, &mut lcd, 5), [0, 1, 1, 2, 2]);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 2), "|stat|\n|cdef|\n");
+    assert_eq!(emulator.display_shift(), 0);
+}
+
+#[test]
+fn software_wraps_around() {
+    let mut lcd = testing::emulator_display();
+    let mut marquee = Marquee::row(Geometry::new(4, 1), 0, "abcdef");
+    marquee.set_pause(2);
+    assert_eq!(
+        run(&mut marquee, &mut lcd, 14),
+        [0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0]
+    );
+    run(&mut marquee, &mut lcd, 8);
+    assert_eq!(lcd.unwrap().snapshot(4, 1), "|  ab|\n");
+}
+
+#[test]
+fn bounce() {
+    let mut lcd = testing::emulator_display();
+    let mut marquee = Marquee::row(Geometry::new(4, 1), 0, "abcdef");
+    marquee.set_bounce(true);
+    marquee.set_pause(1);
+    assert_eq!(
+        run(&mut marquee, &mut lcd, 10),
+        [0, 1, 2, 2, 1, 0, 0, 1, 2, 2]
+    );
+    assert_eq!(lcd.unwrap().snapshot(4, 1), "|cdef|\n");
+}
+
+#[test]
+fn hardware() {
+    let mut lcd = testing::emulator_display();
+    let mut marquee = Marquee::display(Geometry::new(4, 2), 0, "abcdef");
+    marquee.draw(&mut lcd);
+    run(&mut marquee, &mut lcd, 3);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 2), "|def |\n|    |\n");
+    assert_eq!(emulator.display_shift(), 3);
+
+    // Going around the DDRAM line shifts the display back
+    let mut lcd = Display::new(emulator);
+    run(&mut marquee, &mut lcd, 37);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 2), "|abcd|\n|    |\n");
+    assert_eq!(emulator.display_shift(), 0);
+}
@@ -0,0 +1,210 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use core::cell::Cell;
+use lcd::emulator::Hd44780Emulator;
+use lcd::menu::{Event, Item, Menu, Response};
+use lcd::testing;
+use lcd::*;
+
+fn snapshot(lcd: Display<Hd44780Emulator>) -> (String, Display<Hd44780Emulator>) {
+    let emulator = lcd.unwrap();
+    (emulator.snapshot(12, 2), Display::new(emulator))
+}
+
+#[test]
+fn navigation() {
+    let items = [
+        Item::Action("Start", 1),
+        Item::Action("Stop", 2),
+        Item::Action("Reset all", 3),
+    ];
+    let mut lcd = testing::emulator_display();
+    let mut menu = Menu::new(Geometry::new(12, 2), &items);
+    menu.draw(&mut lcd);
+    let (screen, mut lcd) = snapshot(lcd);
+    assert_eq!(screen, "|>Start      |\n| Stop      v|\n");
+
+    assert_eq!(menu.update(Event::Down), Response::None);
+    assert_eq!(menu.update(Event::Down), Response::None);
+    assert_eq!(menu.update(Event::Down), Response::None);
+    menu.draw(&mut lcd);
+    let (screen, _) = snapshot(lcd);
+    assert_eq!(screen, "| Stop      ^|\n|>Reset all  |\n");
+
+    assert_eq!(menu.update(Event::Select), Response::Action(3));
+    assert_eq!(menu.update(Event::Back), Response::Exit);
+}
+
+#[test]
+fn nested_fields() {
+    let volume = Cell::new(5);
+    let mute = Cell::new(false);
+    let mode = Cell::new(0);
+    let sound = [
+        Item::Number {
+            label: "Volume",
+            value: &volume,
+            min: 0,
+            max: 6,
+            step: 1,
+        },
+        Item::Toggle {
+            label: "Mute",
+            value: &mute,
+        },
+        Item::Choice {
+            label: "Mode",
+            value: &mode,
+            options: &["Mono", "Stereo"],
+        },
+    ];
+    let items = [Item::Menu("Sound", &sound)];
+    let mut lcd = testing::emulator_display();
+    let mut menu = Menu::new(Geometry::new(12, 2), &items);
+    menu.update(Event::Select);
+    assert_eq!(menu.depth(), 1);
+
+    // Edit and cancel
+    menu.update(Event::Select);
+    menu.update(Event::Up);
+    menu.update(Event::Up);
+    menu.draw(&mut lcd);
+    let (screen, mut lcd) = snapshot(lcd);
+    assert_eq!(screen, "|>Volume [6] |\n| Mute   Offv|\n");
+    assert_eq!(menu.update(Event::Back), Response::None);
+    assert_eq!(volume.get(), 5);
+
+    // Edit and confirm
+    menu.update(Event::Select);
+    menu.update(Event::Down);
+    assert_eq!(menu.update(Event::Select), Response::Changed("Volume"));
+    assert_eq!(volume.get(), 4);
+
+    menu.update(Event::Down);
+    assert_eq!(menu.update(Event::Select), Response::Changed("Mute"));
+    assert!(mute.get());
+    menu.update(Event::Down);
+    menu.update(Event::Select);
+    menu.update(Event::Down);
+    assert_eq!(menu.update(Event::Select), Response::Changed("Mode"));
+    assert_eq!(mode.get(), 1);
+    menu.draw(&mut lcd);
+    let (screen, _) = snapshot(lcd);
+    assert_eq!(screen, "| Mute    On^|\n|>Mod Stereo |\n");
+
+    menu.update(Event::Back);
+    assert_eq!(menu.depth(), 0);
+    assert_eq!(menu.selected().label(), "Sound");
+}
+
+#[test]
+fn text_editor() {
+    let name = [Cell::new(b'A'), Cell::new(b'B'), Cell::new(b'C')];
+    let items = [Item::Text {
+        label: "Name",
+        value: &name,
+        charset: b"ABC",
+    }];
+    let mut lcd = testing::emulator_display();
+    let mut menu = Menu::new(Geometry::new(12, 2), &items);
+    menu.update(Event::Select);
+    menu.update(Event::Select);
+    menu.update(Event::Up);
+    menu.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(12, 2), "|>Name [ACC] |\n|            |\n");
+    assert!(emulator.is_blink_on());
+    assert_eq!(emulator.cursor_position(12, 2), Some((8, 0)));
+
+    // Back to the first character and then cancel
+    let mut lcd = Display::new(emulator);
+    menu.update(Event::Back);
+    assert_eq!(menu.update(Event::Back), Response::None);
+    assert!(!menu.is_editing());
+    assert_eq!(name[1].get(), b'B');
+    menu.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(12, 2), "|>Name   ABC |\n|            |\n");
+    assert!(!emulator.is_blink_on());
+
+    menu.update(Event::Select);
+    menu.update(Event::Down);
+    menu.update(Event::Select);
+    menu.update(Event::Select);
+    assert_eq!(menu.update(Event::Select), Response::Changed("Name"));
+    assert_eq!(name[0].get(), b'C');
+}
+
+#[test]
+fn input() {
+    let items = [Item::Action("First", 1), Item::Action("Second", 2)];
+    let mut menu = Menu::new(Geometry::new(12, 2), &items);
+    let mut events = vec![Event::Down, Event::Select].into_iter();
+    let mut input = || events.next();
+    assert_eq!(menu.poll(&mut input), Response::None);
+    assert_eq!(menu.poll(&mut input), Response::Action(2));
+    assert_eq!(menu.poll(&mut input), Response::None);
+}
+
+#[test]
+fn long_values() {
+    let mode = Cell::new(0);
+    let level = Cell::new(100);
+    let items = [
+        Item::Choice {
+            label: "Mode",
+            value: &mode,
+            options: &["Automatic with schedule"],
+        },
+        Item::Number {
+            label: "Level",
+            value: &level,
+            min: 0,
+            max: 1000,
+            step: 1,
+        },
+    ];
+    let mut lcd = testing::emulator_display();
+    let mut menu = Menu::new(Geometry::new(16, 2), &items);
+    menu.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(
+        emulator.snapshot(16, 2),
+        "|> Automatic wit |\n| Level      100 |\n"
+    );
+
+    // Smallest display, value leaves no space for the label
+    let mut lcd = Display::new(emulator);
+    let mut menu = Menu::new(Geometry::new(4, 2), &items[1..]);
+    menu.update(Event::Select);
+    menu.draw(&mut lcd);
+    assert_eq!(lcd.unwrap().snapshot(4, 1), "|> [ |\n");
+}
+
+#[test]
+#[should_panic]
+fn empty_choice() {
+    let mode = Cell::new(0);
+    let items = [Item::Choice {
+        label: "Mode",
+        value: &mode,
+        options: &[],
+    }];
+    let mut menu = Menu::new(Geometry::new(16, 2), &items);
+    menu.update(Event::Select);
+}
+
+#[test]
+#[should_panic]
+fn empty_charset() {
+    let name = [Cell::new(b'A')];
+    let items = [Item::Text {
+        label: "Name",
+        value: &name,
+        charset: &[],
+    }];
+    let mut menu = Menu::new(Geometry::new(16, 2), &items);
+    menu.update(Event::Select);
+}
@@ -2,9 +2,10 @@
 extern crate pretty_assertions;
 extern crate lcd;
 
-mod util;
+use lcd::testing as util;
 use lcd::{
-    Backlight, Direction, EntryModeDirection, EntryModeShift, FunctionDots, FunctionLine, FunctionMode,
+    Backlight, Direction, DisplayBlink, DisplayCursor, DisplayMode, EntryModeDirection,
+    EntryModeShift, FunctionDots, FunctionLine, FunctionMode, InitConfig, Timing,
 };
 
 #[test]
@@ -141,6 +142,67 @@ fn init_8bit() {
     );
 }
 
+#[test]
+fn init_power_on_wait() {
+    let vec = util::test(FunctionMode::Bit8, None, |lcd| {
+        lcd.init_with(InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8));
+    });
+    assert_eq!(
+        vec[..3].to_vec(),
+        vec![
+            // Wait for the power to stabilize
+            "DELAY 50000",
+            // Send init command three times
+            "R/S false",
+            "DATA 0b00111100",
+        ]
+    );
+}
+
+#[test]
+fn init_warm() {
+    let vec = util::test(FunctionMode::Bit8, None, |lcd| {
+        lcd.init_with(
+            InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+                .power_on_wait(false)
+                .warm(true)
+                .clear(false)
+                .display(
+                    DisplayMode::DisplayOn,
+                    DisplayCursor::CursorOn,
+                    DisplayBlink::BlinkOff,
+                )
+                .entry_mode(EntryModeDirection::EntryLeft, EntryModeShift::Shift),
+        );
+    });
+    assert_eq!(
+        vec,
+        vec![
+            // Set lines, font size
+            "R/S false",
+            "DATA 0b00111000",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            // Display
+            "R/S false",
+            "DATA 0b00001110",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            // Entry mode
+            "R/S false",
+            "DATA 0b00000101",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50"
+        ]
+    );
+}
+
 #[test]
 fn clear_4bit() {
     let vec = util::test(FunctionMode::Bit4, None, |lcd| {
@@ -183,6 +245,44 @@ fn clear_8bit() {
     );
 }
 
+#[test]
+fn timing() {
+    let timing = Timing::for_oscillator(135);
+    assert_eq!(timing.command, 100);
+    assert_eq!(timing.clear_home, 4000);
+    assert_eq!(timing.address_update, 10);
+    assert_eq!(timing.reset_first, Timing::typical().reset_first);
+
+    let timing = Timing::worst_case();
+    assert_eq!(timing.command, 72);
+    assert_eq!(timing.clear_home, 2843);
+    assert_eq!(timing.address_update, 8);
+
+    let vec = util::test_timing(FunctionMode::Bit8, Timing::for_oscillator(135), |lcd| {
+        lcd.clear();
+        lcd.write(b'a');
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "R/S false",
+            "DATA 0b00000001",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 100",
+            "DELAY 4000",
+            "R/S true",
+            "DATA 0b01100001",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 100",
+            "DELAY 10",
+        ]
+    );
+}
+
 #[test]
 fn home_4bit() {
     let vec = util::test(FunctionMode::Bit4, None, |lcd| {
@@ -0,0 +1,191 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::emulator::{Hd44780Emulator, Rom};
+use lcd::*;
+
+fn display(rom: Rom, line: FunctionLine, dots: FunctionDots) -> Display<Hd44780Emulator> {
+    let mut emulator = Hd44780Emulator::new(FunctionMode::Bit4);
+    emulator.set_rom(rom);
+    let mut lcd = Display::new(emulator);
+    lcd.init(line, dots);
+    lcd
+}
+
+const HEART: [u8; 8] = [0x00, 0x0a, 0x1f, 0x1f, 0x0e, 0x04, 0x00, 0x00];
+
+#[test]
+fn snapshot() {
+    let mut lcd = display(Rom::A00, FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd.print("Hello\\~");
+    lcd.position(0, 1);
+    lcd.write(0xb1);
+    lcd.write(0xdd);
+    lcd.write(0xe4);
+    lcd.write(0x03);
+    let emulator = lcd.unwrap();
+    assert_eq!(
+        emulator.snapshot(8, 2),
+        "|Hello¥→ |\n\
+         |ｱﾝμ③    |\n"
+    );
+
+    let mut lcd = display(Rom::A02, FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd.print("C:\\~");
+    lcd.write(0xe9);
+    lcd.write(0x84);
+    lcd.write(0x1e);
+    lcd.write(0x7f);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(8, 1), "|C:\\~éИ▲⌂|\n");
+}
+
+#[test]
+fn glyphs() {
+    assert_eq!(
+        Rom::A00.glyph(b'A'),
+        [0x0e, 0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x00]
+    );
+    assert_eq!(Rom::A00.glyph(b'\\'), Rom::A02.glyph(0xa5));
+    assert_eq!(Rom::A00.glyph(0x01), [0; 8]);
+    assert_eq!(Rom::A00.glyph(0x90), [0; 8]);
+    assert_eq!(Rom::A00.to_char(0xdf), 'ﾟ');
+    assert_eq!(Rom::A00.to_char(0xff), '█');
+    assert_eq!(Rom::A02.to_char(0x1f), '▼');
+    assert_eq!(Rom::A02.to_char(0xbf), '¿');
+    assert_eq!(Rom::A02.to_char(0xff), 'ÿ');
+}
+
+#[test]
+fn pbm() {
+    let mut lcd = display(Rom::A00, FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd.upload_character(0, HEART);
+    lcd.display(
+        DisplayMode::DisplayOn,
+        DisplayCursor::CursorOff,
+        DisplayBlink::BlinkOff,
+    );
+    lcd.position(0, 0);
+    lcd.write(0);
+    lcd.write(b'A');
+    lcd.position(0, 1);
+    lcd.write(b'-');
+    let emulator = lcd.unwrap();
+    let frame = emulator.render(2, 2);
+    assert_eq!((frame.width(), frame.height()), (11, 17));
+    assert_eq!(
+        frame.to_pbm(),
+        "P1\n11 17\n\
+         00000001110\n\
+         01010010001\n\
+         11111010001\n\
+         11111010001\n\
+         01110011111\n\
+         00100010001\n\
+         00000010001\n\
+         00000000000\n\
+         00000000000\n\
+         00000000000\n\
+         00000000000\n\
+         00000000000\n\
+         11111000000\n\
+         00000000000\n\
+         00000000000\n\
+         00000000000\n\
+         00000000000\n"
+    );
+}
+
+#[test]
+fn cursor_and_blink() {
+    let mut lcd = display(Rom::A00, FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd.display(
+        DisplayMode::DisplayOn,
+        DisplayCursor::CursorOn,
+        DisplayBlink::BlinkOn,
+    );
+    lcd.write(b'.');
+    let mut emulator = lcd.unwrap();
+    while emulator.blink_phase() {
+        emulator.delay_us(1000);
+    }
+
+    // Cursor underline
+    let frame = emulator.render(2, 1);
+    let row = |frame: &lcd::emulator::Frame, y: usize| -> String {
+        (0..frame.width())
+            .map(|x| if frame.dot(x, y) { '#' } else { '.' })
+            .collect()
+    };
+    assert_eq!(row(&frame, 6), ".##........");
+    assert_eq!(row(&frame, 7), "......#####");
+
+    // Whole character is on in the other blink phase
+    emulator.delay_us(409_600);
+    assert!(emulator.blink_phase());
+    let frame = emulator.render(2, 1);
+    for y in 0..8 {
+        assert_eq!(&row(&frame, y)[6..], "#####");
+    }
+
+    // Nothing is shown when display is off
+    let mut lcd = Display::new(emulator);
+    lcd.display(
+        DisplayMode::DisplayOff,
+        DisplayCursor::CursorOn,
+        DisplayBlink::BlinkOn,
+    );
+    let frame = lcd.unwrap().render(2, 1);
+    for y in 0..8 {
+        assert_eq!(row(&frame, y), "...........");
+    }
+}
+
+#[test]
+fn large_font() {
+    let mut lcd = display(Rom::A00, FunctionLine::Line1, FunctionDots::Dots5x10);
+    lcd.upload_character(0, [0x1f; 8]);
+    lcd.upload_character(1, [0x1f, 0x1f, 0, 0, 0, 0, 0, 0]);
+    lcd.display(
+        DisplayMode::DisplayOn,
+        DisplayCursor::CursorOff,
+        DisplayBlink::BlinkOff,
+    );
+    lcd.position(0, 0);
+    lcd.write(1);
+    let frame = lcd.unwrap().render(1, 1);
+    assert_eq!((frame.width(), frame.height()), (5, 11));
+    // Code 1 is the same as code 0, 5x10 glyph takes first 11 bytes of CGRAM
+    let lit: Vec<bool> = (0..11).map(|y| frame.dot(0, y)).collect();
+    assert_eq!(
+        lit,
+        vec![true, true, true, true, true, true, true, true, true, true, false]
+    );
+}
+
+#[test]
+fn svg() {
+    let mut lcd = display(Rom::A00, FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd.display(
+        DisplayMode::DisplayOn,
+        DisplayCursor::CursorOff,
+        DisplayBlink::BlinkOff,
+    );
+    lcd.write(b'-');
+    let svg = lcd.unwrap().render(1, 1).to_svg(4);
+    assert!(svg.starts_with(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"32\" viewBox=\"0 0 20 32\">\n"
+    ));
+    assert!(svg.ends_with("</svg>\n"));
+    // Background and a rectangle for every dot
+    assert_eq!(svg.matches("<rect").count(), 1 + 40);
+    assert_eq!(svg.matches("fill=\"#1e2a10\"").count(), 5);
+    assert!(svg.contains("<rect x=\"0\" y=\"12\" width=\"4\" height=\"4\" fill=\"#1e2a10\"/>"));
+}
+
+#[test]
+#[should_panic]
+fn render_empty() {
+    Hd44780Emulator::new(FunctionMode::Bit4).render(0, 2);
+}
@@ -0,0 +1,161 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::emulator::Hd44780Emulator;
+use lcd::screen::{Field, Format, Label, Page, Screen};
+use lcd::testing::BufferHardware;
+use lcd::*;
+
+static STATUS: Page = Page {
+    labels: &[
+        Label {
+            col: 0,
+            row: 0,
+            text: "Temp",
+        },
+        Label {
+            col: 11,
+            row: 0,
+            text: "C",
+        },
+        Label {
+            col: 0,
+            row: 1,
+            text: "Mode",
+        },
+    ],
+    fields: &[
+        Field {
+            col: 5,
+            row: 0,
+            width: 6,
+            align: Align::Right,
+            format: Format::Decimal(1),
+        },
+        Field {
+            col: 5,
+            row: 1,
+            width: 7,
+            align: Align::Center,
+            format: Format::Integer,
+        },
+    ],
+};
+
+static DEBUG: Page = Page {
+    labels: &[
+        Label {
+            col: 0,
+            row: 0,
+            text: "Temp",
+        },
+        Label {
+            col: 0,
+            row: 1,
+            text: "Reg",
+        },
+    ],
+    fields: &[Field {
+        col: 4,
+        row: 1,
+        width: 8,
+        align: Align::Left,
+        format: Format::Hex(4),
+    }],
+};
+
+fn config() -> InitConfig {
+    InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8).geometry(Geometry::new(12, 2))
+}
+
+#[test]
+fn fields() {
+    let mut lcd =
+        UninitializedDisplay::new(Hd44780Emulator::new(FunctionMode::Bit4)).init(config());
+    let mut screen = Screen::new(Geometry::new(12, 2));
+    screen.show(&mut lcd, &STATUS);
+    screen.set_number(&mut lcd, 0, -5);
+    screen.set_text(&mut lcd, 1, "Auto");
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(12, 2), "|Temp   -0.5C|\n|Mode  Auto  |\n");
+
+    let mut lcd = Display::new(emulator);
+    screen.show(&mut lcd, &DEBUG);
+    screen.set_number(&mut lcd, 0, 0xbeef);
+    assert_eq!(
+        lcd.unwrap().snapshot(12, 2),
+        "|Temp        |\n|Reg BEEF    |\n"
+    );
+}
+
+#[test]
+fn repaint_changed() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    let mut screen = Screen::new(Geometry::new(12, 2));
+    screen.show(&mut lcd, &STATUS);
+    screen.set_number(&mut lcd, 0, 215);
+    let hw = lcd.unwrap();
+    let mark = hw.instructions().len();
+    let mut lcd = Display::new(hw);
+
+    // Same text, nothing is sent
+    screen.set_number(&mut lcd, 0, 215);
+    lcd = {
+        let hw = lcd.unwrap();
+        assert_eq!(hw.instructions().len(), mark);
+        Display::new(hw)
+    };
+
+    // Only the last digit changes
+    screen.set_number(&mut lcd, 0, 217);
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[mark..],
+        &[Instruction::SetDDRamAddr(0x0a), Instruction::Write(b'7')]
+    );
+}
+
+#[test]
+fn switch_page() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    let mut screen = Screen::new(Geometry::new(12, 2));
+    screen.show(&mut lcd, &STATUS);
+    let hw = lcd.unwrap();
+    let mark = hw.instructions().len();
+    let mut lcd = Display::new(hw);
+
+    // "Temp" stays, "C" is removed, "Mode" becomes "Reg "
+    screen.show(&mut lcd, &DEBUG);
+    assert_eq!(screen.page(), Some(&DEBUG));
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[mark..],
+        &[
+            Instruction::SetDDRamAddr(0x0b),
+            Instruction::Write(b' '),
+            Instruction::SetDDRamAddr(0x40),
+            Instruction::Write(b'R'),
+            Instruction::Write(b'e'),
+            Instruction::Write(b'g'),
+            Instruction::Write(b' '),
+        ]
+    );
+}
+
+#[test]
+fn invalidate() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    let mut screen = Screen::new(Geometry::new(12, 2));
+    screen.show(&mut lcd, &DEBUG);
+    let hw = lcd.unwrap();
+    let mark = hw.instructions().len();
+    let mut lcd = Display::new(hw);
+
+    screen.invalidate();
+    screen.set_text(&mut lcd, 0, "ab");
+    let instructions = lcd.unwrap().instructions().to_vec();
+    let mut expected = vec![Instruction::SetDDRamAddr(0x44)];
+    expected.extend(b"ab      ".iter().map(|&data| Instruction::Write(data)));
+    assert_eq!(&instructions[mark..], &expected[..]);
+}
@@ -0,0 +1,220 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::checker::{Constraints, Violation};
+use lcd::sigrok::{decode_csv, parse_csv, EventKind, ParseError, Pinout, Pins, ProtocolError};
+use lcd::*;
+use std::fmt::Write as _;
+
+/// Produces the sigrok CSV export (with the time column) from the pin activity.
+struct CsvCapture {
+    csv: String,
+    now: u64,
+    pins: Pins,
+}
+
+impl CsvCapture {
+    fn new() -> Self {
+        CsvCapture {
+            csv: "Time [s],RS,E,D4,D5,D6,D7\n".to_string(),
+            now: 0,
+            pins: Pins {
+                rs: false,
+                rw: false,
+                enable: false,
+                data: 0,
+            },
+        }
+    }
+}
+
+impl Hardware for CsvCapture {
+    fn rs(&mut self, bit: bool) {
+        self.pins.rs = bit;
+    }
+
+    fn enable(&mut self, bit: bool) {
+        self.pins.enable = bit;
+    }
+
+    fn data(&mut self, data: u8) {
+        self.pins.data = data;
+    }
+
+    fn wait_address(&mut self) {
+        self.now += 40;
+    }
+
+    fn apply(&mut self) {
+        let bit = |on: bool| if on { 1 } else { 0 };
+        write!(
+            self.csv,
+            "{}.{:09},{},{}",
+            self.now / 1_000_000_000,
+            self.now % 1_000_000_000,
+            bit(self.pins.rs),
+            bit(self.pins.enable)
+        )
+        .unwrap();
+        for index in 0..4 {
+            write!(self.csv, ",{}", bit(self.pins.data & (1 << index) != 0)).unwrap();
+        }
+        self.csv.push('\n');
+    }
+}
+
+impl Delay for CsvCapture {
+    fn delay_us(&mut self, delay_usec: u32) {
+        self.now += u64::from(delay_usec) * 1000;
+    }
+}
+
+fn pinout() -> Pinout {
+    Pinout::new("RS", "E", &["D4", "D5", "D6", "D7"])
+}
+
+fn instructions(csv: &str) -> Vec<Instruction> {
+    decode_csv(csv, &pinout(), Constraints::datasheet())
+        .unwrap()
+        .into_iter()
+        .map(|event| match event.kind {
+            EventKind::Instruction(instruction) => instruction,
+            EventKind::Error(error) => panic!("unexpected error at {}ns: {}", event.time, error),
+        })
+        .collect()
+}
+
+fn errors(csv: &str) -> Vec<ProtocolError> {
+    decode_csv(csv, &pinout(), Constraints::datasheet())
+        .unwrap()
+        .into_iter()
+        .filter_map(|event| match event.kind {
+            EventKind::Error(error) => Some(error),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn round_trip() {
+    let mut lcd = Display::new(CsvCapture::new());
+    lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+    lcd.position(0, 1);
+    lcd.print("Hi");
+    let csv = lcd.unwrap().csv;
+
+    let decoded = instructions(&csv);
+    // Reset sequence: three 8-bit function sets, then switch to 4-bit mode
+    let reset = Instruction::FunctionSet(
+        FunctionMode::Bit8,
+        FunctionLine::Line1,
+        FunctionDots::Dots5x8,
+    );
+    assert_eq!(&decoded[..3], &[reset, reset, reset]);
+    assert_eq!(
+        &decoded[decoded.len() - 3..],
+        &[
+            Instruction::SetDDRamAddr(0x40),
+            Instruction::Write(b'H'),
+            Instruction::Write(b'i'),
+        ]
+    );
+    assert!(decoded.contains(&Instruction::FunctionSet(
+        FunctionMode::Bit4,
+        FunctionLine::Line2,
+        FunctionDots::Dots5x8
+    )));
+    assert!(decoded.contains(&Instruction::ClearDisplay));
+}
+
+#[test]
+fn undefined_instruction() {
+    let csv = "\
+; Samplerate: 10 kHz
+RS,E,D4,D5,D6,D7
+0,0,0,0,0,0
+0,1,0,0,0,0
+0,0,0,0,0,0
+";
+    assert_eq!(errors(csv), vec![ProtocolError::UndefinedInstruction]);
+}
+
+#[test]
+fn unstable_bus() {
+    let csv = "\
+; Samplerate: 10 kHz
+RS,E,D4,D5,D6,D7
+0,0,0,1,0,0
+0,1,0,1,0,0
+0,1,1,1,0,0
+0,0,1,1,0,0
+";
+    assert_eq!(errors(csv), vec![ProtocolError::UnstableBus]);
+}
+
+#[test]
+fn incomplete_transfer() {
+    // Switch to 4-bit mode, then send only the first half of the write
+    let csv = "\
+; Samplerate: 10 kHz
+RS,E,D4,D5,D6,D7
+0,0,0,1,0,0
+0,1,0,1,0,0
+0,0,0,1,0,0
+1,0,0,0,1,0
+1,1,0,0,1,0
+1,0,0,0,1,0
+";
+    assert_eq!(errors(csv), vec![ProtocolError::IncompleteTransfer]);
+}
+
+#[test]
+fn timing() {
+    // Second 8-bit function set is sent before the first one has finished executing
+    let csv = "\
+Time [s],RS,E,D4,D5,D6,D7
+0.000000,0,0,1,1,0,0
+0.000001,0,1,1,1,0,0
+0.000002,0,0,1,1,0,0
+0.000010,0,1,1,1,0,0
+0.000011,0,0,1,1,0,0
+";
+    let errors = errors(csv);
+    assert_eq!(errors.len(), 1);
+    match errors[0] {
+        ProtocolError::Timing(diagnostic) => {
+            assert_eq!(
+                diagnostic.violation,
+                Violation::ExecutionTime {
+                    previous: 0x30,
+                    data: false
+                }
+            )
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        parse_csv("; Samplerate: 1 MHz\n", &pinout()),
+        Err(ParseError::NoHeader)
+    );
+    assert_eq!(
+        parse_csv("RS,E,D4,D5,D6,D7\n0,0,0,0,0,0\n", &pinout()),
+        Err(ParseError::NoTimebase)
+    );
+    assert_eq!(
+        parse_csv("; Samplerate: 1 MHz\nRS,E,D4,D5,D6\n", &pinout()),
+        Err(ParseError::MissingChannel("D7".to_string()))
+    );
+    assert_eq!(
+        parse_csv(
+            "; Samplerate: 1 MHz\nRS,E,D4,D5,D6,D7\n0,0,0,0,0,x\n",
+            &pinout()
+        ),
+        Err(ParseError::InvalidValue(3))
+    );
+}
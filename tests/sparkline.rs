@@ -0,0 +1,103 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::sparkline::Sparkline;
+use lcd::testing::{self, BufferHardware};
+use lcd::*;
+
+#[test]
+fn auto_range() {
+    let mut lcd = testing::emulator_display();
+    let mut chart = Sparkline::new(Geometry::new(4, 2), 1, 1, 2);
+    assert_eq!(chart.capacity(), 10);
+    for sample in 0..10 {
+        chart.push(sample * 10 - 30);
+    }
+    chart.draw(&mut lcd);
+    assert_eq!(chart.sample_range(), Some((-30, 60)));
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 2), "|    |\n| ⓪① |\n");
+    assert_eq!(
+        emulator.custom_glyph(0),
+        [0, 0, 0, 0, 0x01, 0x03, 0x07, 0x1f]
+    );
+    assert_eq!(
+        emulator.custom_glyph(1),
+        [0x01, 0x03, 0x07, 0x0f, 0x1f, 0x1f, 0x1f, 0x1f]
+    );
+}
+
+#[test]
+fn shared_glyphs() {
+    let mut lcd = testing::emulator_display();
+    let mut chart = Sparkline::new(Geometry::new(8, 1), 0, 0, 6);
+    chart.set_samples_per_character(1);
+    for &sample in &[1, 2, 1, 2] {
+        chart.push(sample);
+    }
+    chart.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(8, 1), "|  ⓪①⓪①  |\n");
+    assert_eq!(emulator.custom_glyph(0), [0, 0, 0, 0, 0, 0, 0, 0x1f]);
+    assert_eq!(emulator.custom_glyph(1), [0x1f; 8]);
+}
+
+#[test]
+fn reduced_resolution() {
+    let mut lcd = testing::emulator_display();
+    let mut chart = Sparkline::new(Geometry::new(4, 1), 0, 0, 4);
+    chart.set_samples_per_character(1);
+    chart.set_glyph_locations(6..8);
+    for &sample in &[0, 2, 4, 7] {
+        chart.push(sample);
+    }
+    chart.draw(&mut lcd);
+    let emulator = lcd.unwrap();
+    assert_eq!(emulator.snapshot(4, 1), "|⑥⑥⑦⑦|\n");
+    assert_eq!(
+        emulator.custom_glyph(6),
+        [0, 0, 0, 0, 0x1f, 0x1f, 0x1f, 0x1f]
+    );
+}
+
+#[test]
+fn incremental() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    let mut chart = Sparkline::new(Geometry::new(4, 1), 0, 0, 4);
+    chart.set_samples_per_character(1);
+    chart.set_range(0, 7);
+    for sample in 0..4 {
+        chart.push(sample);
+    }
+    chart.draw(&mut lcd);
+    chart.push(4);
+    chart.draw(&mut lcd);
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[instructions.len() - 14..],
+        &[
+            // Only the new glyph is uploaded, into the location which is no longer used
+            Instruction::SetCGRamAddr(0x00),
+            Instruction::Write(0x00),
+            Instruction::Write(0x00),
+            Instruction::Write(0x00),
+            Instruction::Write(0x1f),
+            Instruction::Write(0x1f),
+            Instruction::Write(0x1f),
+            Instruction::Write(0x1f),
+            Instruction::Write(0x1f),
+            Instruction::SetDDRamAddr(0x00),
+            Instruction::Write(1),
+            Instruction::Write(2),
+            Instruction::Write(3),
+            Instruction::Write(0),
+        ]
+    );
+}
+
+#[test]
+#[should_panic]
+fn outside_display() {
+    Sparkline::new(Geometry::new(4, 1), 2, 0, 255);
+}
@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::st7032::St7032;
+use lcd::*;
+
+#[derive(Default)]
+struct BufferBus {
+    commands: Vec<String>,
+}
+
+impl I2c for BufferBus {
+    fn write(&mut self, address: u8, bytes: &[u8]) {
+        let bytes: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        self.commands
+            .push(format!("I2C {:02x} {}", address, bytes.join(" ")));
+    }
+}
+
+struct IgnoredDelay;
+
+impl Delay for IgnoredDelay {
+    fn delay_us(&mut self, _delay: u32) {}
+}
+
+fn test(ops: impl Fn(&mut Display<St7032<BufferBus, IgnoredDelay>>)) -> Vec<String> {
+    let mut display = Display::new(St7032::new(BufferBus::default(), IgnoredDelay));
+    ops(&mut display);
+    display.unwrap().unwrap().0.commands
+}
+
+#[test]
+fn init() {
+    let vec = test(|lcd| {
+        lcd.init_st7032(FunctionLine::Line2);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            // Reset sequence
+            "I2C 3e 00 3c",
+            "I2C 3e 00 3c",
+            "I2C 3e 00 3c",
+            // Function set, display off, clear, entry mode
+            "I2C 3e 00 38",
+            "I2C 3e 00 08",
+            "I2C 3e 00 01",
+            "I2C 3e 00 06",
+            // Extended instruction set
+            "I2C 3e 00 39",
+            "I2C 3e 00 14",
+            "I2C 3e 00 70",
+            "I2C 3e 00 56",
+            "I2C 3e 00 6c",
+            "I2C 3e 00 38",
+        ]
+    );
+}
+
+#[test]
+fn contrast() {
+    let vec = test(|lcd| {
+        lcd.set_contrast(0x2a);
+        lcd.set_booster(false);
+        lcd.set_follower(true, 5);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "I2C 3e 00 39",
+            "I2C 3e 00 7a",
+            "I2C 3e 00 56",
+            "I2C 3e 00 38",
+            "I2C 3e 00 39",
+            "I2C 3e 00 52",
+            "I2C 3e 00 38",
+            "I2C 3e 00 39",
+            "I2C 3e 00 6d",
+            "I2C 3e 00 38",
+        ]
+    );
+}
+
+#[test]
+fn icons() {
+    let vec = test(|lcd| {
+        lcd.set_icons_enabled(true);
+        lcd.set_icon(3, 4);
+        lcd.set_icon(3, 0);
+        lcd.clear_icon(3, 4);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "I2C 3e 00 39",
+            "I2C 3e 00 5e",
+            "I2C 3e 00 38",
+            "I2C 3e 00 39",
+            "I2C 3e 00 43",
+            "I2C 3e 40 10",
+            "I2C 3e 00 38",
+            "I2C 3e 00 39",
+            "I2C 3e 00 43",
+            "I2C 3e 40 11",
+            "I2C 3e 00 38",
+            "I2C 3e 00 39",
+            "I2C 3e 00 43",
+            "I2C 3e 40 01",
+            "I2C 3e 00 38",
+        ]
+    );
+}
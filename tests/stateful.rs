@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::stateful::Stateful;
+use lcd::testing::BufferHardware;
+use lcd::*;
+
+fn test(ops: impl Fn(&mut Display<Stateful<BufferHardware>>)) -> Vec<Instruction> {
+    let hw = BufferHardware::new(FunctionMode::Bit4, None);
+    let mut display = Display::new(Stateful::new(hw));
+    ops(&mut display);
+    display.unwrap().unwrap().instructions().to_vec()
+}
+
+#[test]
+fn independent_options() {
+    let vec = test(|lcd| {
+        lcd.set_display(true);
+        lcd.set_blink(true);
+        lcd.set_cursor(true);
+        lcd.set_cursor(false);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOn,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff
+            ),
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOn,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOn
+            ),
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOn,
+                DisplayCursor::CursorOn,
+                DisplayBlink::BlinkOn
+            ),
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOn,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOn
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unchanged_options_are_not_sent() {
+    let vec = test(|lcd| {
+        lcd.set_display(false);
+        lcd.set_entry_direction(EntryModeDirection::EntryRight);
+        lcd.set_lines(FunctionLine::Line1);
+        lcd.set_entry_shift(EntryModeShift::Shift);
+        lcd.set_entry_shift(EntryModeShift::Shift);
+    });
+    assert_eq!(
+        vec,
+        vec![Instruction::EntryModeSet(
+            EntryModeDirection::EntryRight,
+            EntryModeShift::Shift
+        )]
+    );
+}
+
+#[test]
+fn tracks_other_methods() {
+    let vec = test(|lcd| {
+        lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+        lcd.display(
+            DisplayMode::DisplayOn,
+            DisplayCursor::CursorOn,
+            DisplayBlink::BlinkOff,
+        );
+        lcd.set_blink(true);
+        lcd.set_dots(FunctionDots::Dots5x8);
+        assert_eq!(lcd.state().line, FunctionLine::Line2);
+        assert_eq!(lcd.state().mode, FunctionMode::Bit4);
+    });
+    assert_eq!(
+        vec.last(),
+        Some(&Instruction::DisplayControl(
+            DisplayMode::DisplayOn,
+            DisplayCursor::CursorOn,
+            DisplayBlink::BlinkOn
+        ))
+    );
+}
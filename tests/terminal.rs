@@ -0,0 +1,101 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use core::fmt::Write;
+use lcd::testing;
+use lcd::*;
+
+#[test]
+fn control_characters() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    write!(lcd, "one\ntwo\r2\tX\nabc\x08\x08d\nend").unwrap();
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|one     |\n\
+         |2wo X   |\n\
+         |ad      |\n\
+         |end     |\n"
+    );
+}
+
+#[test]
+fn scroll_and_form_feed() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Scroll);
+    write!(lcd, "1\n2\n3\n4\n5").unwrap();
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|2       |\n\
+         |3       |\n\
+         |4       |\n\
+         |5       |\n"
+    );
+
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Scroll);
+    write!(lcd, "gone\x0cnew").unwrap();
+    assert_eq!(lcd.text_position(), Some((3, 0)));
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 2),
+        "|new     |\n\
+         |        |\n"
+    );
+}
+
+#[test]
+fn ansi() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    write!(lcd, "abcdefgh\x1b[2;3HXY\x1b[4;1Hlast\x1b[1;4H\x1b[K").unwrap();
+    // Sequences could be split between the writes
+    write!(lcd, "\x1b[?").unwrap();
+    write!(lcd, "25h\x1b[31mred").unwrap();
+    assert_eq!(lcd.text_position(), Some((6, 0)));
+    let emulator = lcd.unwrap();
+    assert!(emulator.is_cursor_on());
+    assert_eq!(
+        emulator.snapshot(8, 4),
+        "|abcred  |\n\
+         |  XY    |\n\
+         |        |\n\
+         |last    |\n"
+    );
+
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    write!(lcd, "text\x1b[2J").unwrap();
+    assert_eq!(lcd.unwrap().snapshot(8, 1), "|        |\n");
+}
+
+#[test]
+fn charlcd() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    write!(lcd, "\x1b[LC\x1b[LB\x1b[Lx2y1;hi\x1b[LG10a1f;\x01\x1b[L+").unwrap();
+    let emulator = lcd.unwrap();
+    assert!(emulator.is_cursor_on());
+    assert!(emulator.is_blink_on());
+    assert_eq!(emulator.custom_glyph(1), [0x0a, 0x1f, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        emulator.snapshot(8, 2),
+        "|        |\n\
+         |  hi①   |\n"
+    );
+
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    write!(lcd, "\x1b[Ld").unwrap();
+    assert!(!lcd.unwrap().is_display_on());
+}
+
+#[test]
+fn reinitialize() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    // Cursor turned on by the escape sequence is not kept, configured state is restored
+    write!(lcd, "\x1b[LC\x1b[LI").unwrap();
+    let emulator = lcd.unwrap();
+    assert!(emulator.is_display_on());
+    assert!(!emulator.is_cursor_on());
+}
+
+#[test]
+fn raw() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Raw);
+    write!(lcd, "a\x01b").unwrap();
+    assert_eq!(lcd.unwrap().snapshot(8, 1), "|a①b     |\n");
+}
@@ -0,0 +1,102 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::testing::{self, BufferHardware};
+use lcd::*;
+
+#[test]
+fn init_4bit() {
+    let instructions = testing::test_instructions(FunctionMode::Bit4, None, |lcd| {
+        lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+    });
+    // Reset sequence nibbles are paired as display would do if it was in 4-bit mode already
+    assert_eq!(
+        instructions,
+        vec![
+            Instruction::FunctionSet(
+                FunctionMode::Bit8,
+                FunctionLine::Line1,
+                FunctionDots::Dots5x8
+            ),
+            Instruction::FunctionSet(
+                FunctionMode::Bit8,
+                FunctionLine::Line1,
+                FunctionDots::Dots5x8
+            ),
+            Instruction::FunctionSet(
+                FunctionMode::Bit4,
+                FunctionLine::Line1,
+                FunctionDots::Dots5x8
+            ),
+            Instruction::FunctionSet(
+                FunctionMode::Bit4,
+                FunctionLine::Line2,
+                FunctionDots::Dots5x8
+            ),
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOff,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff
+            ),
+            Instruction::ClearDisplay,
+            Instruction::EntryModeSet(EntryModeDirection::EntryRight, EntryModeShift::NoShift),
+        ]
+    );
+}
+
+#[test]
+fn busy_flag_is_omitted() {
+    // Busy flag is polled after each of the nine transfers, two nibbles each
+    let input = vec![0; 18];
+    let instructions = testing::test_instructions(FunctionMode::Bit4, Some(input), |lcd| {
+        lcd.upload_character(1, [0x1f; 8]);
+    });
+    assert_eq!(instructions[0], Instruction::SetCGRamAddr(0x08));
+    assert_eq!(&instructions[1..], &[Instruction::Write(0x1f); 8]);
+}
+
+#[test]
+fn commands_and_instructions() {
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    lcd.write(b'A');
+    let hw = lcd.unwrap();
+    assert_eq!(
+        hw.commands,
+        vec![
+            "R/S true",
+            "DATA 0b01000001",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            "DELAY 5"
+        ]
+    );
+    assert_eq!(hw.instructions(), &[Instruction::Write(b'A')]);
+}
+
+/// Send undefined instruction 0x00 (valid for some controllers, as an argument byte).
+fn undefined_instruction() -> BufferHardware {
+    let mut hw = BufferHardware::new(FunctionMode::Bit8, None);
+    hw.rs(false);
+    hw.data(0x00);
+    hw.enable(true);
+    hw.enable(false);
+    hw
+}
+
+#[test]
+fn undefined_instruction_recorded() {
+    let hw = undefined_instruction();
+    assert_eq!(
+        hw.commands,
+        vec!["R/S false", "DATA 0b00000000", "EN true", "EN false"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "undefined instruction 0x00 was sent (command #3)")]
+fn undefined_instruction_reported() {
+    undefined_instruction().instructions();
+}
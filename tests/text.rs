@@ -0,0 +1,71 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::testing;
+use lcd::*;
+
+#[test]
+fn raw() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Raw);
+    lcd.print("0123456789");
+    assert_eq!(lcd.text_position(), None);
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|01234567|\n\
+         |        |\n\
+         |89      |\n\
+         |        |\n"
+    );
+}
+
+#[test]
+fn wrap() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Wrap);
+    lcd.print("0123456789");
+    assert_eq!(lcd.text_position(), Some((2, 1)));
+    lcd.position(6, 3);
+    lcd.print("abcd");
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|cd234567|\n\
+         |89      |\n\
+         |        |\n\
+         |      ab|\n"
+    );
+}
+
+#[test]
+fn truncate() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Truncate);
+    lcd.print("0123456789");
+    assert_eq!(lcd.text_position(), Some((8, 0)));
+    lcd.position(0, 1);
+    lcd.print("Hi");
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|01234567|\n\
+         |Hi      |\n\
+         |        |\n\
+         |        |\n"
+    );
+}
+
+#[test]
+fn scroll() {
+    let mut lcd = testing::emulator_text_display(Geometry::new(8, 4), TextMode::Scroll);
+    lcd.print("first...second..third...fourth..");
+    // Last row is filled, but not scrolled until the next character
+    assert_eq!(lcd.text_position(), Some((8, 3)));
+    lcd.print("fifth");
+    // Custom characters do not affect the text
+    lcd.upload_character(0, [0x1f; 8]);
+    lcd.print("!");
+    assert_eq!(
+        lcd.unwrap().snapshot(8, 4),
+        "|second..|\n\
+         |third...|\n\
+         |fourth..|\n\
+         |fifth!  |\n"
+    );
+}
@@ -0,0 +1,149 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::testing::BufferHardware;
+use lcd::*;
+
+#[test]
+fn init() {
+    let hw = BufferHardware::new(FunctionMode::Bit8, None);
+    let lcd = UninitializedDisplay::new(hw).init(
+        InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8).geometry(Geometry::new(16, 4)),
+    );
+    assert_eq!(lcd.lines(), Some(FunctionLine::Line2));
+    assert_eq!(lcd.dots(), Some(FunctionDots::Dots5x8));
+    assert_eq!(lcd.geometry(), Some(Geometry::new(16, 4)));
+
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[3..5],
+        &[
+            Instruction::FunctionSet(
+                FunctionMode::Bit8,
+                FunctionLine::Line2,
+                FunctionDots::Dots5x8
+            ),
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOff,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff
+            ),
+        ]
+    );
+}
+
+#[test]
+fn position() {
+    let hw = BufferHardware::new(FunctionMode::Bit8, None);
+    let mut lcd = UninitializedDisplay::new(hw).init(
+        InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+            .warm(true)
+            .clear(false)
+            .geometry(Geometry::new(16, 4)),
+    );
+    lcd.position(1, 2);
+    lcd.position(1, 3);
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[instructions.len() - 2..],
+        &[
+            Instruction::SetDDRamAddr(0x11),
+            Instruction::SetDDRamAddr(0x51)
+        ]
+    );
+}
+
+#[test]
+fn legacy() {
+    let hw = BufferHardware::new(FunctionMode::Bit8, None);
+    let mut lcd = Display::new(hw);
+    assert_eq!(lcd.lines(), None);
+    lcd.init(FunctionLine::Line2, FunctionDots::Dots5x8);
+    assert_eq!(lcd.lines(), Some(FunctionLine::Line2));
+    assert_eq!(lcd.geometry(), None);
+
+    // Rows 2 and 3 of 20x4 display
+    lcd.position(0, 2);
+    lcd.position(0, 3);
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[instructions.len() - 2..],
+        &[
+            Instruction::SetDDRamAddr(0x14),
+            Instruction::SetDDRamAddr(0x54)
+        ]
+    );
+}
+
+#[test]
+fn geometry() {
+    let geometry = Geometry::new(20, 4);
+    assert_eq!(geometry.address(0, 0), 0x00);
+    assert_eq!(geometry.address(3, 1), 0x43);
+    assert_eq!(geometry.address(0, 2), 0x14);
+    assert_eq!(geometry.address(19, 3), 0x67);
+    assert_eq!(Geometry::new(80, 1).address(79, 0), 0x4f);
+}
+
+#[test]
+#[should_panic]
+fn geometry_too_large() {
+    Geometry::new(24, 4);
+}
+
+#[test]
+#[should_panic]
+fn geometry_column_out_of_range() {
+    Geometry::new(16, 2).address(16, 0);
+}
+
+#[test]
+#[should_panic]
+fn position_out_of_range() {
+    let mut lcd = UninitializedDisplay::new(BufferHardware::new(FunctionMode::Bit8, None)).init(
+        InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8).geometry(Geometry::new(16, 2)),
+    );
+    lcd.position(16, 1);
+}
+
+#[test]
+fn init_order() {
+    let on = Instruction::DisplayControl(
+        DisplayMode::DisplayOn,
+        DisplayCursor::CursorOn,
+        DisplayBlink::BlinkOff,
+    );
+    let entry = Instruction::EntryModeSet(EntryModeDirection::EntryRight, EntryModeShift::NoShift);
+    let config = InitConfig::new(FunctionLine::Line2, FunctionDots::Dots5x8)
+        .power_on_wait(false)
+        .display(
+            DisplayMode::DisplayOn,
+            DisplayCursor::CursorOn,
+            DisplayBlink::BlinkOff,
+        );
+
+    // Cold: display off, clear, entry mode, then display on
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    lcd.init_with(config);
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(
+        &instructions[instructions.len() - 4..],
+        &[
+            Instruction::DisplayControl(
+                DisplayMode::DisplayOff,
+                DisplayCursor::CursorOff,
+                DisplayBlink::BlinkOff
+            ),
+            Instruction::ClearDisplay,
+            entry,
+            on,
+        ]
+    );
+
+    // Warm: clear first, so old content does not flash
+    let mut lcd = Display::new(BufferHardware::new(FunctionMode::Bit8, None));
+    lcd.init_with(config.warm(true));
+    let instructions = lcd.unwrap().instructions().to_vec();
+    assert_eq!(&instructions[1..], &[Instruction::ClearDisplay, on, entry]);
+}
@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::testing as util;
+
+use lcd::transport::SpiTransport;
+use lcd::us2066::{DoubleHeight, Fade, Lines, Regulator, Rom, Us2066};
+use lcd::*;
+
+#[derive(Default)]
+struct BufferBus {
+    commands: Vec<String>,
+}
+
+impl Spi for BufferBus {
+    fn write(&mut self, bytes: &[u8]) {
+        let bytes: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        self.commands.push(format!("SPI {}", bytes.join(" ")));
+    }
+}
+
+fn test(
+    ops: impl Fn(&mut Display<Us2066<SpiTransport<BufferBus, util::IgnoredDelay>>>),
+) -> Vec<String> {
+    let transport = SpiTransport::new(BufferBus::default(), util::IgnoredDelay);
+    let mut display = Display::new(Us2066::new(transport));
+    ops(&mut display);
+    display.unwrap().unwrap().unwrap().0.commands
+}
+
+/// Collapse SPI transfers back into bytes, prefixing data bytes with "D".
+fn bytes(commands: Vec<String>) -> Vec<String> {
+    commands
+        .iter()
+        .map(|cmd| {
+            let bytes: Vec<u8> = cmd[4..]
+                .split(' ')
+                .map(|b| u8::from_str_radix(b, 16).unwrap())
+                .collect();
+            let prefix = if bytes[0] & 0x40 != 0 { "D" } else { "" };
+            format!("{}{:02x}", prefix, bytes[1] | (bytes[2] << 4))
+        })
+        .collect()
+}
+
+#[test]
+fn spi_framing() {
+    let vec = test(|lcd| {
+        lcd.print("A");
+        lcd.home();
+    });
+    assert_eq!(vec, vec!["SPI 5f 01 04", "SPI 1f 02 00"]);
+}
+
+#[test]
+fn init() {
+    let vec = test(|lcd| {
+        lcd.init_us2066(Lines::Line4, Rom::A, Regulator::Internal);
+    });
+    assert_eq!(
+        bytes(vec),
+        vec![
+            // Function selection A
+            "3a", "71", "D5c", "38", // Display off
+            "08", // Display clock
+            "3a", "79", "d5", "70", "78", "38",
+            // Extended function set, COM/SEG direction, function selection B
+            "3a", "09", "06", "72", "D00", "38", // OLED characterization
+            "3a", "79", "da", "10", "dc", "00", "81", "7f", "d9", "f1", "db", "40", "78", "38",
+            // Clear, entry mode
+            "01", "06",
+        ]
+    );
+}
+
+#[test]
+fn brightness_and_fade() {
+    let vec = test(|lcd| {
+        lcd.set_brightness(0xc0);
+        lcd.set_fade(Fade::Blinking, 3);
+        lcd.set_fade(Fade::Disabled, 0);
+    });
+    assert_eq!(
+        bytes(vec),
+        vec![
+            "3a", "79", "81", "c0", "78", "38", "3a", "79", "23", "33", "78", "38", "3a", "79",
+            "23", "00", "78", "38",
+        ]
+    );
+}
+
+#[test]
+fn double_height_parallel() {
+    let hw = util::BufferHardware::new(FunctionMode::Bit8, None);
+    let mut lcd = Display::new(Us2066::new(hw));
+    lcd.set_double_height(DoubleHeight::Top);
+    lcd.set_reverse(true);
+    lcd.set_double_height(DoubleHeight::Off);
+    let commands = lcd.unwrap().unwrap().commands;
+    let data: Vec<&String> = commands.iter().filter(|c| c.starts_with("DATA")).collect();
+    assert_eq!(
+        data,
+        vec![
+            "DATA 0b00111010",
+            "DATA 0b00011100",
+            "DATA 0b00111100",
+            "DATA 0b00111011",
+            "DATA 0b00111100",
+            "DATA 0b00111000",
+        ]
+    );
+}
@@ -0,0 +1,88 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::emulator::Hd44780Emulator;
+use lcd::vcd::VcdRecorder;
+use lcd::*;
+
+#[test]
+fn write_8bit() {
+    let mut lcd = Display::new(VcdRecorder::new(Hd44780Emulator::new(FunctionMode::Bit8)));
+    lcd.write(b'A');
+    let recorder = lcd.unwrap();
+    assert_eq!(recorder.now_ns(), 56_002);
+    assert_eq!(
+        recorder.to_vcd(),
+        concat!(
+            "$version lcd ",
+            env!("CARGO_PKG_VERSION"),
+            " $end\n",
+            "$timescale 1ns $end\n",
+            "$scope module lcd $end\n",
+            "$var wire 1 ! rs $end\n",
+            "$var wire 1 \" rw $end\n",
+            "$var wire 1 # e $end\n",
+            "$var wire 8 $ data [7:0] $end\n",
+            "$var event 1 % apply $end\n",
+            "$var integer 32 & delay_us $end\n",
+            "$upscope $end\n",
+            "$enddefinitions $end\n",
+            "$dumpvars\n",
+            "x!\n",
+            "x\"\n",
+            "x#\n",
+            "bxxxxxxxx $\n",
+            "bx &\n",
+            "$end\n",
+            "#0\n",
+            "1!\n",
+            "1%\n",
+            "b01000001 $\n",
+            "#1\n",
+            "1%\n",
+            "1#\n",
+            "#2\n",
+            "1%\n",
+            "b1 &\n",
+            "#1002\n",
+            "0#\n",
+            "1%\n",
+            "b110010 &\n",
+            "#51002\n",
+            "b101 &\n",
+        )
+    );
+}
+
+#[test]
+fn short_pulse() {
+    let mut recorder = VcdRecorder::new(Hd44780Emulator::new(FunctionMode::Bit4));
+    recorder.data(0xa5);
+    recorder.enable(true);
+    recorder.enable(false);
+    recorder.delay_us(1);
+    recorder.enable(true);
+    let vcd = recorder.to_vcd();
+    let body = &vcd[vcd.find("#0").unwrap()..];
+    assert_eq!(body, "#0\nb0101 $\n1#\n#1\n0#\nb1 &\n#1001\n1#\n");
+}
+
+#[test]
+fn read_data() {
+    let mut emulator = Hd44780Emulator::new(FunctionMode::Bit8);
+    emulator.set_readable(true);
+    let mut lcd = Display::new(VcdRecorder::new(emulator));
+    lcd.clear();
+    let vcd = lcd.unwrap().to_vcd();
+    // Busy flag is set right after the clear display instruction
+    assert!(vcd.contains("1\"\n"));
+    assert!(vcd.contains("b10000000 $\n"));
+
+    let mut out = Vec::new();
+    let mut lcd = Display::new(VcdRecorder::new(Hd44780Emulator::new(FunctionMode::Bit8)));
+    lcd.home();
+    let recorder = lcd.unwrap();
+    recorder.write_vcd(&mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), recorder.to_vcd());
+}
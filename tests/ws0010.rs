@@ -0,0 +1,209 @@
+#[macro_use]
+extern crate pretty_assertions;
+extern crate lcd;
+
+use lcd::emulator::Hd44780Emulator;
+use lcd::testing as util;
+
+use lcd::ws0010::{FontTable, GraphicBuffer, Ws0010};
+use lcd::*;
+
+fn commands(
+    mode: FunctionMode,
+    ops: impl Fn(&mut Display<Ws0010<util::BufferHardware>>),
+) -> Vec<String> {
+    let hw = util::BufferHardware::new(mode, None);
+    let mut display = Display::new(Ws0010::new(hw));
+    ops(&mut display);
+    display.unwrap().unwrap().commands
+}
+
+fn test(
+    mode: FunctionMode,
+    ops: impl Fn(&mut Display<Ws0010<util::BufferHardware>>),
+) -> Vec<String> {
+    data(commands(mode, ops))
+}
+
+fn data(commands: Vec<String>) -> Vec<String> {
+    commands
+        .into_iter()
+        .filter(|cmd| cmd.starts_with("DATA") || cmd.starts_with("R/S"))
+        .collect()
+}
+
+#[test]
+fn init_4bit() {
+    let mut commands = commands(FunctionMode::Bit4, |lcd| {
+        lcd.init_ws0010(FunctionLine::Line2, FontTable::WesternEuropean1);
+    });
+    let rest = commands.split_off(45);
+    assert_eq!(
+        commands,
+        vec![
+            // Zero nibbles align the 4-bit transfer...
+            "R/S false",
+            "DATA 0b0000",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            "DATA 0b0000",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            "DATA 0b0000",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            "DATA 0b0000",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            "DATA 0b0000",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            // ...then reset by instruction: send 8-bit function set three times...
+            "R/S false",
+            "DATA 0b0011",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 4500",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 150",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+            // ...and switch to 4-bit mode
+            "DATA 0b0010",
+            "EN true",
+            "DELAY 1",
+            "EN false",
+            "DELAY 50",
+        ]
+    );
+    assert_eq!(
+        data(rest),
+        vec![
+            // Function set
+            "R/S false",
+            "DATA 0b0010",
+            "DATA 0b1001",
+            // Display off
+            "R/S false",
+            "DATA 0b0000",
+            "DATA 0b1000",
+            // Character mode, power on
+            "R/S false",
+            "DATA 0b0001",
+            "DATA 0b0111",
+            // Clear
+            "R/S false",
+            "DATA 0b0000",
+            "DATA 0b0001",
+            // Entry mode
+            "R/S false",
+            "DATA 0b0000",
+            "DATA 0b0110",
+            // Home
+            "R/S false",
+            "DATA 0b0000",
+            "DATA 0b0010",
+        ]
+    );
+}
+
+#[test]
+fn resync_4bit() {
+    let mut lcd = Display::new(Ws0010::new(Hd44780Emulator::new(FunctionMode::Bit4)));
+    lcd.init_ws0010(FunctionLine::Line2, FontTable::WesternEuropean1);
+    lcd.print("old");
+
+    // MCU restarts in the middle of the 4-bit transfer (first nibble of "x" is sent)
+    let mut emulator = lcd.unwrap().unwrap();
+    emulator.rs(true);
+    emulator.data(0x7);
+    emulator.enable(true);
+    emulator.enable(false);
+    emulator.delay_us(100);
+
+    let mut lcd = Display::new(Ws0010::new(emulator));
+    lcd.init_ws0010(FunctionLine::Line2, FontTable::WesternEuropean1);
+    lcd.print("new");
+    let emulator = lcd.unwrap().unwrap();
+    assert!(!emulator.is_eight_bit());
+    assert!(emulator.is_two_lines());
+    assert_eq!(emulator.snapshot(4, 1), "|new |\n");
+
+    // Warm restart with the aligned 4-bit interface
+    let mut lcd = Display::new(Ws0010::new(emulator));
+    lcd.init_ws0010(FunctionLine::Line2, FontTable::WesternEuropean1);
+    lcd.print("ok");
+    let emulator = lcd.unwrap().unwrap();
+    assert!(!emulator.is_eight_bit());
+    assert_eq!(emulator.snapshot(4, 1), "|ok  |\n");
+}
+
+#[test]
+fn power_and_graphic_mode() {
+    let vec = test(FunctionMode::Bit8, |lcd| {
+        lcd.set_graphic_mode(true);
+        lcd.set_power(false);
+        lcd.set_graphic_mode(false);
+        lcd.set_power(true);
+    });
+    assert_eq!(
+        vec,
+        vec![
+            "R/S false",
+            "DATA 0b00011111",
+            "R/S false",
+            "DATA 0b00011011",
+            "R/S false",
+            "DATA 0b00010011",
+            "R/S false",
+            "DATA 0b00010111",
+        ]
+    );
+}
+
+#[test]
+fn draw() {
+    let vec = test(FunctionMode::Bit8, |lcd| {
+        let mut buffer = GraphicBuffer::new();
+        lcd.draw(&mut buffer);
+        buffer.set_pixel(3, 9, true);
+        buffer.set_pixel(5, 15, true);
+        buffer.set_pixel(5, 15, true);
+        assert!(buffer.pixel(3, 9));
+        assert!(!buffer.pixel(3, 1));
+        lcd.draw(&mut buffer);
+    });
+
+    // Initial draw of the whole buffer: two rows of 100 columns
+    assert_eq!(vec.len(), 2 * (4 + 2 * 100) + 10);
+    assert_eq!(
+        vec[vec.len() - 10..].to_vec(),
+        vec![
+            "R/S false",
+            "DATA 0b10000011",
+            "R/S false",
+            "DATA 0b01000001",
+            "R/S true",
+            "DATA 0b00000010",
+            "R/S true",
+            "DATA 0b00000000",
+            "R/S true",
+            "DATA 0b10000000",
+        ]
+    );
+}